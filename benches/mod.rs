@@ -45,3 +45,16 @@ fn parse_and_render_20_000() {
     assert!(!mathml.is_empty());
     divan::black_box_drop(mathml);
 }
+
+#[divan::bench]
+fn parse_and_stream_10_000() {
+    let src = "gammag gammag gammag gammag gammag ".repeat(10_000);
+    let options = mathemascii::RenderOptions::default();
+
+    let exprs = mathemascii::parse(divan::black_box(&src));
+    let mut mathml = String::new();
+    mathemascii::stream_mathml(divan::black_box(exprs), &options, &mut mathml).unwrap();
+
+    assert!(!mathml.is_empty());
+    divan::black_box_drop(mathml);
+}
@@ -0,0 +1,36 @@
+//! Language server for AsciiMath. Communicates with the editor over stdin and stdout, and offers
+//! diagnostics, hover information, keyword completion, bracket matching and formatting.
+
+mod position;
+mod server;
+
+use lsp_server::Connection;
+use lsp_types::{
+    CompletionOptions, HoverProviderCapability, OneOf, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind,
+};
+
+use server::{Error, Server};
+
+fn main() -> Result<(), Error> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::INCREMENTAL,
+        )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        document_highlight_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    };
+
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+    Server::default().run(&connection)?;
+
+    drop(connection);
+    io_threads.join()?;
+
+    Ok(())
+}
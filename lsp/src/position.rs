@@ -0,0 +1,90 @@
+//! Conversion between LSP positions, which count lines and UTF-16 code units, and the character
+//! offsets used by mathemascii.
+
+use lsp_types::{Position, Range};
+use mathemascii::Span;
+
+/// Returns the character offset of the position in the source. Positions past the end of a line
+/// are clamped to the end of that line, positions past the end of the source to its end.
+pub(crate) fn offset(source: &str, position: Position) -> usize {
+    let mut line = 0;
+    let mut column = 0;
+
+    for (offset, c) in source.chars().enumerate() {
+        if line == position.line && (column >= position.character || c == '\n') {
+            return offset;
+        }
+
+        if c == '\n' {
+            line += 1;
+            column = 0;
+        } else {
+            column += c.len_utf16() as u32;
+        }
+    }
+
+    source.chars().count()
+}
+
+/// Returns the position of the character offset in the source.
+pub(crate) fn position(source: &str, offset: usize) -> Position {
+    let mut position = Position::new(0, 0);
+
+    for c in source.chars().take(offset) {
+        if c == '\n' {
+            position.line += 1;
+            position.character = 0;
+        } else {
+            position.character += c.len_utf16() as u32;
+        }
+    }
+
+    position
+}
+
+/// Returns the range of the span in the source.
+pub(crate) fn range(source: &str, span: Span) -> Range {
+    Range::new(position(source, span.start), position(source, span.end))
+}
+
+/// Returns the range of the whole source.
+pub(crate) fn full_range(source: &str) -> Range {
+    Range::new(
+        Position::new(0, 0),
+        position(source, source.chars().count()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_and_positions() {
+        let source = "a + b\nc -> 𝔸 d\n";
+
+        let cases = [
+            (Position::new(0, 0), 0),
+            (Position::new(0, 4), 4),
+            (Position::new(1, 0), 6),
+            (Position::new(1, 5), 11),
+            // `𝔸` takes two UTF-16 code units
+            (Position::new(1, 7), 12),
+            (Position::new(1, 8), 13),
+            (Position::new(2, 0), 15),
+        ];
+
+        for (pos, offs) in cases {
+            assert_eq!(offset(source, pos), offs, "offset of {pos:?}");
+            assert_eq!(position(source, offs), pos, "position of {offs}");
+        }
+    }
+
+    #[test]
+    fn clamps_offsets() {
+        let source = "ab\ncd";
+
+        assert_eq!(offset(source, Position::new(0, 10)), 2);
+        assert_eq!(offset(source, Position::new(5, 0)), 5);
+    }
+}
@@ -0,0 +1,303 @@
+//! Handlers of the requests and notifications sent by the editor.
+
+use std::collections::HashMap;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+        PublishDiagnostics,
+    },
+    request::{Completion, DocumentHighlightRequest, Formatting, HoverRequest, Request as _},
+    CompletionItem, CompletionItemKind, CompletionParams, CompletionResponse, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DocumentFormattingParams, DocumentHighlight, DocumentHighlightKind, DocumentHighlightParams,
+    Documentation, Hover, HoverContents, HoverParams, MarkupContent, MarkupKind,
+    PublishDiagnosticsParams, Range, TextEdit, Uri,
+};
+use mathemascii::{ide, Document, Limits, Span};
+use serde_json::Value;
+
+use crate::position;
+
+/// Error returned by the server loop, only in case the connection to the editor fails.
+pub(crate) type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Language server keeping track of the documents opened in the editor.
+#[derive(Debug, Default)]
+pub(crate) struct Server {
+    documents: HashMap<Uri, Document>,
+
+    /// Limits the documents are parsed with, so that deeply nested sources can't overflow the
+    /// stack of the server.
+    limits: Limits,
+}
+
+impl Server {
+    /// Handles the messages sent by the editor until it requests the server to shut down.
+    pub(crate) fn run(&mut self, connection: &Connection) -> Result<(), Error> {
+        for msg in &connection.receiver {
+            match msg {
+                Message::Request(req) => {
+                    if connection.handle_shutdown(&req)? {
+                        return Ok(());
+                    }
+
+                    let response = self.request(req);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    for notification in self.notification(notification) {
+                        connection
+                            .sender
+                            .send(Message::Notification(notification))?;
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn request(&self, req: Request) -> Response {
+        let id = req.id.clone();
+
+        let result = match req.method.as_str() {
+            HoverRequest::METHOD => handle(req.params, |p| self.hover(p)),
+            Completion::METHOD => handle(req.params, |p| self.completion(p)),
+            DocumentHighlightRequest::METHOD => handle(req.params, |p| self.highlight(p)),
+            Formatting::METHOD => handle(req.params, |p| self.format(p)),
+            method => {
+                let message = format!("unsupported request `{method}`");
+                return Response::new_err(id, ErrorCode::MethodNotFound as i32, message);
+            }
+        };
+
+        match result {
+            Ok(result) => Response::new_ok(id, result),
+            Err(err) => Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()),
+        }
+    }
+
+    /// Handles the notification, and returns the notifications to be sent back to the editor.
+    fn notification(&mut self, notification: Notification) -> Vec<Notification> {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Ok(params) = parse::<DidOpenTextDocumentParams>(notification.params) else {
+                    return Vec::new();
+                };
+
+                let doc = Document::with_limits(params.text_document.text, &self.limits);
+                let uri = params.text_document.uri;
+
+                self.documents.insert(uri.clone(), doc);
+                uri
+            }
+            DidChangeTextDocument::METHOD => {
+                let Ok(params) = parse::<DidChangeTextDocumentParams>(notification.params) else {
+                    return Vec::new();
+                };
+
+                let uri = params.text_document.uri;
+                let doc = self
+                    .documents
+                    .entry(uri.clone())
+                    .or_insert_with(|| Document::with_limits("", &self.limits));
+
+                for change in params.content_changes {
+                    match change.range {
+                        Some(range) => {
+                            let start = position::offset(doc.source(), range.start);
+                            let end = position::offset(doc.source(), range.end);
+
+                            doc.edit(start.min(end)..end.max(start), &change.text);
+                        }
+                        None => *doc = Document::with_limits(change.text, &self.limits),
+                    }
+                }
+
+                uri
+            }
+            DidCloseTextDocument::METHOD => {
+                let Ok(params) = parse::<DidCloseTextDocumentParams>(notification.params) else {
+                    return Vec::new();
+                };
+
+                self.documents.remove(&params.text_document.uri);
+                params.text_document.uri
+            }
+            _ => return Vec::new(),
+        };
+
+        vec![self.diagnostics(uri)]
+    }
+
+    fn diagnostics(&self, uri: Uri) -> Notification {
+        let diagnostics = self
+            .documents
+            .get(&uri)
+            .map(|doc| {
+                let mut diagnostics: Vec<_> = ide::diagnostics(doc)
+                    .into_iter()
+                    .map(|d| lsp_types::Diagnostic {
+                        range: position::range(doc.source(), d.span),
+                        severity: Some(DiagnosticSeverity::WARNING),
+                        source: Some(String::from("mathemascii")),
+                        message: d.message,
+                        ..Default::default()
+                    })
+                    .collect();
+
+                // the document is parsed only up to the expression exceeding the limit
+                if let Some(err) = doc.exceeded() {
+                    let span = Span {
+                        start: doc.exprs().last().map_or(0, |e| e.span().end),
+                        end: doc.source().chars().count(),
+                    };
+
+                    diagnostics.push(lsp_types::Diagnostic {
+                        range: position::range(doc.source(), span),
+                        severity: Some(DiagnosticSeverity::ERROR),
+                        source: Some(String::from("mathemascii")),
+                        message: format!("{err}, the rest of the document is not parsed"),
+                        ..Default::default()
+                    });
+                }
+
+                diagnostics
+            })
+            .unwrap_or_default();
+
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+
+        Notification::new(PublishDiagnostics::METHOD.to_string(), params)
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let doc = self.documents.get(&position.text_document.uri)?;
+
+        let offset = position::offset(doc.source(), position.position);
+        let hover = ide::hover(doc, offset)?;
+
+        let mut value = match hover.symbol {
+            Some(symbol) => format!("**{symbol}** {}", hover.description),
+            None => hover.description,
+        };
+
+        if !hover.aliases.is_empty() {
+            value.push_str("\n\nKeywords: ");
+            value.push_str(&keywords(hover.aliases));
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(position::range(doc.source(), hover.span)),
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let doc = self.documents.get(&position.text_document.uri)?;
+
+        let end = position::offset(doc.source(), position.position);
+        let prefix: String = {
+            let before: Vec<_> = doc.source().chars().take(end).collect();
+            let len = before
+                .iter()
+                .rev()
+                .take_while(|c| c.is_ascii_alphabetic())
+                .count();
+
+            before[end - len..].iter().collect()
+        };
+
+        let range = Range::new(
+            position::position(doc.source(), end - prefix.chars().count()),
+            position.position,
+        );
+
+        let items = ide::completions(&prefix)
+            .into_iter()
+            .map(|c| CompletionItem {
+                label: c.keyword.to_string(),
+                kind: Some(CompletionItemKind::KEYWORD),
+                detail: Some(match c.symbol {
+                    Some(symbol) => format!("{symbol} {}", c.description),
+                    None => c.description,
+                }),
+                documentation: Some(Documentation::String(format!(
+                    "Keywords: {}",
+                    keywords(c.aliases)
+                ))),
+                text_edit: Some(TextEdit::new(range, c.keyword.to_string()).into()),
+                ..Default::default()
+            })
+            .collect();
+
+        Some(CompletionResponse::Array(items))
+    }
+
+    fn highlight(&self, params: DocumentHighlightParams) -> Option<Vec<DocumentHighlight>> {
+        let position = params.text_document_position_params;
+        let doc = self.documents.get(&position.text_document.uri)?;
+
+        let offset = position::offset(doc.source(), position.position);
+        let (open, close) = ide::matching_bracket(doc, offset)?;
+
+        let highlights = [open, close]
+            .into_iter()
+            .map(|span| DocumentHighlight {
+                range: position::range(doc.source(), span),
+                kind: Some(DocumentHighlightKind::TEXT),
+            })
+            .collect();
+
+        Some(highlights)
+    }
+
+    fn format(&self, params: DocumentFormattingParams) -> Option<Vec<TextEdit>> {
+        let doc = self.documents.get(&params.text_document.uri)?;
+        let formatted = ide::format(doc);
+
+        if formatted == doc.source() {
+            return Some(Vec::new());
+        }
+
+        let range = position::full_range(doc.source());
+        Some(vec![TextEdit::new(range, formatted)])
+    }
+}
+
+fn parse<P: serde::de::DeserializeOwned>(params: Value) -> Result<P, serde_json::Error> {
+    serde_json::from_value(params)
+}
+
+/// Parses the request parameters and serializes the result of the handler.
+fn handle<P, R>(params: Value, handler: impl FnOnce(P) -> R) -> Result<Value, serde_json::Error>
+where
+    P: serde::de::DeserializeOwned,
+    R: serde::Serialize,
+{
+    serde_json::to_value(handler(parse(params)?))
+}
+
+/// Lists the keywords as inline code, e.g. `` `!=`, `ne` ``.
+fn keywords(aliases: &[&str]) -> String {
+    aliases
+        .iter()
+        .map(|k| format!("`{k}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests;
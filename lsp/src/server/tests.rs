@@ -0,0 +1,130 @@
+use lsp_server::{Notification, Request, RequestId};
+use lsp_types::{
+    notification::{DidChangeTextDocument, DidOpenTextDocument, Notification as _},
+    request::{Completion, DocumentHighlightRequest, Formatting, HoverRequest, Request as _},
+    Position, PublishDiagnosticsParams, Range,
+};
+use serde_json::{json, Value};
+
+use super::Server;
+
+const URI: &str = "file:///math.am";
+
+fn open(server: &mut Server, text: &str) -> PublishDiagnosticsParams {
+    let params = json!({
+        "textDocument": { "uri": URI, "languageId": "asciimath", "version": 1, "text": text }
+    });
+
+    let notification = Notification::new(DidOpenTextDocument::METHOD.to_string(), params);
+    let published = server.notification(notification);
+
+    serde_json::from_value(published[0].params.clone()).unwrap()
+}
+
+fn change(server: &mut Server, range: Range, text: &str) -> PublishDiagnosticsParams {
+    let params = json!({
+        "textDocument": { "uri": URI, "version": 2 },
+        "contentChanges": [{ "range": range, "text": text }]
+    });
+
+    let notification = Notification::new(DidChangeTextDocument::METHOD.to_string(), params);
+    let published = server.notification(notification);
+
+    serde_json::from_value(published[0].params.clone()).unwrap()
+}
+
+fn request(server: &Server, method: &str, position: Position) -> Value {
+    let params = json!({
+        "textDocument": { "uri": URI },
+        "position": position,
+        "options": { "tabSize": 4, "insertSpaces": true }
+    });
+
+    let response = server.request(Request::new(RequestId::from(1), method.into(), params));
+    assert!(response.error.is_none());
+
+    response.result.unwrap()
+}
+
+#[test]
+fn publishes_diagnostics_on_change() {
+    let mut server = Server::default();
+
+    let published = open(&mut server, "(a + b");
+    assert_eq!(published.diagnostics.len(), 1);
+    assert_eq!(published.diagnostics[0].message, "`(` is not closed");
+
+    let end = Position::new(0, 6);
+    let published = change(&mut server, Range::new(end, end), ")");
+    assert_eq!(published.diagnostics, vec![]);
+}
+
+#[test]
+fn deeply_nested_documents() {
+    let mut server = Server::default();
+
+    let published = open(&mut server, &"(".repeat(5000));
+    let last = published.diagnostics.last().unwrap();
+    assert_eq!(
+        last.message,
+        "expressions are nested deeper than 64 levels, the rest of the document is not parsed"
+    );
+
+    let start = Position::new(0, 2500);
+    let published = change(&mut server, Range::new(start, start), &"sqrt ".repeat(5000));
+    assert!(published
+        .diagnostics
+        .last()
+        .unwrap()
+        .message
+        .contains("64 levels"));
+
+    let published = change(
+        &mut server,
+        Range::new(Position::new(0, 0), Position::new(0, 30000)),
+        "(a)",
+    );
+    assert_eq!(published.diagnostics, vec![]);
+}
+
+#[test]
+fn hover_and_completion() {
+    let mut server = Server::default();
+    open(&mut server, "a != b\nx in ri");
+
+    let hover = request(&server, HoverRequest::METHOD, Position::new(0, 2));
+    assert_eq!(
+        hover["contents"]["value"],
+        "**≠** Relation NotEq\n\nKeywords: `!=`, `ne`"
+    );
+
+    let completion = request(&server, Completion::METHOD, Position::new(1, 7));
+    let first = &completion[0];
+
+    assert_eq!(first["label"], "rightarrow");
+    assert_eq!(first["detail"], "→ Arrow Right");
+    assert_eq!(
+        first["textEdit"]["range"],
+        json!(Range::new(Position::new(1, 5), Position::new(1, 7)))
+    );
+}
+
+#[test]
+fn highlight_and_format() {
+    let mut server = Server::default();
+    open(&mut server, "sum_(i=0)^n i");
+
+    let highlights = request(
+        &server,
+        DocumentHighlightRequest::METHOD,
+        Position::new(0, 4),
+    );
+    assert_eq!(highlights.as_array().unwrap().len(), 2);
+    assert_eq!(
+        highlights[1]["range"],
+        json!(Range::new(Position::new(0, 8), Position::new(0, 9)))
+    );
+
+    let edits = request(&server, Formatting::METHOD, Position::new(0, 0));
+    assert_eq!(edits[0]["newText"], "sum_(i = 0)^n i");
+}
@@ -0,0 +1,124 @@
+//! Parallel rendering of many independent inputs, e.g. all the formulas of a site.
+
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::{
+    ide::{self, Diagnostic},
+    parse_limited, render, Document, LimitError, Limits, RenderOptions,
+};
+
+/// Options for rendering many inputs with [`render_batch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// Options every input is rendered with.
+    pub render: RenderOptions,
+
+    /// Limits every input is parsed and rendered within, like with
+    /// [`render_mathml_limited`](crate::render_mathml_limited). Limited output is sanitized like
+    /// the output of that function, regardless of [`RenderOptions::safe`]. By default, the inputs
+    /// are limited with [`Limits::default`]. Without limits, a deeply nested input can overflow
+    /// the stack, which aborts the process.
+    pub limits: Option<Limits>,
+
+    /// Whether the [`Diagnostic`]s of every input are collected. Enabled by default.
+    pub diagnostics: bool,
+
+    /// Whether identical inputs are rendered only once, and their output is copied to all of
+    /// them. Enabled by default.
+    pub dedup: bool,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            render: RenderOptions::default(),
+            limits: Some(Limits::default()),
+            diagnostics: true,
+            dedup: true,
+        }
+    }
+}
+
+/// Rendered input of a batch, see [`render_batch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchItem {
+    /// The rendered mathml, or the limit the input exceeds. The other inputs of the batch are
+    /// rendered regardless.
+    pub mathml: Result<String, LimitError>,
+
+    /// Problems found in the input, ordered by their position. Empty if the diagnostics are not
+    /// collected, or the input failed.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Parses and renders the inputs into mathml in parallel, on the global thread pool of
+/// [`rayon`]. The items are returned in the order of the inputs.
+///
+/// ```
+/// let items = mathemascii::render_batch(&["x^2", "sqrt(", "x^2"], &Default::default());
+///
+/// assert_eq!(items.len(), 3);
+/// assert_eq!(items[0], items[2]);
+/// assert_eq!(items[1].diagnostics[0].message, "`(` is not closed");
+/// ```
+pub fn render_batch<S>(inputs: &[S], options: &BatchOptions) -> Vec<BatchItem>
+where
+    S: AsRef<str> + Sync,
+{
+    if !options.dedup {
+        return inputs
+            .par_iter()
+            .map(|input| render_item(input.as_ref(), options))
+            .collect();
+    }
+
+    // index of the unique input that every input is a copy of
+    let mut unique = Vec::new();
+    let mut seen = HashMap::new();
+
+    let copies: Vec<usize> = inputs
+        .iter()
+        .map(|input| {
+            *seen.entry(input.as_ref()).or_insert_with(|| {
+                unique.push(input.as_ref());
+                unique.len() - 1
+            })
+        })
+        .collect();
+
+    let rendered: Vec<BatchItem> = unique
+        .par_iter()
+        .map(|input| render_item(input, options))
+        .collect();
+
+    copies.into_iter().map(|i| rendered[i].clone()).collect()
+}
+
+fn render_item(input: &str, options: &BatchOptions) -> BatchItem {
+    let mathml = match &options.limits {
+        Some(limits) => {
+            // limited inputs are untrusted, as with `render_mathml_limited`
+            let render = RenderOptions {
+                safe: true,
+                ..options.render.clone()
+            };
+
+            parse_limited(input, limits)
+                .and_then(|exprs| render::mathml::render_limited(exprs, &render, limits))
+        }
+        None => Ok(render::mathml::render(crate::parse(input), &options.render)),
+    };
+
+    // failed inputs are not parsed again for the diagnostics
+    let diagnostics = match options.diagnostics && mathml.is_ok() {
+        true => ide::diagnostics(&Document::new(input)),
+        false => Vec::new(),
+    };
+
+    BatchItem {
+        mathml,
+        diagnostics,
+    }
+}
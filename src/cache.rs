@@ -0,0 +1,419 @@
+//! Cache of rendered output, for documents that repeat the same formulas.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{
+    convert::ColorArgument,
+    keywords::{self, Variant},
+    lexer::{keywords::others::Other, Token, TokenIterator, TokenKind},
+    ArtOptions, SvgOptions,
+};
+
+#[cfg(feature = "mathml")]
+use crate::{Direction, DisplayMode, RenderOptions};
+
+/// First line of persisted caches. Caches of other versions are not loaded, since the output of
+/// the renderers may differ between them.
+const HEADER: &str = concat!("mathemascii render cache ", env!("CARGO_PKG_VERSION"));
+
+/// Output format and options the input is rendered with by the [`RenderCache`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Backend {
+    /// MathMl, like [`render_mathml_with`](crate::render_mathml_with).
+    #[cfg(feature = "mathml")]
+    MathMl(RenderOptions),
+
+    /// Typst math markup, like [`render_typst`](crate::render_typst).
+    Typst,
+
+    /// Office Math Markup Language, like [`render_omml`](crate::render_omml).
+    Omml,
+
+    /// HTML, like [`render_html`](crate::render_html).
+    Html,
+
+    /// Text art, like [`render_art`](crate::render_art).
+    Art(ArtOptions),
+
+    /// SVG image, like [`render_svg`](crate::render_svg).
+    Svg(SvgOptions),
+}
+
+impl Backend {
+    fn render(&self, input: &str) -> String {
+        let ascii_math = crate::parse(input);
+
+        match self {
+            #[cfg(feature = "mathml")]
+            Backend::MathMl(options) => crate::render_mathml_with(ascii_math, options),
+            Backend::Typst => crate::render_typst(ascii_math),
+            Backend::Omml => crate::render_omml(ascii_math),
+            Backend::Html => crate::render_html(ascii_math),
+            Backend::Art(options) => crate::render_art(ascii_math, *options),
+            Backend::Svg(options) => crate::render_svg(ascii_math, options),
+        }
+    }
+
+    /// Writes the backend with all its options into the key. The options are written one by
+    /// one, so that the keys of saved caches stay the same as long as the options do.
+    fn write_key(&self, key: &mut String) {
+        match self {
+            #[cfg(feature = "mathml")]
+            Backend::MathMl(options) => {
+                let RenderOptions {
+                    display,
+                    alttext,
+                    xmlns,
+                    indent,
+                    dir,
+                    mathsize,
+                    wrap_groupings,
+                    safe,
+                } = options;
+
+                let display = match display {
+                    DisplayMode::Inline => "inline",
+                    DisplayMode::Block => "block",
+                };
+
+                let dir = match dir {
+                    Some(Direction::LeftToRight) => "ltr",
+                    Some(Direction::RightToLeft) => "rtl",
+                    None => "none",
+                };
+
+                let _ = write!(key, "mathml display={display} dir={dir} xmlns={xmlns}");
+                let _ = write!(key, " wrap_groupings={wrap_groupings} safe={safe}");
+
+                let _ = match indent {
+                    Some(indent) => write!(key, " indent={indent}"),
+                    None => write!(key, " indent=none"),
+                };
+
+                write_str(key, "alttext", alttext.as_deref());
+                write_str(key, "mathsize", mathsize.as_deref());
+            }
+            Backend::Typst => key.push_str("typst"),
+            Backend::Omml => key.push_str("omml"),
+            Backend::Html => key.push_str("html"),
+            Backend::Art(ArtOptions { width, ascii }) => {
+                let _ = write!(key, "art width={width} ascii={ascii}");
+            }
+            Backend::Svg(SvgOptions { font_size, color }) => {
+                let _ = write!(key, "svg font_size={font_size}");
+                write_str(key, "color", Some(color));
+            }
+        }
+    }
+}
+
+/// Writes the string option into the key with its length, so that its content can't be mistaken
+/// for other options.
+fn write_str(key: &mut String, name: &str, value: Option<&str>) {
+    let _ = match value {
+        Some(value) => write!(key, " {name}={}:{value}", value.len()),
+        None => write!(key, " {name}=none"),
+    };
+}
+
+/// Cache of rendered output, keyed by the backend with its options and the normalized input.
+///
+/// Inputs that differ only in whitespace or in the spelling of keywords other than groupings, like
+/// `a -> b` and `a rarr  b`, share the same entry. The cache holds at most the given number of
+/// entries, and evicts the least recently used ones. It can be saved to a file and loaded in a later run, e.g.
+/// between builds of a site:
+///
+/// ```no_run
+/// # #[cfg(feature = "mathml")] {
+/// use mathemascii::{Backend, RenderCache};
+///
+/// let mut cache = RenderCache::load("math.cache", 10_000)?;
+/// let backend = Backend::MathMl(Default::default());
+///
+/// let mathml = cache.render("sum_(i=1)^n i", &backend);
+/// # let _ = mathml;
+///
+/// cache.save("math.cache")?;
+/// # }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenderCache {
+    capacity: usize,
+    entries: HashMap<Arc<str>, Entry>,
+
+    /// Keys of the entries by their last use, the least recently used first.
+    recent: BTreeMap<u64, Arc<str>>,
+    tick: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    output: String,
+    used: u64,
+}
+
+impl RenderCache {
+    /// Creates an empty cache that holds at most the given number of entries. The cache holds at
+    /// least one entry, the output returned last.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            recent: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Returns the maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recent.clear();
+    }
+
+    /// Returns the input rendered with the backend, from the cache if it was rendered before.
+    pub fn render(&mut self, input: &str, backend: &Backend) -> &str {
+        let key = key(input, backend);
+
+        if !self.entries.contains_key(key.as_str()) {
+            let output = backend.render(input);
+            self.insert(Arc::from(key.as_str()), output);
+        }
+
+        self.touch(&key)
+    }
+
+    /// Returns the cached output of the input rendered with the backend, without rendering it.
+    pub fn get(&mut self, input: &str, backend: &Backend) -> Option<&str> {
+        let key = key(input, backend);
+
+        match self.entries.contains_key(key.as_str()) {
+            true => Some(self.touch(&key)),
+            false => None,
+        }
+    }
+
+    /// Marks the entry as the most recently used one, and returns its output.
+    fn touch(&mut self, key: &str) -> &str {
+        let entry = self.entries.get_mut(key).expect("entry is cached");
+
+        if let Some(key) = self.recent.remove(&entry.used) {
+            self.tick += 1;
+            entry.used = self.tick;
+            self.recent.insert(self.tick, key);
+        }
+
+        &entry.output
+    }
+
+    fn insert(&mut self, key: Arc<str>, output: String) {
+        while self.entries.len() >= self.capacity {
+            let Some((_, oldest)) = self.recent.pop_first() else {
+                break;
+            };
+
+            self.entries.remove(&oldest);
+        }
+
+        self.tick += 1;
+        self.recent.insert(self.tick, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                output,
+                used: self.tick,
+            },
+        );
+    }
+
+    /// Writes the entries into the writer, the least recently used first, see
+    /// [`RenderCache::read_from`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the writer, if writing into it fails.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "{HEADER}")?;
+
+        for key in self.recent.values() {
+            let output = &self.entries[key].output;
+
+            // lengths first, since keys and outputs can contain line breaks
+            writeln!(writer, "{} {}", key.len(), output.len())?;
+            writer.write_all(key.as_bytes())?;
+            writer.write_all(output.as_bytes())?;
+            writeln!(writer)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Reads the entries written with [`RenderCache::write_to`] into a cache with the given
+    /// capacity. Entries written by other versions of this crate are skipped, since their output
+    /// may be stale, so the cache is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the reader, or an error of kind [`io::ErrorKind::InvalidData`] if the
+    /// data is not a cache.
+    pub fn read_from(reader: impl Read, capacity: usize) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut cache = Self::new(capacity);
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+
+        reader.read_line(&mut line)?;
+
+        if !line.starts_with("mathemascii render cache ") {
+            return Err(invalid("not a render cache"));
+        }
+
+        if line.trim_end() != HEADER {
+            return Ok(cache);
+        }
+
+        loop {
+            line.clear();
+
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(cache);
+            }
+
+            let lengths = line.trim_end().split_once(' ').and_then(|(key, output)| {
+                Some((key.parse::<usize>().ok()?, output.parse::<usize>().ok()?))
+            });
+
+            let Some((key_len, output_len)) = lengths else {
+                return Err(invalid("invalid lengths of a render cache entry"));
+            };
+
+            // read up to the lengths, so that invalid lengths don't allocate more than the data
+            let len = key_len.saturating_add(output_len).saturating_add(1);
+            let mut entry = Vec::new();
+            reader.by_ref().take(len as u64).read_to_end(&mut entry)?;
+
+            if entry.len() != len || entry.pop() != Some(b'\n') {
+                return Err(invalid("render cache entry is not terminated"));
+            }
+
+            let mut entry =
+                String::from_utf8(entry).map_err(|_| invalid("render cache is not UTF-8"))?;
+
+            if !entry.is_char_boundary(key_len) {
+                return Err(invalid("invalid lengths of a render cache entry"));
+            }
+
+            let output = entry.split_off(key_len);
+            cache.insert(Arc::from(entry), output);
+        }
+    }
+
+    /// Saves the cache into the file at the given path, see [`RenderCache::write_to`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of creating or writing the file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_to(io::BufWriter::new(fs::File::create(path)?))
+    }
+
+    /// Loads the cache saved into the file at the given path, with the given capacity. A missing
+    /// file gives an empty cache, like the first build of a site. See
+    /// [`RenderCache::read_from`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of reading the file, or an error of kind [`io::ErrorKind::InvalidData`]
+    /// if the file is not a cache.
+    pub fn load(path: impl AsRef<Path>, capacity: usize) -> io::Result<Self> {
+        match fs::File::open(path) {
+            Ok(file) => Self::read_from(file, capacity),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::new(capacity)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Returns the key of the input rendered with the backend.
+fn key(input: &str, backend: &Backend) -> String {
+    let mut key = String::new();
+
+    backend.write_key(&mut key);
+    key.push('\n');
+    key.push_str(&normalize(input));
+
+    key
+}
+
+/// Normalizes the input, so that inputs rendered into the same output have the same key. The
+/// tokens are separated by single spaces, and keywords are replaced by the primary keyword of
+/// their symbol, i.e. `a rarr  b` becomes `a -> b`.
+///
+/// Groupings keep their spelling, since unmatched ones are rendered as they're written, and so
+/// does the argument of `color`, which is used as it's written. The normalized input is lexed into
+/// the same tokens as the input, except for the spelling of the keywords. Otherwise, e.g. for
+/// texts containing quotes, the input is returned unchanged.
+pub(crate) fn normalize(input: &str) -> String {
+    let tokens: Vec<_> = TokenIterator::tokenize(input).collect();
+    let mut normalized = String::with_capacity(input.len());
+    let mut color = ColorArgument::default();
+
+    for token in &tokens {
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+
+        let verbatim = color.next(*token) || matches!(token.kind(), TokenKind::Grouping(_));
+
+        match Variant::from_token(token.kind()) {
+            Some(variant) if !verbatim => normalized.push_str(keywords::symbol(variant).keyword()),
+            None if is_text(token) => {
+                normalized.push('"');
+                normalized.push_str(token.as_str());
+                normalized.push('"');
+            }
+            _ => normalized.push_str(token.as_str()),
+        }
+    }
+
+    let mut lexed = TokenIterator::tokenize(&normalized);
+
+    let same = tokens.iter().all(|token| {
+        lexed.next().is_some_and(|t| {
+            t.kind() == token.kind()
+                && (Variant::from_token(t.kind()).is_some() || t.as_str() == token.as_str())
+        })
+    }) && lexed.next().is_none();
+
+    match same {
+        true => normalized,
+        false => input.to_string(),
+    }
+}
+
+fn is_text(token: &Token<'_>) -> bool {
+    token.kind() == TokenKind::Other(Other::Text)
+}
@@ -0,0 +1,130 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    keywords::{self, Variant},
+    lexer::{
+        keywords::{accents::Accent, groupings::Grouping},
+        Token, TokenIterator, TokenKind,
+    },
+};
+
+/// Rewrites Unicode math characters into the keywords they're rendered from, keeping the rest of
+/// the input as it is. Spaces are inserted only where the keyword would otherwise merge with its
+/// neighbor or where two words would touch, e.g. `x∈y` becomes `x in y`, but `α≤β` becomes
+/// `alpha<=beta`.
+pub(crate) fn unicode_to_asciimath(input: &str) -> String {
+    // byte offsets of all the characters, and of the end of input
+    let offsets: Vec<_> = input
+        .char_indices()
+        .map(|(offs, _)| offs)
+        .chain([input.len()])
+        .collect();
+
+    let mut out = String::with_capacity(input.len());
+    let mut prev: Option<(Token<'_>, &str, bool)> = None;
+    let mut color = ColorArgument::default();
+    let mut end = 0;
+
+    for token in TokenIterator::tokenize(input) {
+        let span = token.span();
+
+        // the color is used as it's written, like a text
+        let verbatim = color.next(token);
+
+        let (text, rewritten) = match keyword(token).filter(|_| !verbatim) {
+            Some(keyword) => (keyword, true),
+            None => (&input[offsets[span.start]..offsets[span.end]], false),
+        };
+
+        if let Some((prev, prev_text, prev_rewritten)) = prev {
+            let adjacent = span.start == end && (rewritten || prev_rewritten);
+
+            // words are kept apart for readability, e.g. `inRR` would be lexed correctly too
+            let words = prev_text.ends_with(|c: char| c.is_ascii_alphanumeric())
+                && text.starts_with(|c: char| c.is_ascii_alphanumeric());
+
+            if adjacent && (words || !lexes_apart(prev, prev_text, token, text)) {
+                out.push(' ');
+            }
+        }
+
+        out.push_str(&input[offsets[end]..offsets[span.start]]);
+        out.push_str(text);
+
+        prev = Some((token, text, rewritten));
+        end = span.end;
+    }
+
+    out.push_str(&input[offsets[end]..]);
+    out
+}
+
+/// Position relative to the argument of `color`, which the parser takes as it's written, see
+/// `Parser::parse_grouping_as_str`.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) enum ColorArgument {
+    #[default]
+    Outside,
+    Expected,
+    Inside {
+        opening: Grouping,
+        depth: usize,
+    },
+}
+
+impl ColorArgument {
+    /// Moves past the token, returns `true` if the token is part of the argument.
+    pub(crate) fn next(&mut self, token: Token<'_>) -> bool {
+        let (next, inside) = match (*self, token.kind()) {
+            (Self::Expected, TokenKind::Grouping(opening)) => {
+                (Self::Inside { opening, depth: 0 }, true)
+            }
+            (Self::Inside { opening, depth }, TokenKind::Grouping(grouping))
+                if grouping == opening =>
+            {
+                (
+                    Self::Inside {
+                        opening,
+                        depth: depth + 1,
+                    },
+                    true,
+                )
+            }
+            (Self::Inside { opening, depth }, TokenKind::Grouping(grouping))
+                if opening.matches(grouping) =>
+            {
+                match depth.checked_sub(1) {
+                    Some(depth) => (Self::Inside { opening, depth }, true),
+                    None => (Self::Outside, true),
+                }
+            }
+            (Self::Inside { .. }, _) => (*self, true),
+            (_, TokenKind::Accent(Accent::Color)) => (Self::Expected, false),
+            _ => (Self::Outside, false),
+        };
+
+        *self = next;
+        inside
+    }
+}
+
+/// Returns the primary keyword of the token, if it's a Unicode character of a keyword.
+fn keyword(token: Token<'_>) -> Option<&'static str> {
+    if token.as_str().is_ascii() {
+        return None;
+    }
+
+    let variant = Variant::from_token(token.kind())?;
+    Some(keywords::symbol(variant).keyword())
+}
+
+/// Returns `true` if the texts of the two tokens are lexed into the same two tokens when they're
+/// written next to each other.
+fn lexes_apart(first: Token<'_>, first_text: &str, second: Token<'_>, second_text: &str) -> bool {
+    let joined = format!("{first_text}{second_text}");
+    let mut lexed = TokenIterator::tokenize(&joined);
+
+    lexed.next().is_some_and(|t| t.kind() == first.kind())
+        && lexed.next().is_some_and(|t| t.kind() == second.kind())
+        && lexed.next().is_none()
+}
@@ -0,0 +1,309 @@
+//! Lossless concrete syntax tree of AsciiMath source, e.g. for refactoring tools. Unlike the
+//! [`Expression`]s produced by the parser, the tree keeps every token with its original spelling,
+//! and the whitespace between them, so the source is reproduced exactly:
+//!
+//! ```
+//! use mathemascii::cst::Cst;
+//!
+//! let input = "sum_(i=0)^n  i != oo";
+//! let cst = Cst::parse(input);
+//!
+//! assert_eq!(cst.to_string(), input);
+//! ```
+//!
+//! The tree has the same structure as the [`Expression`]s. Every node owns the tokens that are
+//! not part of its child nodes, i.e. a grouping owns its brackets and a unary operator its
+//! keyword, while the whitespace between the expressions is owned by the enclosing node.
+
+use alloc::{string::ToString, vec, vec::Vec};
+use core::fmt::Display;
+
+use crate::{
+    tokens::{self, Token},
+    Expression, SimpleExpr, Span, VarKind,
+};
+
+/// Concrete syntax tree of AsciiMath source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cst<'src> {
+    root: Node<'src>,
+}
+
+/// Kind of a node in the [`Cst`], corresponding to the types of the abstract syntax tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// The whole source, containing the top-level expressions.
+    Root,
+
+    /// An [`Expression`], containing the expression and its subscript and superscript.
+    Expression,
+
+    /// A variable, see [`Var`](crate::Var).
+    Var,
+
+    /// A grouping, see [`GroupingExpr`](crate::GroupingExpr).
+    Grouping,
+
+    /// A unary operator, see [`Unary`](crate::Unary).
+    Unary,
+
+    /// A binary operator, see [`Binary`](crate::Binary).
+    Binary,
+}
+
+/// Node of the [`Cst`], containing tokens and other nodes in the order of the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node<'src> {
+    /// Kind of the node.
+    pub kind: NodeKind,
+
+    /// Tokens and nodes of this node.
+    pub children: Vec<Child<'src>>,
+}
+
+/// Child of a [`Node`], either a token or another node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Child<'src> {
+    /// Nested node.
+    Node(Node<'src>),
+
+    /// Token, including whitespace.
+    Token(Token<'src>),
+}
+
+impl<'src> Cst<'src> {
+    /// Parses the AsciiMath source into a concrete syntax tree.
+    pub fn parse(input: &'src str) -> Self {
+        let exprs: Vec<_> = crate::parse(input).collect();
+
+        let mut builder = Builder {
+            tokens: tokens::tokenize(input).peekable(),
+        };
+
+        let children = exprs.iter().map(Ast::Expr).collect();
+        let mut root = builder.node(NodeKind::Root, children, None);
+
+        // tokens the expressions don't cover, i.e. the trailing whitespace
+        root.children.extend(builder.tokens.map(Child::Token));
+
+        Cst { root }
+    }
+
+    /// Returns the root node.
+    pub fn root(&self) -> &Node<'src> {
+        &self.root
+    }
+
+    /// Returns the root node for editing. The spans of the tokens are not updated when the tree
+    /// is edited, they keep referring to the original source.
+    pub fn root_mut(&mut self) -> &mut Node<'src> {
+        &mut self.root
+    }
+
+    /// Converts the tree into the abstract syntax tree. The tree is lossless, so the expressions
+    /// are parsed from its source, and reflect all the edits made to the tree.
+    pub fn to_ast(&self) -> Vec<Expression<'static>> {
+        let source = self.to_string();
+        crate::parse(source.as_str())
+            .map(Expression::into_owned)
+            .collect()
+    }
+}
+
+impl Display for Cst<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.root.fmt(f)
+    }
+}
+
+impl<'src> Node<'src> {
+    /// Returns the span of the node in the original source, from its first to its last token, or
+    /// `None` if the node has no tokens.
+    pub fn span(&self) -> Option<Span> {
+        let mut tokens = self.tokens();
+        let first = tokens.next()?;
+        let last = tokens.last().unwrap_or(first);
+
+        Some(Span {
+            start: first.span.start,
+            end: last.span.end,
+        })
+    }
+
+    /// Returns all the tokens of this node and its nested nodes, in the order of the source.
+    pub fn tokens(&self) -> impl Iterator<Item = &Token<'src>> {
+        let mut tokens = Vec::new();
+        self.walk(&mut |child| {
+            if let Child::Token(token) = child {
+                tokens.push(token);
+            }
+        });
+
+        tokens.into_iter()
+    }
+
+    /// Returns the nested nodes of this node, excluding the nodes nested further.
+    pub fn nodes(&self) -> impl Iterator<Item = &Node<'src>> {
+        self.children.iter().filter_map(|child| match child {
+            Child::Node(node) => Some(node),
+            Child::Token(_) => None,
+        })
+    }
+
+    /// Calls the given function for every token of this node and its nested nodes, in the order
+    /// of the source, e.g. to rename a variable.
+    pub fn for_each_token_mut(&mut self, f: &mut impl FnMut(&mut Token<'src>)) {
+        for child in &mut self.children {
+            match child {
+                Child::Node(node) => node.for_each_token_mut(f),
+                Child::Token(token) => f(token),
+            }
+        }
+    }
+
+    fn walk<'n>(&'n self, f: &mut impl FnMut(&'n Child<'src>)) {
+        for child in &self.children {
+            f(child);
+
+            if let Child::Node(node) = child {
+                node.walk(f);
+            }
+        }
+    }
+}
+
+impl Display for Node<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for child in &self.children {
+            match child {
+                Child::Node(node) => node.fmt(f)?,
+                Child::Token(token) => f.write_str(token.text)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Node of the abstract syntax tree the concrete syntax tree is built from.
+#[derive(Clone, Copy)]
+enum Ast<'e> {
+    Expr(&'e Expression<'e>),
+    Simple(&'e SimpleExpr<'e>),
+}
+
+impl<'e> Ast<'e> {
+    /// Returns the kind of the node and its children, in the order of the source.
+    fn children(self) -> (NodeKind, Vec<Ast<'e>>) {
+        let simple = match self {
+            Ast::Expr(expr) => {
+                let children = [
+                    Some(&expr.interm),
+                    expr.subscript.as_ref(),
+                    expr.supscript.as_ref(),
+                ];
+                let children = children.into_iter().flatten().map(Ast::Simple).collect();
+
+                return (NodeKind::Expression, children);
+            }
+            Ast::Simple(simple) => simple,
+        };
+
+        match simple {
+            SimpleExpr::Var(_) => (NodeKind::Var, Vec::new()),
+            SimpleExpr::Grouping(grp) => {
+                (NodeKind::Grouping, grp.expr.iter().map(Ast::Expr).collect())
+            }
+            SimpleExpr::Unary(unary) => (NodeKind::Unary, vec![Ast::Simple(&unary.expr)]),
+            SimpleExpr::Binary(binary) => (
+                NodeKind::Binary,
+                vec![Ast::Simple(&binary.expr_1), Ast::Simple(&binary.expr_2)],
+            ),
+            SimpleExpr::Interm(expr) => Ast::Expr(expr).children(),
+        }
+    }
+
+    /// Returns the span covered by the node and all its children, or `None` if the node was
+    /// inserted by the parser in place of a missing expression, and covers no tokens.
+    fn extent(self) -> Option<Span> {
+        let own = match self {
+            Ast::Expr(_) => None,
+            Ast::Simple(SimpleExpr::Var(var)) => match &var.kind {
+                // missing arguments of binary operators have the span of the operator
+                VarKind::UnknownOperator(op) if op.is_empty() => return None,
+                _ if var.span.start == var.span.end => return None,
+                _ => Some(var.span),
+            },
+            Ast::Simple(simple) => Some(simple.span()).filter(|s| s.start < s.end),
+        };
+
+        let (_, children) = self.children();
+
+        children
+            .into_iter()
+            .filter_map(Ast::extent)
+            .chain(own)
+            .reduce(|a, b| Span {
+                start: a.start.min(b.start),
+                end: a.end.max(b.end),
+            })
+    }
+}
+
+/// Assigns the tokens of the source to the nodes of the abstract syntax tree.
+struct Builder<'src> {
+    tokens: core::iter::Peekable<tokens::Tokens<'src>>,
+}
+
+impl<'src> Builder<'src> {
+    /// Builds the node of the given kind, from the given children, taking the tokens up to the
+    /// given end, or the tokens of the children if there's no end.
+    fn node(&mut self, kind: NodeKind, ast: Vec<Ast<'_>>, end: Option<usize>) -> Node<'src> {
+        let mut children = Vec::new();
+
+        for child in ast {
+            let (kind, nested) = child.children();
+
+            let Some(extent) = child.extent() else {
+                // placeholder of a missing expression, placed after the operator it belongs to
+                if let Ast::Simple(SimpleExpr::Var(var)) = child {
+                    self.take(&mut children, |t| t.span.end <= var.span.end);
+                }
+
+                children.push(Child::Node(placeholder(child)));
+                continue;
+            };
+
+            self.take(&mut children, |t| t.span.start < extent.start);
+
+            let node = self.node(kind, nested, Some(extent.end));
+            children.push(Child::Node(node));
+        }
+
+        if let Some(end) = end {
+            self.take(&mut children, |t| t.span.end <= end);
+        }
+
+        Node { kind, children }
+    }
+
+    /// Takes the tokens while they satisfy the predicate.
+    fn take(&mut self, children: &mut Vec<Child<'src>>, pred: impl Fn(&Token<'src>) -> bool) {
+        while let Some(token) = self.tokens.next_if(&pred) {
+            children.push(Child::Token(token));
+        }
+    }
+}
+
+/// Builds the node of an expression that covers no tokens.
+fn placeholder(ast: Ast<'_>) -> Node<'static> {
+    let (kind, children) = ast.children();
+
+    Node {
+        kind,
+        children: children
+            .into_iter()
+            .map(|child| Child::Node(placeholder(child)))
+            .collect(),
+    }
+}
@@ -0,0 +1,730 @@
+//! Incremental parsing of AsciiMath source that is edited over time, e.g. in live editors.
+
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    lexer::{keywords::others::Other, Span, Token, TokenIterator, TokenKind, MAX_LOOKAHEAD},
+    AsciiMath, Expression, GroupingExpr, LimitError, Limits, SimpleExpr,
+};
+
+/// Number of bytes of source lexed at once when re-lexing after an edit. The window grows if the
+/// re-lexed tokens don't line up with the old ones within it.
+const RELEX_WINDOW: usize = 64;
+
+/// AsciiMath source together with its tokens and parsed [`Expression`]s, that can be edited in
+/// place.
+///
+/// Editing the document reuses the unchanged parts of the token stream and the syntax tree. Only
+/// the tokens around the edited range are lexed again, and only the smallest grouping enclosing
+/// the changed tokens is parsed again. If no such grouping exists, the top-level expressions
+/// around the edit are parsed again. Spans of all expressions are kept consistent with the
+/// edited source.
+///
+/// # Example
+///
+/// ```
+/// let mut doc = mathemascii::Document::new("sum_(i=0)^n i");
+/// doc.edit(10..11, "k");
+///
+/// assert_eq!(doc.source(), "sum_(i=0)^k i");
+/// assert_eq!(doc.exprs(), mathemascii::parse("sum_(i=0)^k i").collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Document {
+    source: String,
+    tokens: Vec<TokenInfo>,
+    exprs: Vec<Expression<'static>>,
+
+    /// Index of the first token after each top-level expression.
+    bounds: Vec<usize>,
+
+    /// Maximum nesting depth of the expressions, and whether the source exceeds it. Parsing stops
+    /// at the expression that exceeds it, so the expressions don't cover the rest of the tokens.
+    max_depth: usize,
+    too_deep: bool,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self {
+            source: String::new(),
+            tokens: Vec::new(),
+            exprs: Vec::new(),
+            bounds: Vec::new(),
+            max_depth: usize::MAX,
+            too_deep: false,
+        }
+    }
+}
+
+/// Token stored independently of the source it was lexed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TokenInfo {
+    kind: TokenKind,
+    span: Span,
+
+    /// Byte offset of the token start in the source.
+    offset: usize,
+
+    /// Byte range of the token content in the source.
+    content: Range<usize>,
+
+    /// Whether the lexer looked at the rest of the input to lex this token, i.e. an unclosed
+    /// text.
+    unbounded: bool,
+}
+
+impl TokenInfo {
+    fn new(token: Token<'_>, source: &str, offset: usize) -> Self {
+        let start = token.as_str().as_ptr() as usize - source.as_ptr() as usize;
+        let head = &source[offset..];
+
+        let is_text = matches!(token.kind(), TokenKind::Other(Other::Text));
+        let unbounded = !is_text
+            && (head.starts_with('"') || head.starts_with("text") || head.starts_with("mbox"));
+
+        TokenInfo {
+            kind: token.kind(),
+            span: token.span(),
+            offset,
+            content: start..start + token.as_str().len(),
+            unbounded,
+        }
+    }
+
+    fn token<'s>(&self, source: &'s str) -> Token<'s> {
+        Token::with_span(source, &source[self.content.clone()], self.kind, self.span)
+    }
+
+    /// Returns the source of the whole token. Unlike the token content, it includes the quotes
+    /// or the `text(` and `)` around texts.
+    fn source<'s>(&self, source: &'s str) -> &'s str {
+        let closing = match self.kind {
+            TokenKind::Other(Other::Text) => 1,
+            _ => 0,
+        };
+
+        &source[self.offset..self.content.end + closing]
+    }
+}
+
+/// Change of positions caused by an edit. Positions at or after `at` in the old source are
+/// moved by `chars` characters and `bytes` bytes.
+#[derive(Debug, Clone, Copy)]
+struct Shift {
+    at: usize,
+    chars: isize,
+    bytes: isize,
+}
+
+impl Shift {
+    fn pos(&self, pos: usize) -> usize {
+        if pos >= self.at {
+            pos.saturating_add_signed(self.chars)
+        } else {
+            pos
+        }
+    }
+
+    fn span(&self, span: &mut Span) {
+        span.start = self.pos(span.start);
+        span.end = self.pos(span.end);
+    }
+
+    fn token(&self, token: &mut TokenInfo) {
+        self.span(&mut token.span);
+        token.offset = token.offset.saturating_add_signed(self.bytes);
+        token.content.start = token.content.start.saturating_add_signed(self.bytes);
+        token.content.end = token.content.end.saturating_add_signed(self.bytes);
+    }
+
+    fn expr(&self, expr: &mut Expression) {
+        self.simple(&mut expr.interm);
+
+        if let Some(sub) = &mut expr.subscript {
+            self.simple(sub);
+        }
+
+        if let Some(sup) = &mut expr.supscript {
+            self.simple(sup);
+        }
+    }
+
+    fn simple(&self, expr: &mut SimpleExpr) {
+        match expr {
+            SimpleExpr::Var(var) => self.span(&mut var.span),
+            SimpleExpr::Grouping(grp) => {
+                self.span(&mut grp.span);
+                grp.expr.iter_mut().for_each(|e| self.expr(e));
+            }
+            SimpleExpr::Unary(unary) => {
+                self.span(&mut unary.span);
+                self.simple(&mut unary.expr);
+            }
+            SimpleExpr::Binary(binary) => {
+                self.span(&mut binary.span);
+                self.simple(&mut binary.expr_1);
+                self.simple(&mut binary.expr_2);
+            }
+            SimpleExpr::Interm(inner) => self.expr(inner),
+        }
+    }
+}
+
+/// Tokens changed by an edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Relexed {
+    /// Index of the first changed token.
+    start: usize,
+
+    /// Index of the first token after the changed ones, in the old token stream.
+    old_end: usize,
+
+    /// The newly lexed tokens, replacing the old tokens `start..old_end`.
+    tokens: Vec<TokenInfo>,
+}
+
+impl Document {
+    /// Creates a new document, lexing and parsing the whole source.
+    pub fn new(source: impl Into<String>) -> Self {
+        Self::with_max_depth(source.into(), usize::MAX)
+    }
+
+    /// Creates a new document like [`Document::new`], but for untrusted sources. Parsing stops at
+    /// the first expression nested deeper than the maximum depth of the [`Limits`], also after
+    /// edits, see [`Document::exceeded`]. The other limits are not applied, because a document
+    /// grows with its edits instead of being parsed at once.
+    ///
+    /// ```
+    /// use mathemascii::{Document, LimitError, Limits};
+    ///
+    /// let limits = Limits::default();
+    /// let mut doc = Document::with_limits("(".repeat(100_000), &limits);
+    /// assert_eq!(doc.exceeded(), Some(LimitError::TooDeep { max: limits.max_depth }));
+    ///
+    /// doc.edit(0..100_000, "(a)");
+    /// assert_eq!(doc.exceeded(), None);
+    /// ```
+    pub fn with_limits(source: impl Into<String>, limits: &Limits) -> Self {
+        Self::with_max_depth(source.into(), limits.max_depth)
+    }
+
+    fn with_max_depth(source: String, max_depth: usize) -> Self {
+        let tokens = lex(&source, 0, 0);
+
+        let mut doc = Document {
+            source,
+            tokens,
+            max_depth,
+            ..Default::default()
+        };
+
+        doc.reparse_all();
+        doc
+    }
+
+    /// Returns the current source of the document.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the top-level expressions of the document, the same ones that [`crate::parse`]
+    /// produces for the current source.
+    pub fn exprs(&self) -> &[Expression<'static>] {
+        &self.exprs
+    }
+
+    /// Returns the limit exceeded by the source, if any. The expressions of the document end with
+    /// the expression that exceeds it.
+    pub fn exceeded(&self) -> Option<LimitError> {
+        self.too_deep.then_some(LimitError::TooDeep {
+            max: self.max_depth,
+        })
+    }
+
+    /// Returns the tokens of the document, together with their source.
+    #[cfg_attr(not(feature = "ide"), allow(dead_code))]
+    pub(crate) fn tokens(&self) -> impl Iterator<Item = (Token<'_>, &str)> + '_ {
+        self.tokens
+            .iter()
+            .map(|t| (t.token(&self.source), t.source(&self.source)))
+    }
+
+    /// Replaces the characters in the given range with the given text. The range is given in
+    /// characters, the same unit as the [`Span`]s of expressions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the end is greater than
+    /// the number of characters in the source.
+    pub fn edit(&mut self, range: Range<usize>, text: &str) {
+        let (relexed, shift) = self.replace(range, text);
+        self.apply(relexed, shift);
+    }
+
+    /// Replaces the characters in the given range of the source and re-lexes the tokens changed
+    /// by it.
+    fn replace(&mut self, range: Range<usize>, text: &str) -> (Relexed, Shift) {
+        assert!(
+            range.start <= range.end,
+            "edit range starts at {} but ends at {}",
+            range.start,
+            range.end
+        );
+
+        let start_byte = self.byte_offset(range.start);
+        let end_byte = self.byte_offset(range.end);
+
+        let shift = Shift {
+            at: range.end,
+            chars: text.chars().count() as isize - range.len() as isize,
+            bytes: text.len() as isize - (end_byte - start_byte) as isize,
+        };
+
+        // first token the edit might change, tokens before it were lexed without looking at the
+        // edited range
+        let first = self.tokens.partition_point(|t| {
+            t.span.end < range.start && t.span.start + MAX_LOOKAHEAD <= range.start
+        });
+
+        // the lexer doesn't look past whitespace, so tokens followed by whitespace before the
+        // edited range are not changed either
+        let before = self.tokens.partition_point(|t| t.span.end < range.start);
+        let after_whitespace = (1..=before)
+            .rev()
+            .find(|&i| {
+                self.tokens
+                    .get(i)
+                    .is_none_or(|t| t.span.start > self.tokens[i - 1].span.end)
+            })
+            .unwrap_or(0);
+
+        let first = first.max(after_whitespace);
+        let first = self.tokens[..first]
+            .iter()
+            .position(|t| t.unbounded)
+            .unwrap_or(first);
+
+        let (lex_from, lex_from_byte) = match self.tokens.get(first) {
+            Some(token) if token.span.start < range.start => (token.span.start, token.offset),
+            _ => (range.start, start_byte),
+        };
+
+        self.source.replace_range(start_byte..end_byte, text);
+
+        let relexed = self.relex(first, (lex_from, lex_from_byte), range.end, shift);
+        (relexed, shift)
+    }
+
+    /// Returns the byte offset of the character at the given index in the source.
+    fn byte_offset(&self, index: usize) -> usize {
+        // start counting characters at the closest token, instead of the start of source
+        let (chars, bytes) = match self.tokens.partition_point(|t| t.span.start <= index) {
+            0 => (0, 0),
+            i => (self.tokens[i - 1].span.start, self.tokens[i - 1].offset),
+        };
+
+        let rest = &self.source[bytes..];
+        let mut indices = rest.char_indices().map(|(i, _)| i).chain([rest.len()]);
+
+        match indices.nth(index - chars) {
+            Some(offset) => bytes + offset,
+            None => panic!("edit range is out of bounds of the source"),
+        }
+    }
+
+    /// Lexes the edited source, starting at `from` (character and byte offset) until the new
+    /// tokens line up with the old tokens after the edited range, which ends at `old_end` in the
+    /// old source.
+    fn relex(&self, first: usize, from: (usize, usize), old_end: usize, shift: Shift) -> Relexed {
+        let (from, from_byte) = from;
+
+        // only old tokens after the edit can line up with the new ones
+        let after = first + self.tokens[first..].partition_point(|t| t.span.start < old_end);
+
+        let mut window = RELEX_WINDOW;
+
+        loop {
+            let mut end = (from_byte + window).min(self.source.len());
+            while !self.source.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let at_end = end == self.source.len();
+            let window_len = self.source[from_byte..end].chars().count();
+
+            let mut tokens = Vec::new();
+            let mut old = after;
+            let mut complete = true;
+
+            for token in lex(&self.source[..end], from, from_byte) {
+                let start = token.span.start - from;
+                let is_whole = start + MAX_LOOKAHEAD <= window_len
+                    && token.span.end - from < window_len
+                    && !token.unbounded;
+
+                if !at_end && !is_whole {
+                    // the token might continue past the window
+                    complete = false;
+                    break;
+                }
+
+                while self
+                    .tokens
+                    .get(old)
+                    .is_some_and(|t| shift.pos(t.span.start) < token.span.start)
+                {
+                    old += 1;
+                }
+
+                if self
+                    .tokens
+                    .get(old)
+                    .is_some_and(|t| shift.pos(t.span.start) == token.span.start)
+                {
+                    // the rest of the source is the same as before, so are the tokens
+                    return Relexed {
+                        start: first,
+                        old_end: old,
+                        tokens,
+                    };
+                }
+
+                tokens.push(token);
+            }
+
+            if at_end || complete {
+                return Relexed {
+                    start: first,
+                    old_end: self.tokens.len(),
+                    tokens,
+                };
+            }
+
+            window *= 2;
+        }
+    }
+
+    /// Replaces the re-lexed tokens and reparses the expressions containing them.
+    fn apply(&mut self, relexed: Relexed, shift: Shift) {
+        let Relexed {
+            start,
+            old_end,
+            tokens,
+        } = relexed;
+
+        let new_end = start + tokens.len();
+        let delta = new_end as isize - old_end as isize;
+
+        let mut rest = self.tokens.split_off(old_end);
+        rest.iter_mut().for_each(|t| shift.token(t));
+
+        self.tokens.truncate(start);
+        self.tokens.extend(tokens);
+        self.tokens.extend(rest);
+
+        // the tokens after the expression exceeding the depth were never parsed, so there is
+        // nothing to line the reparsed expressions up with
+        if self.too_deep {
+            self.reparse_all();
+            return;
+        }
+
+        self.exprs.iter_mut().for_each(|e| shift.expr(e));
+
+        // top-level expression containing the first changed token
+        let k = self.bounds.partition_point(|&b| b <= start);
+
+        if k < self.exprs.len() && self.bounds[k] >= old_end {
+            let placeholder = Expression::default_with_span(Span::default());
+            let mut expr = core::mem::replace(&mut self.exprs[k], placeholder);
+            let reparsed = self.reparse_in(&mut expr, &(start..new_end), 0);
+            self.exprs[k] = expr;
+
+            if reparsed {
+                self.bounds[k..]
+                    .iter_mut()
+                    .for_each(|b| *b = b.saturating_add_signed(delta));
+                return;
+            }
+        }
+
+        // reparse top-level expressions, starting with the one that might have looked at the
+        // first changed token, until they line up with the old ones again
+        let k = self.bounds.partition_point(|&b| b < start);
+        let from = k.checked_sub(1).map_or(0, |i| self.bounds[i]);
+
+        let old_bounds = &self.bounds;
+        let (exprs, bounds, too_deep) = self.parse_from(from, |end| {
+            end >= new_end
+                && old_bounds
+                    .binary_search(&((end as isize - delta) as usize))
+                    .is_ok()
+        });
+
+        // expressions after the last reparsed one are reused, unless the parsing reached the end
+        // or stopped at an expression exceeding the depth
+        let reused = bounds
+            .last()
+            .filter(|&&end| end >= new_end && !too_deep)
+            .and_then(|&end| {
+                self.bounds
+                    .binary_search(&((end as isize - delta) as usize))
+                    .ok()
+            })
+            .map_or(self.bounds.len(), |m| m + 1);
+
+        self.too_deep = too_deep;
+
+        let mut rest_bounds = self.bounds.split_off(reused);
+        rest_bounds
+            .iter_mut()
+            .for_each(|b| *b = b.saturating_add_signed(delta));
+
+        self.bounds.truncate(k);
+        self.bounds.extend(bounds);
+        self.bounds.extend(rest_bounds);
+
+        self.exprs.splice(k..reused, exprs);
+    }
+
+    /// Parses all the top-level expressions again.
+    fn reparse_all(&mut self) {
+        let (exprs, bounds, too_deep) = self.parse_from(0, |_| false);
+        self.exprs = exprs;
+        self.bounds = bounds;
+        self.too_deep = too_deep;
+    }
+
+    /// Parses top-level expressions starting at the given token, until `stop` returns `true` for
+    /// the index of the first token after the parsed expression, or until the end of tokens.
+    /// Returns the expressions, their bounds and whether parsing stopped at an expression nested
+    /// deeper than the maximum depth.
+    fn parse_from(
+        &self,
+        from: usize,
+        mut stop: impl FnMut(usize) -> bool,
+    ) -> (Vec<Expression<'static>>, Vec<usize>, bool) {
+        let tokens = self.tokens[from..]
+            .iter()
+            .map(|t| t.token(&self.source))
+            .collect();
+
+        let mut parser = AsciiMath::replay(tokens).with_max_depth(self.max_depth);
+        let mut exprs = Vec::new();
+        let mut bounds = Vec::new();
+
+        while let Some(expr) = parser.next() {
+            let end = from + parser.consumed();
+
+            exprs.push(expr.into_owned());
+            bounds.push(end);
+
+            if stop(end) {
+                break;
+            }
+        }
+
+        (exprs, bounds, parser.is_too_deep())
+    }
+
+    /// Reparses the innermost grouping in the given expression that encloses the given range of
+    /// tokens. Returns `true` if a grouping was reparsed.
+    ///
+    /// The depth is the number of expressions enclosing the given one. It's at least the nesting
+    /// depth the parser counted for it, so a grouping that stays within the remaining depth when
+    /// parsed on its own stays within the maximum depth in the whole document as well.
+    fn reparse_in(
+        &self,
+        expr: &mut Expression<'static>,
+        region: &Range<usize>,
+        depth: usize,
+    ) -> bool {
+        let mut reparse =
+            |expr: &mut SimpleExpr<'static>| self.reparse_grouping(expr, region, depth);
+
+        reparse(&mut expr.interm)
+            || expr.subscript.as_mut().is_some_and(&mut reparse)
+            || expr.supscript.as_mut().is_some_and(&mut reparse)
+    }
+
+    /// Same as [`Document::reparse_in`], but for [`SimpleExpr`]s. The grouping has to enclose the
+    /// range without sharing its opening or closing token with it.
+    fn reparse_grouping(
+        &self,
+        expr: &mut SimpleExpr<'static>,
+        region: &Range<usize>,
+        depth: usize,
+    ) -> bool {
+        let inner = depth + 1;
+
+        let reparsed = match expr {
+            SimpleExpr::Var(_) => false,
+            SimpleExpr::Grouping(grp) => grp
+                .expr
+                .iter_mut()
+                .any(|e| self.reparse_in(e, region, inner)),
+            SimpleExpr::Unary(unary) => self.reparse_grouping(&mut unary.expr, region, inner),
+            SimpleExpr::Binary(binary) => {
+                self.reparse_grouping(&mut binary.expr_1, region, inner)
+                    || self.reparse_grouping(&mut binary.expr_2, region, inner)
+            }
+            SimpleExpr::Interm(inner_expr) => self.reparse_in(inner_expr, region, inner),
+        };
+
+        if reparsed {
+            return true;
+        }
+
+        let SimpleExpr::Grouping(grp) = expr else {
+            return false;
+        };
+
+        let Some((open, close)) = self.grouping_tokens(grp) else {
+            return false;
+        };
+
+        if open >= region.start || close < region.end {
+            return false;
+        }
+
+        let tokens = self.tokens[open..=close]
+            .iter()
+            .map(|t| t.token(&self.source))
+            .collect();
+
+        let max_depth = self.max_depth.saturating_sub(depth);
+        let mut parser = AsciiMath::replay(tokens).with_max_depth(max_depth);
+        let grouping = parser.parse_simple_expr();
+
+        // the grouping has to be closed by the same token as before, otherwise the change
+        // affects the expressions after it as well. Groupings exceeding the remaining depth are
+        // left to the reparsing of the top-level expressions, which counts the depth exactly.
+        match grouping {
+            Some(grouping @ SimpleExpr::Grouping(_))
+                if !parser.is_exhausted()
+                    && !parser.is_too_deep()
+                    && parser.consumed() == close - open + 1 =>
+            {
+                *expr = grouping.into_owned();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Finds the indices of the opening and closing tokens of the grouping with the given span.
+    fn grouping_tokens(&self, grp: &GroupingExpr) -> Option<(usize, usize)> {
+        let span = grp.span;
+        let open = self
+            .tokens
+            .binary_search_by_key(&span.start, |t| t.span.start)
+            .ok()?;
+
+        let close = self.tokens.partition_point(|t| t.span.end < span.end);
+
+        let is_open = self.tokens[open].kind == TokenKind::Grouping(grp.left_grouping);
+        let is_close = self.tokens.get(close).is_some_and(|t| {
+            t.span.end == span.end && t.kind == TokenKind::Grouping(grp.right_grouping)
+        });
+
+        (is_open && is_close && open < close).then_some((open, close))
+    }
+}
+
+/// Lexes the source starting at the given character and byte offset.
+fn lex(source: &str, from: usize, from_byte: usize) -> Vec<TokenInfo> {
+    let rest = &source[from_byte..];
+    let mut offsets = rest.char_indices().map(|(i, _)| i).enumerate();
+
+    TokenIterator::tokenize(rest)
+        .map(|token| {
+            let start = token.span().start;
+            let (_, offset) = offsets
+                .find(|&(i, _)| i == start)
+                .expect("token starts within the source");
+
+            let span = Span {
+                start: start + from,
+                end: token.span().end + from,
+            };
+
+            TokenInfo::new(
+                Token::with_span(rest, token.as_str(), token.kind(), span),
+                source,
+                from_byte + offset,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relexes_only_around_edit() {
+        let mut doc = Document::new("a + b + c + d + e + f + g + h + i + j + k");
+        let (relexed, _) = doc.replace(20..21, "xyz");
+
+        assert_eq!(doc.source(), "a + b + c + d + e + xyz + g + h + i + j + k");
+        assert_eq!(relexed.start, 10);
+        assert_eq!(relexed.old_end, 11);
+        assert_eq!(relexed.tokens.len(), 3);
+    }
+
+    #[test]
+    fn relexes_keyword_prefixes() {
+        let mut doc = Document::new("a+b+alp+c");
+        let (relexed, _) = doc.replace(7..7, "ha");
+
+        // the lexer looked at all the tokens before, since they are not separated by whitespace
+        assert_eq!(relexed.start, 0);
+        assert_eq!(relexed.old_end, 7);
+        assert_eq!(relexed.tokens.len(), 5);
+    }
+
+    #[test]
+    fn stops_at_max_depth() {
+        let check = |source: &str, max_depth, edits: &[(Range<usize>, &str)]| {
+            let limits = Limits {
+                max_depth,
+                ..Limits::UNLIMITED
+            };
+
+            let mut doc = Document::with_limits(source, &limits);
+
+            for (range, text) in edits {
+                doc.edit(range.clone(), text);
+
+                let mut parser = AsciiMath::limited(doc.source(), &limits);
+                let expected: Vec<_> = parser.by_ref().collect();
+
+                assert_eq!(doc.exprs(), expected, "{}", doc.source());
+                assert_eq!(doc.exceeded(), parser.exceeded(&limits), "{}", doc.source());
+            }
+        };
+
+        check(
+            "a ((b)) c + d",
+            3,
+            &[(4..4, "("), (9..9, ")"), (12..13, "(e)"), (2..3, "")],
+        );
+
+        // the expression exceeding the depth ends where an expression ended before the edit, the
+        // expressions after it are not reused anyway
+        check("())(", 2, &[(1..2, "(")]);
+    }
+
+    #[test]
+    fn relexes_unclosed_texts() {
+        let mut doc = Document::new(r#"a "b + c + d + e + f + g + h + i + j"#);
+        let (relexed, _) = doc.replace(36..36, r#"""#);
+
+        // the unclosed quote is lexed again, now as a text up to the end of input
+        assert_eq!(relexed.start, 1);
+        assert_eq!(relexed.old_end, doc.tokens.len());
+        assert_eq!(relexed.tokens.len(), 1);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    lexer::{
+        keywords::{groupings::Grouping, others::Other},
+        TokenKind,
+    },
+    Document, SimpleExpr, Span, Var, VarKind,
+};
+
+use super::{brackets, walk};
+
+/// Problem found in AsciiMath input. The parser accepts any input, so these are places where the
+/// input is most likely not rendered as intended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Span of the input the problem is found in.
+    pub span: Span,
+
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// Returns the problems found in the document, ordered by their position.
+pub fn diagnostics(doc: &Document) -> Vec<Diagnostic> {
+    let tokens: Vec<_> = doc.tokens().collect();
+    let mut diagnostics = Vec::new();
+
+    for (token, source) in &tokens {
+        let is_text = token.kind() == TokenKind::Other(Other::Text);
+
+        if !is_text
+            && (source.starts_with('"') || source.starts_with("text") || source.starts_with("mbox"))
+        {
+            diagnostics.push(Diagnostic::new(token.span(), "text is not closed"));
+        }
+    }
+
+    if let Some((token, _)) = tokens
+        .last()
+        .filter(|(t, _)| matches!(t.kind(), TokenKind::Other(Other::Subscript | Other::Power)))
+    {
+        let message = format!("missing expression after `{}`", token.as_str());
+        diagnostics.push(Diagnostic::new(token.span(), message));
+    }
+
+    let tokens: Vec<_> = tokens.into_iter().map(|(t, _)| t).collect();
+    let mut closing = Vec::new();
+
+    walk(doc.exprs(), &mut |expr| match expr {
+        SimpleExpr::Grouping(grp) => match brackets(&tokens, grp) {
+            Some((_, close)) => closing.push(close),
+            None => {
+                let i = tokens.partition_point(|t| t.span().start < grp.span.start);
+
+                if let Some(open) = tokens.get(i) {
+                    let message = format!("`{}` is not closed", open.as_str());
+                    diagnostics.push(Diagnostic::new(open.span(), message));
+                }
+            }
+        },
+        SimpleExpr::Var(Var {
+            kind: VarKind::UnknownOperator(op) | VarKind::Text(op),
+            span,
+        }) if op.is_empty() => {
+            diagnostics.push(Diagnostic::new(*span, "missing argument"));
+        }
+        _ => {}
+    });
+
+    closing.sort();
+
+    // closing brackets that don't close any grouping are parsed as operators
+    for token in &tokens {
+        let span = token.span();
+        let is_closing = matches!(
+            token.kind(),
+            TokenKind::Grouping(
+                Grouping::CloseParen
+                    | Grouping::CloseBracket
+                    | Grouping::CloseBrace
+                    | Grouping::RightAngled
+                    | Grouping::CloseIgnored
+            )
+        );
+
+        if is_closing && closing.binary_search(&span).is_err() {
+            let message = format!("`{}` does not close any grouping", token.as_str());
+            diagnostics.push(Diagnostic::new(span, message));
+        }
+    }
+
+    diagnostics.sort_by_key(|d| d.span);
+    diagnostics
+}
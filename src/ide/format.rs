@@ -0,0 +1,124 @@
+use crate::{
+    lexer::{
+        keywords::{logicals::Logical, operators::Operator, others::Other},
+        Token, TokenIterator, TokenKind,
+    },
+    Document, UnaryKind,
+};
+
+/// Role of a token for the purpose of spacing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Open,
+    Close,
+    /// Subscript, superscript, fraction slash and prime, attached to the previous token.
+    Attached,
+    Comma,
+    /// Binary operators, relations, arrows and logical connectives, surrounded by spaces.
+    Infix,
+    Other,
+}
+
+impl Role {
+    fn of(kind: TokenKind) -> Self {
+        match kind {
+            TokenKind::Grouping(_) if UnaryKind::try_from(kind).is_ok() => Role::Other,
+            TokenKind::Grouping(_) if kind.is_grouping_open() => Role::Open,
+            TokenKind::Grouping(_) => Role::Close,
+            TokenKind::Other(Other::Subscript | Other::Power | Other::ForwardSlash) => {
+                Role::Attached
+            }
+            TokenKind::Operator(Operator::Prime) => Role::Attached,
+            TokenKind::Other(Other::Comma) => Role::Comma,
+            TokenKind::Other(Other::PlusMinus) => Role::Infix,
+            TokenKind::Operator(
+                Operator::Sum
+                | Operator::Prod
+                | Operator::BigWedge
+                | Operator::BigCap
+                | Operator::BigCup,
+            ) => Role::Other,
+            TokenKind::Logical(Logical::Not | Logical::ForAll | Logical::Exists) => Role::Other,
+            TokenKind::Operator(_)
+            | TokenKind::Relation(_)
+            | TokenKind::Arrow(_)
+            | TokenKind::Logical(_) => Role::Infix,
+            _ => Role::Other,
+        }
+    }
+}
+
+/// Formats the document with consistent spacing: binary operators and relations are surrounded
+/// by single spaces, brackets, scripts and fraction slashes are not, and any other whitespace is
+/// collapsed into a single space. Texts are kept as they are.
+///
+/// The formatted source always produces the same expressions as the original one.
+pub fn format(doc: &Document) -> String {
+    let tokens: Vec<_> = doc.tokens().collect();
+
+    let formatted = join(&tokens, true);
+
+    if same_tokens(&tokens, &formatted) {
+        formatted
+    } else {
+        // the spacing changed the tokens, e.g. `( :` became `(:`, so only collapse whitespace
+        join(&tokens, false)
+    }
+}
+
+/// Joins the tokens, placing spaces between them by their roles if `by_role` is set, otherwise
+/// only where the source has whitespace.
+fn join(tokens: &[(Token<'_>, &str)], by_role: bool) -> String {
+    let mut out = String::new();
+
+    // roles of the two previous tokens
+    let mut before: Option<Role> = None;
+    let mut prev: Option<(Token<'_>, Role)> = None;
+
+    for &(token, source) in tokens {
+        let role = Role::of(token.kind());
+
+        if let Some((prev_token, prev_role)) = prev {
+            let has_whitespace = token.span().start > prev_token.span().end;
+
+            let space = if !by_role {
+                has_whitespace
+            } else {
+                match (prev_role, role) {
+                    (Role::Open, _) | (_, Role::Close) => false,
+                    (Role::Attached, _) | (_, Role::Attached | Role::Comma) => false,
+                    (Role::Comma, _) => true,
+                    (_, Role::Infix) => true,
+                    // unary operators, like `-` in `(-a)`, stick to their operand
+                    (Role::Infix, _) => !matches!(
+                        before,
+                        None | Some(Role::Open | Role::Infix | Role::Comma | Role::Attached)
+                    ),
+                    _ => has_whitespace,
+                }
+            };
+
+            if space {
+                out.push(' ');
+            }
+        }
+
+        out.push_str(source);
+
+        before = prev.map(|(_, role)| role);
+        prev = Some((token, role));
+    }
+
+    out
+}
+
+/// Returns `true` if the formatted source is lexed into the same tokens as the original.
+fn same_tokens(tokens: &[(Token<'_>, &str)], formatted: &str) -> bool {
+    let mut lexed = TokenIterator::tokenize(formatted);
+
+    tokens.iter().all(|(token, _)| {
+        lexed
+            .next()
+            .is_some_and(|t| t.kind() == token.kind() && t.as_str() == token.as_str())
+    }) && lexed.next().is_none()
+}
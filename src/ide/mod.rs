@@ -0,0 +1,174 @@
+//! Analysis of AsciiMath [`Document`]s for editor tooling, like language servers. Provides
+//! diagnostics, hover information, keyword completion, bracket matching and formatting.
+
+mod diagnostics;
+mod format;
+
+pub use diagnostics::{diagnostics, Diagnostic};
+pub use format::format;
+
+use crate::{
+    keywords::{self, Grouping, Other, Variant},
+    lexer::{Token, TokenKind},
+    Document, Expression, GroupingExpr, SimpleExpr, Span,
+};
+
+/// Information about the token under the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hover {
+    /// Span of the token.
+    pub span: Span,
+
+    /// The Unicode form of the token as it's rendered, if it's a symbol.
+    pub symbol: Option<&'static str>,
+
+    /// Description of the token, e.g. `Relation NotEq`.
+    pub description: String,
+
+    /// All the keywords that produce the same symbol as the token, including itself.
+    pub aliases: &'static [&'static str],
+}
+
+/// Keyword suggested for completion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The keyword to be inserted.
+    pub keyword: &'static str,
+
+    /// The Unicode form of the keyword as it's rendered, if it's a symbol.
+    pub symbol: Option<&'static str>,
+
+    /// Description of the keyword, e.g. `Relation NotEq`.
+    pub description: String,
+
+    /// All the keywords that produce the same symbol, including this one.
+    pub aliases: &'static [&'static str],
+}
+
+/// Returns information about the token at the given character offset.
+pub fn hover(doc: &Document, offset: usize) -> Option<Hover> {
+    let (token, _) = doc
+        .tokens()
+        .find(|(t, _)| t.span().start <= offset && offset < t.span().end)?;
+
+    let kind = token.kind();
+    let symbol = Variant::from_token(kind).map(keywords::symbol);
+
+    Some(Hover {
+        span: token.span(),
+        symbol: symbol.and_then(|s| s.unicode),
+        description: describe(kind),
+        aliases: symbol.map(|s| s.keywords).unwrap_or_default(),
+    })
+}
+
+/// Returns all the keywords starting with the given prefix, ordered by length, so that the
+/// closest matches come first.
+pub fn completions(prefix: &str) -> Vec<Completion> {
+    keywords::search(prefix)
+        .into_iter()
+        .map(|(keyword, symbol)| Completion {
+            keyword,
+            symbol: symbol.unicode,
+            description: describe(symbol.variant.into()),
+            aliases: symbol.keywords,
+        })
+        .collect()
+}
+
+/// Returns the spans of the opening and the closing bracket of the grouping, if the character at
+/// the given offset is one of them. Groupings that are not closed have no matching bracket.
+pub fn matching_bracket(doc: &Document, offset: usize) -> Option<(Span, Span)> {
+    let tokens: Vec<_> = doc.tokens().map(|(t, _)| t).collect();
+    let mut found = None;
+
+    walk(doc.exprs(), &mut |expr| {
+        let SimpleExpr::Grouping(grp) = expr else {
+            return;
+        };
+
+        if let Some((open, close)) = brackets(&tokens, grp) {
+            let contains = |span: Span| span.start <= offset && offset < span.end;
+
+            if contains(open) || contains(close) {
+                found = Some((open, close));
+            }
+        }
+    });
+
+    found
+}
+
+/// Returns the spans of the opening and the closing bracket of the grouping, or `None` if the
+/// grouping is not closed.
+fn brackets(tokens: &[Token<'_>], grp: &GroupingExpr) -> Option<(Span, Span)> {
+    let find = |i: usize, grouping: Grouping| {
+        tokens
+            .get(i)
+            .filter(|t| t.kind() == TokenKind::Grouping(grouping))
+            .map(Token::span)
+    };
+
+    let open = tokens.partition_point(|t| t.span().start < grp.span.start);
+    let close = tokens.partition_point(|t| t.span().end < grp.span.end);
+
+    let open = find(open, grp.left_grouping).filter(|s| s.start == grp.span.start)?;
+    let close = find(close, grp.right_grouping).filter(|s| s.end == grp.span.end)?;
+
+    Some((open, close)).filter(|(open, close)| open.end <= close.start)
+}
+
+/// Calls the given function for every simple expression in the given expressions, including the
+/// nested ones.
+fn walk<'e>(exprs: &'e [Expression], f: &mut impl FnMut(&'e SimpleExpr<'e>)) {
+    fn simple<'e>(expr: &'e SimpleExpr<'e>, f: &mut impl FnMut(&'e SimpleExpr<'e>)) {
+        f(expr);
+
+        match expr {
+            SimpleExpr::Var(_) => {}
+            SimpleExpr::Grouping(grp) => walk(&grp.expr, f),
+            SimpleExpr::Unary(unary) => simple(&unary.expr, f),
+            SimpleExpr::Binary(binary) => {
+                simple(&binary.expr_1, f);
+                simple(&binary.expr_2, f);
+            }
+            SimpleExpr::Interm(inner) => walk(std::slice::from_ref(inner), f),
+        }
+    }
+
+    for expr in exprs {
+        simple(&expr.interm, f);
+
+        if let Some(sub) = &expr.subscript {
+            simple(sub, f);
+        }
+
+        if let Some(sup) = &expr.supscript {
+            simple(sup, f);
+        }
+    }
+}
+
+/// Describes the token kind with its category and variant, e.g. `Relation NotEq`.
+fn describe(kind: TokenKind) -> String {
+    match kind {
+        TokenKind::Number => String::from("Number"),
+        TokenKind::Variable => String::from("Variable"),
+        TokenKind::UnknownOperator => String::from("Operator"),
+        TokenKind::Other(Other::Text) => String::from("Text"),
+        TokenKind::Greek(greek) => format!("Greek letter {greek:?}"),
+        TokenKind::Arrow(arrow) => format!("Arrow {arrow:?}"),
+        TokenKind::Function(func) => format!("Function {func:?}"),
+        TokenKind::Operator(op) => format!("Operator {op:?}"),
+        TokenKind::Relation(rel) => format!("Relation {rel:?}"),
+        TokenKind::Logical(log) => format!("Logical {log:?}"),
+        TokenKind::Grouping(grp) => format!("Grouping {grp:?}"),
+        TokenKind::Other(other) => format!("Symbol {other:?}"),
+        TokenKind::Accent(accent) => format!("Accent {accent:?}"),
+        TokenKind::FontCommand(font) => format!("Font command {font:?}"),
+        TokenKind::Unimplemented => String::from("Unknown"),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,118 @@
+use crate::{Document, Span};
+
+use super::{completions, diagnostics, format, hover, matching_bracket};
+
+fn span(start: usize, end: usize) -> Span {
+    Span { start, end }
+}
+
+#[test]
+fn diagnostics_of_valid_input() {
+    let doc = Document::new(r#"sum_(i=0)^n i^2 = "text" + sqrt(x) + [[a,b],[c,d]]"#);
+
+    assert_eq!(diagnostics(&doc), vec![]);
+}
+
+#[test]
+fn diagnostics_of_invalid_input() {
+    let doc = Document::new(r#"(a + b ] ) + sqrt "c"#);
+
+    let messages: Vec<_> = diagnostics(&doc)
+        .into_iter()
+        .map(|d| (d.span, d.message))
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            (span(7, 8), String::from("`]` does not close any grouping")),
+            (span(18, 19), String::from("text is not closed")),
+        ]
+    );
+
+    let doc = Document::new("frac(a)");
+    let messages: Vec<_> = diagnostics(&doc).into_iter().map(|d| d.message).collect();
+
+    assert_eq!(messages, vec!["missing argument"]);
+
+    let doc = Document::new("x^");
+    let messages: Vec<_> = diagnostics(&doc).into_iter().map(|d| d.message).collect();
+
+    assert_eq!(messages, vec!["missing expression after `^`"]);
+
+    let doc = Document::new("langle a, b");
+    let messages: Vec<_> = diagnostics(&doc).into_iter().map(|d| d.message).collect();
+
+    assert_eq!(messages, vec!["`langle` is not closed"]);
+}
+
+#[test]
+fn hover_keywords() {
+    let doc = Document::new("a != b -> sin x");
+
+    let ne = hover(&doc, 3).unwrap();
+    assert_eq!(ne.span, span(2, 4));
+    assert_eq!(ne.symbol, Some("≠"));
+    assert_eq!(ne.description, "Relation NotEq");
+    assert_eq!(ne.aliases, ["!=", "ne"]);
+
+    let sin = hover(&doc, 10).unwrap();
+    assert_eq!(sin.symbol, None);
+    assert_eq!(sin.description, "Function Sin");
+
+    let var = hover(&doc, 0).unwrap();
+    assert_eq!(var.description, "Variable");
+    assert!(var.aliases.is_empty());
+
+    assert_eq!(hover(&doc, 1), None);
+
+    let le = hover(&Document::new("α ≤ β"), 2).unwrap();
+    assert_eq!(le.symbol, Some("≤"));
+    assert_eq!(le.description, "Relation LessEqualThan");
+    assert_eq!(le.aliases, ["<=", "le"]);
+}
+
+#[test]
+fn complete_prefix() {
+    let keywords: Vec<_> = completions("sub").into_iter().map(|c| c.keyword).collect();
+    assert_eq!(keywords, ["sub", "sube", "subset", "subseteq"]);
+
+    let ri = completions("ri");
+    assert_eq!(ri[0].keyword, "rightarrow");
+    assert_eq!(ri[0].symbol, Some("→"));
+    assert_eq!(ri[0].description, "Arrow Right");
+    assert_eq!(ri[0].aliases, ["->", "to", "rarr", "rightarrow"]);
+}
+
+#[test]
+fn match_brackets() {
+    let doc = Document::new("(a + [b]) + (c");
+
+    assert_eq!(matching_bracket(&doc, 0), Some((span(0, 1), span(8, 9))));
+    assert_eq!(matching_bracket(&doc, 8), Some((span(0, 1), span(8, 9))));
+    assert_eq!(matching_bracket(&doc, 7), Some((span(5, 6), span(7, 8))));
+    assert_eq!(matching_bracket(&doc, 2), None);
+    assert_eq!(matching_bracket(&doc, 12), None);
+}
+
+#[test]
+fn format_spacing() {
+    let cases = [
+        ("sum_(i=0)^n i", "sum_(i = 0)^n i"),
+        ("a+b  -c", "a + b - c"),
+        ("-a*(-b)", "-a * (-b)"),
+        ("x^-1 = f'(x)", "x^-1 = f'(x)"),
+        ("(a ,b)/ 2", "(a, b)/2"),
+        (r#"a+"  spaced  text""#, r#"a + "  spaced  text""#),
+        // `(:` would be an angle bracket, so the whitespace is kept as it is
+        ("( :a:)", "( :a:)"),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(
+            format(&Document::new(input)),
+            expected,
+            "formatting {input:?}"
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! Registry of all the keywords of the AsciiMath grammar, e.g. for symbol palettes and keyword
+//! completion. Every [`Symbol`] groups all the keywords (aliases) producing the same variant,
+//! together with its [`Category`] and the Unicode character it's rendered as.
+//!
+//! ```
+//! use mathemascii::keywords::{self, Category, Relation};
+//!
+//! let sub = keywords::search("sub");
+//! assert_eq!(sub[0].0, "sub");
+//! assert_eq!(sub[0].1.category(), Category::Relation);
+//! assert_eq!(sub[0].1.unicode, Some("⊂"));
+//!
+//! let ne = keywords::symbol(Relation::NotEq);
+//! assert_eq!(ne.keywords, ["!=", "ne"]);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use crate::lexer::{
+    keywords::{
+        accents, arrows, font_commands, functions, greeks, groupings, logicals, operators, others,
+        relations,
+    },
+    TokenKind,
+};
+
+pub use crate::lexer::keywords::{
+    accents::Accent, arrows::Arrow, font_commands::FontCommand, functions::Function, greeks::Greek,
+    groupings::Grouping, logicals::Logical, operators::Operator, others::Other,
+    relations::Relation,
+};
+
+/// Category of a keyword, corresponding to the symbol tables of the AsciiMath grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    /// Greek letters, see [`Greek`].
+    Greek,
+
+    /// Arrows, see [`Arrow`].
+    Arrow,
+
+    /// Standard functions, see [`Function`].
+    Function,
+
+    /// Operation symbols, see [`Operator`].
+    Operator,
+
+    /// Relation symbols, see [`Relation`].
+    Relation,
+
+    /// Logical symbols, see [`Logical`].
+    Logical,
+
+    /// Brackets and other grouping symbols, see [`Grouping`].
+    Grouping,
+
+    /// Miscellaneous symbols, see [`Other`].
+    Other,
+
+    /// Accents, see [`Accent`].
+    Accent,
+
+    /// Font commands, see [`FontCommand`].
+    FontCommand,
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Category::Greek => "Greek letter",
+            Category::Arrow => "Arrow",
+            Category::Function => "Function",
+            Category::Operator => "Operator",
+            Category::Relation => "Relation",
+            Category::Logical => "Logical",
+            Category::Grouping => "Grouping",
+            Category::Other => "Symbol",
+            Category::Accent => "Accent",
+            Category::FontCommand => "Font command",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// Variant of a keyword, i.e. what the keyword stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Variant {
+    /// Greek letter, i.e. `alpha`.
+    Greek(Greek),
+
+    /// Arrow, i.e. `->`.
+    Arrow(Arrow),
+
+    /// Standard function, i.e. `sin`.
+    Function(Function),
+
+    /// Operation symbol, i.e. `+`.
+    Operator(Operator),
+
+    /// Relation symbol, i.e. `!=`.
+    Relation(Relation),
+
+    /// Logical symbol, i.e. `and`.
+    Logical(Logical),
+
+    /// Grouping symbol, i.e. `(`.
+    Grouping(Grouping),
+
+    /// Miscellaneous symbol, i.e. `oo`.
+    Other(Other),
+
+    /// Accent, i.e. `hat`.
+    Accent(Accent),
+
+    /// Font command, i.e. `bb`.
+    FontCommand(FontCommand),
+}
+
+impl Variant {
+    /// Returns the category of this variant.
+    pub fn category(&self) -> Category {
+        match self {
+            Variant::Greek(_) => Category::Greek,
+            Variant::Arrow(_) => Category::Arrow,
+            Variant::Function(_) => Category::Function,
+            Variant::Operator(_) => Category::Operator,
+            Variant::Relation(_) => Category::Relation,
+            Variant::Logical(_) => Category::Logical,
+            Variant::Grouping(_) => Category::Grouping,
+            Variant::Other(_) => Category::Other,
+            Variant::Accent(_) => Category::Accent,
+            Variant::FontCommand(_) => Category::FontCommand,
+        }
+    }
+
+    /// Returns all the keywords of this variant.
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Variant::Greek(greek) => greek.literals(),
+            Variant::Arrow(arrow) => arrow.literals(),
+            Variant::Function(func) => func.literals(),
+            Variant::Operator(op) => op.literals(),
+            Variant::Relation(rel) => rel.literals(),
+            Variant::Logical(log) => log.literals(),
+            Variant::Grouping(grp) => grp.literals(),
+            Variant::Other(other) => other.literals(),
+            Variant::Accent(accent) => accent.literals(),
+            Variant::FontCommand(font) => font.literals(),
+        }
+    }
+
+    /// Returns the Unicode form of this variant as it's rendered, if it's a symbol.
+    fn unicode(self) -> Option<&'static str> {
+        let unicode = match self {
+            Variant::Greek(greek) => greek.as_unicode(),
+            Variant::Arrow(arrow) => arrow.as_unicode(),
+            Variant::Operator(op) => op.as_unicode(),
+            Variant::Relation(rel) => rel.as_unicode(),
+            Variant::Logical(log) => log.as_unicode(),
+            Variant::Grouping(grp) => grp.as_unicode(TokenKind::from(self).is_grouping_open()),
+            Variant::Other(Other::Fraction | Other::Root | Other::Text | Other::Quote) => "",
+            Variant::Other(other) => other.as_unicode(),
+            Variant::Accent(accent) => accent.as_unicode(),
+            Variant::Function(_) | Variant::FontCommand(_) => "",
+        };
+
+        Some(unicode).filter(|s| !s.is_empty())
+    }
+
+    /// Converts the token kind into a variant, if it's a keyword.
+    pub(crate) fn from_token(kind: TokenKind) -> Option<Self> {
+        let variant = match kind {
+            TokenKind::Greek(greek) => greek.into(),
+            TokenKind::Arrow(arrow) => arrow.into(),
+            TokenKind::Function(func) => func.into(),
+            TokenKind::Operator(op) => op.into(),
+            TokenKind::Relation(rel) => rel.into(),
+            TokenKind::Logical(log) => log.into(),
+            TokenKind::Grouping(grp) => grp.into(),
+            TokenKind::Other(Other::Text) => return None,
+            TokenKind::Other(other) => other.into(),
+            TokenKind::Accent(accent) => accent.into(),
+            TokenKind::FontCommand(font) => font.into(),
+            TokenKind::Number
+            | TokenKind::Variable
+            | TokenKind::UnknownOperator
+            | TokenKind::Unimplemented => return None,
+        };
+
+        Some(variant)
+    }
+}
+
+macro_rules! impl_from {
+    ($($kind:ident),*) => {
+        $(
+            impl From<$kind> for Variant {
+                fn from(value: $kind) -> Self {
+                    Variant::$kind(value)
+                }
+            }
+        )*
+
+        impl From<Variant> for TokenKind {
+            fn from(value: Variant) -> Self {
+                match value {
+                    $(
+                        Variant::$kind(kind) => kind.into(),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+impl_from!(
+    Greek,
+    Arrow,
+    Function,
+    Operator,
+    Relation,
+    Logical,
+    Grouping,
+    Other,
+    Accent,
+    FontCommand
+);
+
+/// Symbol of the AsciiMath grammar with all the keywords that produce it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    /// What the keywords stand for.
+    pub variant: Variant,
+
+    /// All the keywords of the variant, i.e. `->`, `to`, `rarr` and `rightarrow` for the right
+    /// arrow. The first one is the primary keyword.
+    pub keywords: &'static [&'static str],
+
+    /// The Unicode form of the symbol as it's rendered, i.e. `→` for the right arrow. Functions,
+    /// font commands and keywords with arguments, like `frac`, have no Unicode form.
+    pub unicode: Option<&'static str>,
+}
+
+impl Symbol {
+    /// Returns the category of the symbol.
+    pub fn category(&self) -> Category {
+        self.variant.category()
+    }
+
+    /// Returns the primary keyword of the symbol, i.e. `->` for the right arrow.
+    pub fn keyword(&self) -> &'static str {
+        self.keywords[0]
+    }
+}
+
+impl From<Variant> for Symbol {
+    fn from(variant: Variant) -> Self {
+        Self {
+            variant,
+            keywords: variant.keywords(),
+            unicode: variant.unicode(),
+        }
+    }
+}
+
+/// Returns an iterator over all the symbols, ordered by their category.
+pub fn symbols() -> impl Iterator<Item = Symbol> {
+    fn variants<K: Into<Variant> + Copy>(variants: &'static [K]) -> impl Iterator<Item = Variant> {
+        variants.iter().map(|&v| v.into())
+    }
+
+    variants(greeks::VARIANTS)
+        .chain(variants(arrows::VARIANTS))
+        .chain(variants(functions::VARIANTS))
+        .chain(variants(operators::VARIANTS))
+        .chain(variants(relations::VARIANTS))
+        .chain(variants(logicals::VARIANTS))
+        .chain(variants(groupings::VARIANTS))
+        .chain(variants(others::VARIANTS))
+        .chain(variants(accents::VARIANTS))
+        .chain(variants(font_commands::VARIANTS))
+        .map(Symbol::from)
+}
+
+/// Returns the symbol of the given variant.
+pub fn symbol(variant: impl Into<Variant>) -> Symbol {
+    Symbol::from(variant.into())
+}
+
+/// Returns the symbol produced by the given keyword, or `None` if it's not a keyword.
+pub fn lookup(keyword: &str) -> Option<Symbol> {
+    symbols().find(|s| s.keywords.contains(&keyword))
+}
+
+/// Returns all the keywords starting with the given prefix, together with their symbols. The
+/// keywords are ordered by length, so that the closest matches come first.
+pub fn search(prefix: &str) -> Vec<(&'static str, Symbol)> {
+    let mut found: Vec<_> = symbols()
+        .flat_map(|symbol| symbol.keywords.iter().map(move |&k| (k, symbol)))
+        .filter(|(keyword, _)| keyword.starts_with(prefix))
+        .collect();
+
+    found.sort_by_key(|(keyword, _)| (keyword.len(), *keyword));
+    found
+}
+
+/// Characters that look like the Unicode form of a symbol and are commonly used instead of it.
+const LOOKALIKES: &[(char, Variant)] = &[
+    ('·', Variant::Operator(Operator::Dot)),
+    ('µ', Variant::Greek(Greek::Mu)),
+    ('⩽', Variant::Relation(Relation::LessEqualThan)),
+    ('⩾', Variant::Relation(Relation::GreaterEqualThan)),
+];
+
+/// Returns the symbol rendered as the given Unicode character, i.e. the Greek letter `alpha` for
+/// `α`. Greek letters, arrows, operators, relations, logical and miscellaneous symbols are
+/// recognized, as long as their Unicode form is a single character outside of ASCII. A few
+/// look-alike characters, like the middle dot `·` for `⋅`, are recognized too.
+pub fn from_unicode(c: char) -> Option<Symbol> {
+    #[cfg(feature = "std")]
+    {
+        use std::{collections::BTreeMap, sync::OnceLock};
+
+        static SYMBOLS: OnceLock<BTreeMap<char, Symbol>> = OnceLock::new();
+
+        let symbols = SYMBOLS.get_or_init(|| {
+            let mut map = BTreeMap::new();
+
+            for (c, symbol) in unicode_symbols() {
+                map.entry(c).or_insert(symbol);
+            }
+
+            map
+        });
+
+        symbols.get(&c).copied()
+    }
+
+    #[cfg(not(feature = "std"))]
+    unicode_symbols()
+        .find(|&(unicode, _)| unicode == c)
+        .map(|(_, symbol)| symbol)
+}
+
+/// Returns the symbols with their Unicode characters, followed by the look-alike characters.
+/// Symbols listed first take precedence, i.e. arrows over operators.
+fn unicode_symbols() -> impl Iterator<Item = (char, Symbol)> {
+    let symbols = symbols().filter(|s| {
+        matches!(
+            s.category(),
+            Category::Greek
+                | Category::Arrow
+                | Category::Operator
+                | Category::Relation
+                | Category::Logical
+                | Category::Other
+        )
+    });
+
+    let symbols = symbols.filter_map(|symbol| {
+        let mut chars = symbol.unicode.unwrap_or_default().chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if !c.is_ascii() => Some((c, symbol)),
+            _ => None,
+        }
+    });
+
+    let lookalikes = LOOKALIKES
+        .iter()
+        .map(|&(c, variant)| (c, Symbol::from(variant)));
+
+    symbols.chain(lookalikes)
+}
@@ -3,8 +3,8 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Accents above or below an expression, like `hat` or `ubrace`.
     Accent,
-    Accents,
     "hat" => Hat,
     "bar" | "overline" => Overline,
     "ul" | "underline" => Underline,
@@ -25,3 +25,22 @@ impl From<Accent> for TokenKind {
         TokenKind::Accent(value)
     }
 }
+
+impl Accent {
+    /// Returns the Unicode character of this accent. Accents without a character of their own,
+    /// like `color` or `overset`, are empty.
+    pub(crate) fn as_unicode(&self) -> &'static str {
+        match self {
+            Accent::Hat => "ˆ",
+            Accent::Overline => "‾",
+            Accent::Underline => "_",
+            Accent::Vector => "→",
+            Accent::Tilde => "˜",
+            Accent::Dot => "˙",
+            Accent::DoubleDot => "¨",
+            Accent::Underbrace => "⏟",
+            Accent::Overbrace => "⏞",
+            Accent::Overset | Accent::Underset | Accent::Color | Accent::Cancel => "",
+        }
+    }
+}
@@ -1,3 +1,4 @@
+#[cfg(feature = "mathml")]
 use alemat::elements::Operator;
 
 use crate::lexer::token::TokenKind;
@@ -5,8 +6,8 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Arrows, like `->` or `=>`.
     Arrow,
-    Arrows,
     "uarr" | "uparrow" => Up,
     "darr" | "downarrow" => Down,
     "->" | "to" | "rarr" | "rightarrow" => Right,
@@ -18,11 +19,7 @@ generate_impl!(
     "harr" | "leftrightarrow" => LeftRight,
     "rArr" | "Rightarrow" => BigRight,
     "lArr" | "Leftarrow" => BigLeft,
-    "hArr" | "Leftrightarrow" => BigLeftRight,
-    prefixes:
-        RightTail => ">->>",
-        TwoHeadRight => "twoheadrightarrowtail",
-        Right => "->>"
+    "hArr" | "Leftrightarrow" => BigLeftRight
 );
 
 impl From<Arrow> for TokenKind {
@@ -31,6 +28,27 @@ impl From<Arrow> for TokenKind {
     }
 }
 
+impl Arrow {
+    /// Returns the Unicode character of this arrow.
+    pub(crate) fn as_unicode(&self) -> &'static str {
+        match self {
+            Arrow::Up => "↑",
+            Arrow::Down => "↓",
+            Arrow::Right => "→",
+            Arrow::RightTail => "↣",
+            Arrow::TwoHeadRight => "↠",
+            Arrow::TwoHeadRightTail => "⤖",
+            Arrow::MapsTo => "↦",
+            Arrow::Left => "←",
+            Arrow::LeftRight => "↔",
+            Arrow::BigRight => "⇒",
+            Arrow::BigLeft => "⇐",
+            Arrow::BigLeftRight => "⇔",
+        }
+    }
+}
+
+#[cfg(feature = "mathml")]
 impl From<Arrow> for Operator {
     fn from(value: Arrow) -> Self {
         match value {
@@ -3,16 +3,14 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Font commands changing the style of an expression, like `bb` or `cc`.
     FontCommand,
-    FontCommands,
     "bb" | "mathbf" => Bold,
     "bbb" | "mathbb" => BlackboardBold,
     "cc" | "mathcal" => Calligraphic,
     "tt" | "mathtt" => Typewriter,
     "fr" | "mathfrak" => Gothic,
-    "sf" | "mathsf" => SansSerif,
-    prefixes:
-        Bold => "bbb"
+    "sf" | "mathsf" => SansSerif
 );
 
 impl From<FontCommand> for TokenKind {
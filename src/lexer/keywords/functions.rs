@@ -3,8 +3,8 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Standard functions, like `sin` or `log`.
     Function,
-    Functions,
     "sin" => Sin,
     "cos" => Cos,
     "tan" => Tan,
@@ -34,15 +34,7 @@ generate_impl!(
     "min" => Min,
     "max" => Max,
     "f" => F,
-    "g" => G,
-    prefixes:
-        Sin => "sinh",
-        Cos => "cosh",
-        Tan => "tanh",
-        Sec => "sech",
-        Csc => "csch",
-        Cot => "coth",
-        G => "gcd" // or glb, but same length so it does not matter
+    "g" => G
 );
 
 impl From<Function> for TokenKind {
@@ -1,3 +1,4 @@
+#[cfg(feature = "mathml")]
 use alemat::elements::Ident;
 
 use crate::lexer::token::TokenKind;
@@ -5,8 +6,8 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Greek letters, like `alpha` or `Gamma`.
     Greek,
-    Greeks,
     "alpha" => Alpha,
     "beta" => Beta,
     "gamma" => Gamma,
@@ -48,6 +49,48 @@ impl From<Greek> for TokenKind {
     }
 }
 
+impl Greek {
+    /// Returns the (upright) Unicode letter of this greek letter.
+    pub(crate) fn as_unicode(&self) -> &'static str {
+        match self {
+            Greek::Alpha => "α",
+            Greek::Beta => "β",
+            Greek::Gamma => "γ",
+            Greek::BigGamma => "Γ",
+            Greek::Delta => "δ",
+            Greek::Epsilon => "ε",
+            Greek::Varepsilon => "ϵ",
+            Greek::Zeta => "ζ",
+            Greek::Eta => "η",
+            Greek::Theta => "θ",
+            Greek::Vartheta => "ϑ",
+            Greek::Iota => "ι",
+            Greek::Kappa => "κ",
+            Greek::Lambda => "λ",
+            Greek::Mu => "μ",
+            Greek::Nu => "ν",
+            Greek::Xi => "ξ",
+            Greek::BigXi => "Ξ",
+            Greek::Pi => "π",
+            Greek::BigPi => "Π",
+            Greek::Rho => "ρ",
+            Greek::Sigma => "σ",
+            Greek::BigSigma => "Σ",
+            Greek::Tau => "τ",
+            Greek::Upsilon => "υ",
+            Greek::Phi => "φ",
+            Greek::BigPhi => "Φ",
+            Greek::Varphi => "ϕ",
+            Greek::Chi => "χ",
+            Greek::Psi => "ψ",
+            Greek::BigPsi => "Ψ",
+            Greek::Omega => "ω",
+            Greek::BigOmega => "Ω",
+        }
+    }
+}
+
+#[cfg(feature = "mathml")]
 impl From<Greek> for Ident {
     fn from(value: Greek) -> Self {
         match value {
@@ -1,3 +1,4 @@
+#[cfg(feature = "mathml")]
 use alemat::{
     elements::{grouping::Phantom, IntoElements, Operator},
     Element, Elements,
@@ -8,8 +9,8 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Brackets and other grouping symbols, like `(` or `|__`.
     Grouping,
-    Groupings,
     "(" => OpenParen,
     ")" => CloseParen,
     "[" => OpenBracket,
@@ -24,10 +25,7 @@ generate_impl!(
     "floor" => Floor,
     "ceil" => Ceiling,
     "norm" => NormFn,
-    "||" => Norm,
-    prefixes:
-        OpenParen => "(:",
-        OpenBrace => "{:"
+    "||" => Norm
 );
 
 impl From<Grouping> for TokenKind {
@@ -36,18 +34,21 @@ impl From<Grouping> for TokenKind {
     }
 }
 
+#[cfg(feature = "mathml")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct GrpCtxt {
     pub(crate) grp: Grouping,
     pub(crate) is_opening: bool,
 }
 
+#[cfg(feature = "mathml")]
 impl From<(Grouping, bool)> for GrpCtxt {
     fn from((grp, is_opening): (Grouping, bool)) -> Self {
         Self { grp, is_opening }
     }
 }
 
+#[cfg(feature = "mathml")]
 impl From<GrpCtxt> for Element {
     fn from(grp: GrpCtxt) -> Self {
         let GrpCtxt { grp, is_opening } = grp;
@@ -73,6 +74,7 @@ impl From<GrpCtxt> for Element {
     }
 }
 
+#[cfg(feature = "mathml")]
 impl IntoElements for GrpCtxt {
     fn into_elements(self) -> Elements {
         alemat::children![Element::from(self)].into_elements()
@@ -80,6 +82,29 @@ impl IntoElements for GrpCtxt {
 }
 
 impl Grouping {
+    /// Returns the Unicode character of this grouping symbol. Ignored grouping symbols are
+    /// empty.
+    pub(crate) fn as_unicode(&self, is_opening: bool) -> &'static str {
+        match self {
+            Grouping::OpenParen => "(",
+            Grouping::CloseParen => ")",
+            Grouping::OpenBracket => "[",
+            Grouping::CloseBracket => "]",
+            Grouping::OpenBrace => "{",
+            Grouping::CloseBrace => "}",
+            Grouping::LeftAngled => "⟨",
+            Grouping::RightAngled => "⟩",
+            Grouping::OpenIgnored | Grouping::CloseIgnored => "",
+            Grouping::Absolute => "|",
+            Grouping::Floor if is_opening => "⌊",
+            Grouping::Floor => "⌋",
+            Grouping::Ceiling if is_opening => "⌈",
+            Grouping::Ceiling => "⌉",
+            Grouping::NormFn | Grouping::Norm => "‖",
+        }
+    }
+
+    /// Returns `true` if this grouping symbol can be closed (or opened) by the other one.
     pub fn matches(&self, other: Self) -> bool {
         matches!(
             (*self, other),
@@ -3,8 +3,8 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Logical symbols, like `and` or `AA`.
     Logical,
-    Logicals,
     "and" => And,
     "or" => Or,
     "not" | "neg" => Not,
@@ -16,9 +16,7 @@ generate_impl!(
     "_|_" | "bot" => Bottom,
     "TT" | "top" => Top,
     "|--" | "vdash" => VerticalDash,
-    "|==" | "models" => Models,
-    prefixes:
-        If => "iff"
+    "|==" | "models" => Models
 );
 
 impl From<Logical> for TokenKind {
@@ -27,6 +25,27 @@ impl From<Logical> for TokenKind {
     }
 }
 
+impl Logical {
+    /// Returns the Unicode character (or word) of this logical symbol.
+    pub(crate) fn as_unicode(&self) -> &'static str {
+        match self {
+            Logical::And => "∧",
+            Logical::Or => "∨",
+            Logical::Not => "¬",
+            Logical::Implies => "⇒",
+            Logical::If => "if",
+            Logical::IfAndOnlyIf => "⇔",
+            Logical::ForAll => "∀",
+            Logical::Exists => "∃",
+            Logical::Bottom => "⊥",
+            Logical::Top => "⊤",
+            Logical::VerticalDash => "⊢",
+            Logical::Models => "⊨",
+        }
+    }
+}
+
+#[cfg(feature = "mathml")]
 impl From<Logical> for alemat::elements::Operator {
     fn from(value: Logical) -> Self {
         use alemat::elements::Operator;
@@ -11,18 +11,30 @@ macro_rules! define_keywords {
 }
 
 macro_rules! generate_impl {
-    ($kind:ident, $struct:ident, $($($lit:literal)|* => $var:ident),* $(,prefixes: $($($p_kind:ident)|* => $longer:literal),*)?) => {
-        const LITERALS: &[&[&str]] = $crate::lexer::keywords::macros::define_keywords!(
+    ($(#[$meta:meta])* $kind:ident, $($($lit:literal)|* => $var:ident),*) => {
+        pub(crate) const LITERALS: &[&[&str]] = $crate::lexer::keywords::macros::define_keywords!(
             $($($lit)|*),*
         );
 
-        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+        /// All the variants, in the same order as their keywords in [`LITERALS`].
+        pub(crate) const VARIANTS: &[$kind] = &[$($kind::$var),*];
+
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub enum $kind {
         $(
+            #[doc = concat!("Keywords:", $(" `", $lit, "`"),*)]
             $var,
         )*
         }
 
+        impl $kind {
+            /// Returns all the keywords of this variant.
+            pub(crate) fn literals(self) -> &'static [&'static str] {
+                LITERALS[self as usize]
+            }
+        }
+
         impl AsRef<str> for $kind {
             fn as_ref(&self) -> &str {
                 match self {
@@ -47,109 +59,20 @@ macro_rules! generate_impl {
                 Ok(kind)
             }
         }
+    };
+}
 
-        fn map() -> &'static ::std::collections::BTreeMap<&'static str, $kind> {
-            use ::std::{collections::BTreeMap, sync::OnceLock};
-            static KEYWORDS: OnceLock<BTreeMap<&str, $kind>> = OnceLock::new();
-
-            KEYWORDS.get_or_init(|| {
-                LITERALS
-                    .iter()
-                    .flat_map(|s| s.iter())
-                    .map(|&arr_str| (arr_str, $kind::try_from(arr_str).unwrap()))
-                    .collect()
-            })
-        }
-
-        const fn min_len() -> usize {
-            let mut min_len = max_len();
-
-            let mut i = 0;
-
-            while i < LITERALS.len() {
-                let mut j = 0;
-
-                while j < LITERALS[i].len() {
-                    let len = LITERALS[i][j].len();
-                    if len < min_len {
-                        min_len = len;
-                    }
-                    j += 1;
-                }
-
-                i += 1;
-            }
-
-            min_len
-        }
-
-        const fn max_len() -> usize {
-            let mut max_len = 0;
-
-            let mut i = 0;
-
-            while i < LITERALS.len() {
-                let mut j = 0;
-
-                while j < LITERALS[i].len() {
-                    let len = LITERALS[i][j].len();
-
-                    if len > max_len {
-                        max_len = len;
-                    }
-
-                    j += 1;
-                }
-
-                i += 1;
-            }
-
-            max_len
-        }
-
-        impl $crate::lexer::KeywordKind for $kind {
-            fn prefix_of(&self) -> Option<usize> {
-                match *self {
-                    $(
-                    $(
-                        $($kind::$p_kind)|* => Some($longer.len()),
-                    )*
-                    )?
-
-                    _ => None,
-                }
-            }
-        }
-
-        pub struct $struct;
-
-        impl $crate::lexer::Keyword for $struct {
-            const MAX_LEN: usize = max_len();
-            const MIN_LEN: usize = min_len();
-
-            type Kind = $kind;
+pub(super) use {define_keywords, generate_impl};
 
-            fn get(key: &str) -> Option<Self::Kind> {
-                map().get(key).copied()
-            }
+/// Returns the number of all the keywords in the table of literals.
+pub(crate) const fn count(literals: &[&[&str]]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
 
-            fn starts_with(symbol: $crate::scanner::Symbol<'_>) -> bool {
-                use std::sync::OnceLock;
-                static FIRST_SYMBOLS: OnceLock<Vec<&str>> = OnceLock::new();
+    while i < literals.len() {
+        count += literals[i].len();
+        i += 1;
+    }
 
-                FIRST_SYMBOLS
-                    .get_or_init(|| {
-                        self::LITERALS
-                            .iter()
-                            .flat_map(|s| s.iter())
-                            .filter_map(|lit| lit.split("").nth(1))
-                            .collect()
-                    })
-                    .iter()
-                    .any(|&first_sym| first_sym == symbol.content)
-            }
-        }
-    };
+    count
 }
-
-pub(super) use {define_keywords, generate_impl};
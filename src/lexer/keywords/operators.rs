@@ -3,8 +3,8 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Operation symbols, like `+` or `xx`.
     Operator,
-    Operators,
     "+" => Plus,
     "-" => Minus,
     "'" => Prime,
@@ -29,15 +29,7 @@ generate_impl!(
     "nn" | "cap" => Cap,
     "nnn" | "bigcap" => BigCap,
     "uu" | "cup" => Cup,
-    "uuu" | "bigcup" => BigCup,
-    prefixes:
-        Minus => "-:",
-        Dot => "**",
-        Asterisk => "***",
-        LTimes => "|><|",
-        Wedge => "^^^",
-        Cap => "nnn",
-        Cup => "uuu"
+    "uuu" | "bigcup" => BigCup
 );
 
 impl From<Operator> for TokenKind {
@@ -46,6 +38,40 @@ impl From<Operator> for TokenKind {
     }
 }
 
+impl Operator {
+    /// Returns the Unicode character of this operator.
+    pub(crate) fn as_unicode(&self) -> &'static str {
+        match self {
+            Operator::Plus => "+",
+            Operator::Minus => "−",
+            Operator::Prime => "′",
+            Operator::Dot => "⋅",
+            Operator::Asterisk => "∗",
+            Operator::Star => "⋆",
+            Operator::ForwardSlashLiteral => "/",
+            Operator::Backslash => "∖",
+            Operator::Times => "×",
+            Operator::Divide => "÷",
+            Operator::LTimes => "⋉",
+            Operator::RTimes => "⋊",
+            Operator::Bowtie => "⋈",
+            Operator::Circle => "∘",
+            Operator::OPlus => "⊕",
+            Operator::OTimes => "⊗",
+            Operator::ODot => "⊙",
+            Operator::Sum => "∑",
+            Operator::Prod => "∏",
+            Operator::Wedge => "∧",
+            Operator::BigWedge => "⋀",
+            Operator::Cap => "∩",
+            Operator::BigCap => "⋂",
+            Operator::Cup => "∪",
+            Operator::BigCup => "⋃",
+        }
+    }
+}
+
+#[cfg(feature = "mathml")]
 impl From<Operator> for alemat::elements::Operator {
     fn from(value: Operator) -> Self {
         use alemat::elements::Operator;
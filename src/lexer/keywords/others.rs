@@ -1,6 +1,7 @@
 //! This corresponds to the `Miscellaneous symbols` table at [Ascii math syntax
 //! page](http://asciimath.org/#syntax).
 
+#[cfg(feature = "mathml")]
 use alemat::{
     elements::{Ident, Operator},
     Element,
@@ -11,8 +12,8 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Miscellaneous symbols, like `oo` or `frac`.
     Other,
-    Others,
     "," => Comma,
     "frac" => Fraction,
     "/" => ForwardSlash,
@@ -51,13 +52,8 @@ generate_impl!(
     "QQ" => Rational,
     "RR" => Irrational,
     "ZZ" => Integer,
-    "text" => Text,
-    "\"" => Quote,
-    prefixes:
-        VerticalBar => "|\\|",
-        ForwardSlash => "/_",
-        Subscript => "__|",
-        Angle => "/_\\"
+    "text" | "mbox" => Text,
+    "\"" => Quote
 );
 
 impl From<Other> for TokenKind {
@@ -66,6 +62,56 @@ impl From<Other> for TokenKind {
     }
 }
 
+impl Other {
+    /// Returns the Unicode character of this symbol. Symbols without a character of their own
+    /// (e.g. `frac` or `sqrt`) return the AsciiMath keyword.
+    pub(crate) fn as_unicode(&self) -> &'static str {
+        match self {
+            Other::Comma => ",",
+            Other::Fraction => "frac",
+            Other::ForwardSlash => "/",
+            Other::Power => "^",
+            Other::Subscript => "_",
+            Other::SquareRoot => "√",
+            Other::Root => "root",
+            Other::Integral => "∫",
+            Other::OIntegral => "∮",
+            Other::Partial => "∂",
+            Other::Nabla => "∇",
+            Other::PlusMinus => "±",
+            Other::EmptySet => "∅",
+            Other::Infinity => "∞",
+            Other::Aleph => "ℵ",
+            Other::Therefore => "∴",
+            Other::Because => "∵",
+            Other::LowDots => "…",
+            Other::CenterDots => "⋯",
+            Other::VerticalDots => "⋮",
+            Other::DiagonalDots => "⋱",
+            Other::VerticalBar => "|",
+            Other::VerticalBars => "∥",
+            Other::VerticalBarsWide => "|  |",
+            Other::Angle => "∠",
+            Other::Frown => "⌢",
+            Other::Triangle => "△",
+            Other::Diamond => "◇",
+            Other::Square => "□",
+            Other::LeftFloor => "⌊",
+            Other::RightFloor => "⌋",
+            Other::LeftCeiling => "⌈",
+            Other::RightCeiling => "⌉",
+            Other::Complex => "ℂ",
+            Other::Natural => "ℕ",
+            Other::Rational => "ℚ",
+            Other::Irrational => "ℝ",
+            Other::Integer => "ℤ",
+            Other::Text => "text",
+            Other::Quote => "\"",
+        }
+    }
+}
+
+#[cfg(feature = "mathml")]
 impl From<Other> for Element {
     fn from(value: Other) -> Self {
         match value {
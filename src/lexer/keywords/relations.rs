@@ -1,3 +1,4 @@
+#[cfg(feature = "mathml")]
 use alemat::elements::Operator;
 
 use crate::lexer::token::TokenKind;
@@ -5,8 +6,8 @@ use crate::lexer::token::TokenKind;
 use super::macros::generate_impl;
 
 generate_impl!(
+    /// Relation symbols, like `=` or `sub`.
     Relation,
-    Relations,
     "=" => Eq,
     "!=" | "ne" => NotEq,
     ":=" => Define,
@@ -29,14 +30,7 @@ generate_impl!(
     "_=" | "equiv" => Equivalent,
     "~=" | "cong" => Congruent,
     "~~" | "approx" => Approximate,
-    "prop" | "propto" => Prop,
-    prefixes:
-        LessThan => "<=",
-        GreaterThan => ">=", // or >-, or >-=
-        Prec => "preceq", // or -<=, but preceq is longer that both -< and prec
-        Succ => "succeq", // or >-=, but succeq is longer that both >- and succ
-        Subset => "subseteq",
-        Superset => "supseteq"
+    "prop" | "propto" => Prop
 );
 
 impl From<Relation> for TokenKind {
@@ -45,6 +39,38 @@ impl From<Relation> for TokenKind {
     }
 }
 
+impl Relation {
+    /// Returns the Unicode character of this relation.
+    pub(crate) fn as_unicode(&self) -> &'static str {
+        match self {
+            Relation::Eq => "=",
+            Relation::NotEq => "≠",
+            Relation::Define => "≔",
+            Relation::LessThan => "<",
+            Relation::GreaterThan => ">",
+            Relation::LessEqualThan => "≤",
+            Relation::GreaterEqualThan => "≥",
+            Relation::MuchLessThan => "≪",
+            Relation::MuchGreaterThan => "≫",
+            Relation::Prec => "≺",
+            Relation::PrecEq => "⪯",
+            Relation::Succ => "≻",
+            Relation::SuccEq => "⪰",
+            Relation::In => "∈",
+            Relation::NotIn => "∉",
+            Relation::Subset => "⊂",
+            Relation::Superset => "⊃",
+            Relation::SubsetEq => "⊆",
+            Relation::SupersetEq => "⊇",
+            Relation::Equivalent => "≡",
+            Relation::Congruent => "≅",
+            Relation::Approximate => "≈",
+            Relation::Prop => "∝",
+        }
+    }
+}
+
+#[cfg(feature = "mathml")]
 impl From<Relation> for Operator {
     fn from(value: Relation) -> Self {
         match value {
@@ -14,6 +14,7 @@ mod numbers;
 mod operators;
 mod others;
 mod relations;
+mod unicode;
 
 macro_rules! test_snap {
     ($name:ident, $input:literal) => {
@@ -30,6 +31,8 @@ use test_snap;
 
 test_snap!(skip_whitespace, "   alpha   24.42");
 test_snap!(division, "a/b");
+test_snap!(longest_across_tables, "gammag|-><=>|quad|/_\\__|");
+test_snap!(unclosed_text, "mbox(a \"b");
 
 struct Snapshot<T>(T);
 
@@ -43,7 +46,7 @@ impl std::fmt::Display for Snapshot<Token<'_>> {
         f.write_str(token.as_str())?;
         f.write_str("\n")?;
         f.write_str(&indent)?;
-        f.write_str(&("^".repeat(token.as_str().len())))?;
+        f.write_str(&("^".repeat(token.as_str().chars().count())))?;
         f.write_str(" -> ")?;
         f.write_fmt(format_args!("{:?}", token.kind()))?;
         f.write_str(" at: ")?;
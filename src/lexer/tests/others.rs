@@ -6,3 +6,6 @@ super::test_snap!(shapes, "square diamond triangle /_\\ frown /_");
 super::test_snap!(number_sets, "CCNNQQRRZZ");
 super::test_snap!(floor_ceil_power, "^|__ __| |~ rceiling +-");
 super::test_snap!(text, "text(some text) \"also text\"");
+super::test_snap!(text_parens, "text(f(x) is defined) text(a) b)");
+super::test_snap!(text_escapes, r#""say \"hi\"" text(\( \\)"#);
+super::test_snap!(mbox, "mbox(some text) mboxx");
@@ -0,0 +1,9 @@
+// this module tests lexing of Unicode math characters into the keywords they're rendered from
+
+use super::Snapshot;
+
+super::test_snap!(greeks_and_relations, "α ≤ β ≠ π");
+super::test_snap!(arrows_and_others, "x→∞ ⇒ ∀y∈ℝ");
+super::test_snap!(operators, "a×b−c·d ∑ ∫");
+super::test_snap!(lookalikes, "a·b 5µm ⩽ 1");
+super::test_snap!(unknown, "é ☃");
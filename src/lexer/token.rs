@@ -13,15 +13,32 @@ pub(crate) struct Token<'src> {
     kind: TokenKind,
     content: &'src str,
     span: Span,
+
+    /// The whole input the token was lexed from, `content` is a slice of it.
+    src: &'src str,
 }
 
-impl Token<'_> {
-    pub fn with_span(content: &str, kind: TokenKind, span: Span) -> Token<'_> {
+impl<'src> Token<'src> {
+    pub fn with_span(src: &'src str, content: &'src str, kind: TokenKind, span: Span) -> Self {
         Token {
             content,
             kind,
             span,
+            src,
+        }
+    }
+
+    /// Returns the content of this token, the content of the given token following it, and
+    /// everything between them. Returns `None` if the tokens were lexed from different inputs.
+    pub fn joined(&self, next: &Token<'src>) -> Option<&'src str> {
+        if !core::ptr::eq(self.src, next.src) {
+            return None;
         }
+
+        let start = self.content.as_ptr() as usize - self.src.as_ptr() as usize;
+        let end = next.content.as_ptr() as usize - next.src.as_ptr() as usize + next.content.len();
+
+        self.src.get(start..end)
     }
 
     #[allow(dead_code)] // is used in testing
@@ -35,7 +52,7 @@ impl Token<'_> {
     }
 
     #[allow(dead_code)] // is used in testing
-    pub fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &'src str {
         self.content
     }
 }
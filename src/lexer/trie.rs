@@ -0,0 +1,206 @@
+//! Trie of all the keywords of all the keyword tables, used to find the longest keyword at the
+//! start of the input in a single pass.
+//!
+//! The trie is built at compile time. Its nodes are stored in a flat array, where every node
+//! points to its first child and to its next sibling. Children of the root are additionally
+//! indexed by their byte, since almost every token starts with a lookup there.
+
+use crate::scanner::Symbol;
+
+use super::{
+    keywords::{
+        accents, arrows, font_commands, functions, greeks, groupings, logicals, macros, operators,
+        others, relations,
+    },
+    TokenKind,
+};
+
+/// Index of a node in [`Trie::nodes`], where `0` (the root) stands for no node.
+type NodeIdx = u16;
+
+/// Adds the keywords of the given tables to an array of keywords, in the order of the tables.
+macro_rules! keywords {
+    ($($table:ident => $kind:ident),* $(,)?) => {{
+        const COUNT: usize = 0 $(+ macros::count($table::LITERALS))*;
+
+        let mut keywords = [("", TokenKind::Unimplemented); COUNT];
+        let mut k = 0;
+
+        $(
+        let mut i = 0;
+
+        while i < $table::LITERALS.len() {
+            let mut j = 0;
+
+            while j < $table::LITERALS[i].len() {
+                keywords[k] = ($table::LITERALS[i][j], TokenKind::$kind($table::VARIANTS[i]));
+                k += 1;
+                j += 1;
+            }
+
+            i += 1;
+        }
+        )*
+
+        keywords
+    }};
+}
+
+/// All the keywords with their kinds. If a keyword appeared in more than one table, the table
+/// listed first would take precedence.
+const KEYWORDS: &[(&str, TokenKind)] = &keywords!(
+    greeks => Greek,
+    arrows => Arrow,
+    functions => Function,
+    operators => Operator,
+    relations => Relation,
+    logicals => Logical,
+    groupings => Grouping,
+    others => Other,
+    accents => Accent,
+    font_commands => FontCommand,
+);
+
+/// Length of the longest keyword, in bytes.
+pub(crate) const MAX_LEN: usize = {
+    let mut max_len = 0;
+    let mut i = 0;
+
+    while i < KEYWORDS.len() {
+        if KEYWORDS[i].0.len() > max_len {
+            max_len = KEYWORDS[i].0.len();
+        }
+
+        i += 1;
+    }
+
+    max_len
+};
+
+/// Upper bound of the number of nodes: the root and a node for every byte of every keyword.
+const MAX_NODES: usize = {
+    let mut nodes = 1;
+    let mut i = 0;
+
+    while i < KEYWORDS.len() {
+        nodes += KEYWORDS[i].0.len();
+        i += 1;
+    }
+
+    nodes
+};
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    byte: u8,
+    first_child: NodeIdx,
+    next_sibling: NodeIdx,
+    kind: Option<TokenKind>,
+}
+
+impl Node {
+    const EMPTY: Node = Node {
+        byte: 0,
+        first_child: 0,
+        next_sibling: 0,
+        kind: None,
+    };
+}
+
+struct Trie {
+    nodes: [Node; MAX_NODES],
+    root: [NodeIdx; 256],
+}
+
+static TRIE: Trie = Trie::new(KEYWORDS);
+
+impl Trie {
+    const fn new(keywords: &[(&str, TokenKind)]) -> Self {
+        assert!(MAX_NODES <= NodeIdx::MAX as usize, "too many keyword bytes");
+
+        let mut trie = Trie {
+            nodes: [Node::EMPTY; MAX_NODES],
+            root: [0; 256],
+        };
+
+        let mut len = 1;
+        let mut k = 0;
+
+        while k < keywords.len() {
+            let (keyword, kind) = keywords[k];
+            let bytes = keyword.as_bytes();
+
+            let mut node = 0;
+            let mut b = 0;
+
+            while b < bytes.len() {
+                let mut child = trie.child(node, bytes[b]);
+
+                if child == 0 {
+                    child = len as NodeIdx;
+                    len += 1;
+
+                    trie.nodes[child as usize] = Node {
+                        byte: bytes[b],
+                        next_sibling: trie.nodes[node as usize].first_child,
+                        ..Node::EMPTY
+                    };
+                    trie.nodes[node as usize].first_child = child;
+
+                    if node == 0 {
+                        trie.root[bytes[b] as usize] = child;
+                    }
+                }
+
+                node = child;
+                b += 1;
+            }
+
+            if trie.nodes[node as usize].kind.is_none() {
+                trie.nodes[node as usize].kind = Some(kind);
+            }
+
+            k += 1;
+        }
+
+        trie
+    }
+
+    /// Returns the child of the node for the given byte, or `0` if there's none.
+    const fn child(&self, node: NodeIdx, byte: u8) -> NodeIdx {
+        if node == 0 {
+            return self.root[byte as usize];
+        }
+
+        let mut child = self.nodes[node as usize].first_child;
+
+        while child != 0 && self.nodes[child as usize].byte != byte {
+            child = self.nodes[child as usize].next_sibling;
+        }
+
+        child
+    }
+}
+
+/// Finds the longest keyword the symbols start with, made of at most `max_len` symbols. Returns
+/// the kind of the keyword and the number of symbols it's made of.
+pub(crate) fn longest_match(symbols: &[Symbol<'_>], max_len: usize) -> Option<(TokenKind, usize)> {
+    let mut node = 0;
+    let mut longest = None;
+
+    for (i, sym) in symbols.iter().take(max_len).enumerate() {
+        for &byte in sym.content.as_bytes() {
+            node = TRIE.child(node, byte);
+
+            if node == 0 {
+                return longest;
+            }
+        }
+
+        if let Some(kind) = TRIE.nodes[node as usize].kind {
+            longest = Some((kind, i + 1));
+        }
+    }
+
+    longest
+}
@@ -1,14 +1,46 @@
 #![warn(missing_docs)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 //! Crate for parsing and rendering of [AsciiMath](http://asciimath.org/).
+//!
+//! Without the default `std` feature, the lexer, the parser and the abstract syntax tree only
+//! depend on `alloc`. The output formats need `std`.
 
+extern crate alloc;
+
+#[cfg(feature = "rayon")]
+mod batch;
+#[cfg(feature = "std")]
+mod cache;
+mod convert;
+pub mod cst;
+mod document;
+#[cfg(feature = "ide")]
+pub mod ide;
+pub mod keywords;
 mod lexer;
+mod limits;
 mod parser;
+#[cfg(feature = "std")]
+mod render;
+pub mod renderer;
 mod scanner;
+pub mod tokens;
+
+use alloc::{string::String, vec::Vec};
 
+#[cfg(feature = "mathml")]
 use alemat::MathMl;
 use scanner::Symbols;
 
+#[cfg(feature = "mathml")]
 pub use alemat::Writer;
+#[cfg(feature = "rayon")]
+pub use batch::{render_batch, BatchItem, BatchOptions};
+#[cfg(feature = "std")]
+pub use cache::{Backend, RenderCache};
+pub use document::Document;
+pub use lexer::Span;
+pub use limits::{LimitError, Limits};
 pub use parser::*;
 
 /// Parse asciimath content into an abstract syntax tree. The whole input is interpreted as a
@@ -20,6 +52,35 @@ where
     AsciiMath::parse(input)
 }
 
+/// Parse untrusted asciimath content into an abstract syntax tree, like [`parse`], but within
+/// the given [`Limits`]. The whole input is parsed at once.
+///
+/// # Errors
+///
+/// Returns the [`LimitError`] of the first limit the input exceeds.
+pub fn parse_limited<'src>(
+    input: &'src str,
+    limits: &Limits,
+) -> Result<Vec<Expression<'src>>, LimitError> {
+    limits.check_input(input)?;
+
+    let mut ascii_math = AsciiMath::limited(input, limits);
+    let exprs: Vec<_> = ascii_math.by_ref().collect();
+
+    match ascii_math.exceeded(limits) {
+        Some(err) => Err(err),
+        None => Ok(exprs),
+    }
+}
+
+/// Rewrite Unicode math characters in the input into their AsciiMath keywords, e.g. `α ≤ β → ∞`
+/// into `alpha <= beta -> oo`. Characters without a keyword and the content of texts are kept as
+/// they are. The parser recognizes the Unicode characters as well, so this is only needed to get
+/// a plain AsciiMath source.
+pub fn unicode_to_asciimath(input: &str) -> String {
+    convert::unicode_to_asciimath(input)
+}
+
 /// Write an abstract syntax tree into the [`Writer`]. The resulting output is controlled by the
 /// implementation of passed in [`Writer`].
 ///
@@ -27,6 +88,7 @@ where
 ///
 /// The [`Writer`] may fail to write the mathml. In such case the error defined by the [`Writer`]
 /// implementation is returned.
+#[cfg(feature = "mathml")]
 pub fn write_mathml<'w, W>(
     ascii_math: AsciiMath<'_>,
     writer: &'w mut W,
@@ -41,8 +103,210 @@ where
     Ok(writer)
 }
 
+/// Render the abstract syntax tree with the given [`Renderer`](renderer::Renderer), producing the
+/// output of the renderer for every expression. See the [`renderer`] module for implementing
+/// custom renderers.
+pub fn render_with<R>(ascii_math: AsciiMath<'_>, renderer: &mut R) -> Vec<R::Output>
+where
+    R: renderer::Renderer + ?Sized,
+{
+    ascii_math.map(|expr| renderer.expr(&expr)).collect()
+}
+
 /// Render the abstract syntax tree into a string of mathml.
+#[cfg(feature = "mathml")]
 pub fn render_mathml(ascii_math: AsciiMath<'_>) -> String {
-    let mathml = MathMl::from(ascii_math);
-    mathml.render().expect("BufMathMlWriter does not fail.")
+    render::mathml::render(ascii_math, &RenderOptions::default())
+}
+
+/// Parse and render untrusted asciimath content into a string of mathml, like [`render_mathml`],
+/// but within the given [`Limits`]. The output is sanitized like the output of
+/// [`render_mathml_safe`].
+///
+/// # Errors
+///
+/// Returns the [`LimitError`] of the first limit the input or the output exceeds.
+#[cfg(feature = "mathml")]
+pub fn render_mathml_limited(input: &str, limits: &Limits) -> Result<String, LimitError> {
+    let exprs = parse_limited(input, limits)?;
+
+    let options = RenderOptions {
+        safe: true,
+        ..Default::default()
+    };
+
+    render::mathml::render_limited(exprs, &options, limits)
+}
+
+/// Render the abstract syntax tree into a string of mathml that is safe to insert into HTML
+/// without a separate sanitizer, for rendering of untrusted input:
+///
+/// - the content of texts, numbers, identifiers and operators is escaped, so input like
+///   `"<script>"` or `"&lt;"` is displayed as it's written,
+/// - control characters, except tabs and line breaks, are stripped,
+/// - colors that are not valid CSS colors, like `color(red" onclick="...)(x)`, are dropped, and
+///   the colored expression is rendered without color.
+///
+/// The output of [`render_mathml`] is the same for trusted input, except for the escaping.
+#[cfg(feature = "mathml")]
+pub fn render_mathml_safe(ascii_math: AsciiMath<'_>) -> String {
+    let options = RenderOptions {
+        safe: true,
+        ..Default::default()
+    };
+
+    render::mathml::render(ascii_math, &options)
+}
+
+#[cfg(feature = "mathml")]
+pub use render::mathml::{Direction, DisplayMode, RenderOptions};
+
+/// Render the abstract syntax tree into a string of mathml with the given [`RenderOptions`], e.g.
+/// in display mode, with alternative text or indented:
+///
+/// ```
+/// use mathemascii::{DisplayMode, RenderOptions};
+///
+/// let options = RenderOptions {
+///     display: DisplayMode::Block,
+///     alttext: Some(String::from("x^2")),
+///     ..Default::default()
+/// };
+///
+/// let mathml = mathemascii::render_mathml_with(mathemascii::parse("x^2"), &options);
+///
+/// assert_eq!(
+///     mathml,
+///     r#"<math display="block" alttext="x^2"><msup><mi>x</mi><mn>2</mn></msup></math>"#
+/// );
+/// ```
+#[cfg(feature = "mathml")]
+pub fn render_mathml_with(ascii_math: AsciiMath<'_>, options: &RenderOptions) -> String {
+    render::mathml::render(ascii_math, options)
+}
+
+/// Render the abstract syntax tree into the given [`core::fmt::Write`] sink with the given
+/// [`RenderOptions`]. The output is the same as the one of [`render_mathml_with`], but the markup
+/// is written into the sink while the expressions are walked, without building the elements or
+/// the complete output first:
+///
+/// ```
+/// use mathemascii::RenderOptions;
+///
+/// let mut mathml = String::new();
+/// mathemascii::stream_mathml(mathemascii::parse("x^2"), &RenderOptions::default(), &mut mathml)
+///     .unwrap();
+///
+/// assert_eq!(mathml, "<math><msup><mi>x</mi><mn>2</mn></msup></math>");
+/// ```
+///
+/// # Errors
+///
+/// Returns the error of the sink, if writing into it fails.
+#[cfg(feature = "mathml")]
+pub fn stream_mathml<W>(
+    ascii_math: AsciiMath<'_>,
+    options: &RenderOptions,
+    out: &mut W,
+) -> core::fmt::Result
+where
+    W: core::fmt::Write + ?Sized,
+{
+    render::mathml::write(ascii_math, options, out)
+}
+
+/// Render the abstract syntax tree into the given [`std::io::Write`] sink, like
+/// [`stream_mathml`]. The markup is written in many small pieces, so unbuffered sinks like files
+/// should be wrapped in a [`std::io::BufWriter`].
+///
+/// # Errors
+///
+/// Returns the error of the sink, if writing into it fails.
+#[cfg(feature = "mathml")]
+pub fn stream_mathml_io<W>(
+    ascii_math: AsciiMath<'_>,
+    options: &RenderOptions,
+    out: &mut W,
+) -> std::io::Result<()>
+where
+    W: std::io::Write + ?Sized,
+{
+    render::mathml::write_io(ascii_math, options, out)
+}
+
+/// Render the abstract syntax tree into [Typst](https://typst.app/) math markup. The output
+/// contains only the content of the math block, without the surrounding `$` delimiters.
+#[cfg(feature = "std")]
+pub fn render_typst(ascii_math: AsciiMath<'_>) -> String {
+    let exprs: Vec<_> = ascii_math.collect();
+    render::typst::Typst::render(&exprs)
+}
+
+/// Render the abstract syntax tree into an Office Math Markup Language (OMML) `<m:oMath>`
+/// element. This is the native equation format of Microsoft Word, so the output can be embedded
+/// into the paragraphs of `.docx` documents as editable equations.
+#[cfg(feature = "std")]
+pub fn render_omml(ascii_math: AsciiMath<'_>) -> String {
+    let exprs: Vec<_> = ascii_math.collect();
+    render::omml::Omml::render(&exprs)
+}
+
+/// Stylesheet for the output of [`render_html`]. It has to be included in the page that displays
+/// the rendered math.
+#[cfg(feature = "std")]
+pub const HTML_STYLESHEET: &str = include_str!("render/html.css");
+
+/// Render the abstract syntax tree into HTML `<span>` elements. The output is meant to be styled
+/// with [`HTML_STYLESHEET`] and can be used in environments where MathMl is not supported.
+#[cfg(feature = "std")]
+pub fn render_html(ascii_math: AsciiMath<'_>) -> String {
+    let exprs: Vec<_> = ascii_math.collect();
+    render::html::Html::render(&exprs)
+}
+
+#[cfg(feature = "std")]
+pub use render::art::ArtOptions;
+
+/// Render the abstract syntax tree into multi-line text art for terminals and logs. Fractions are
+/// drawn with horizontal bars, scripts are raised and lowered and the limits of big operators
+/// are stacked. See [`ArtOptions`] for configuration of the output width and ASCII-only output.
+#[cfg(feature = "std")]
+pub fn render_art(ascii_math: AsciiMath<'_>, options: ArtOptions) -> String {
+    let exprs: Vec<_> = ascii_math.collect();
+    render::art::Art::render(&exprs, options)
+}
+
+#[cfg(feature = "std")]
+pub use render::svg::SvgOptions;
+
+/// Render the abstract syntax tree into a standalone SVG image. The math is laid out by this
+/// crate, so the image can be used where no math rendering is available, e.g. in emails and PDFs.
+/// See [`SvgOptions`] for configuration of the font size and color.
+///
+/// The glyphs are drawn as paths of a built-in stroke font, with the layout measured from the same
+/// outlines, so the image looks the same in every viewer and needs no installed fonts.
+#[cfg(feature = "std")]
+pub fn render_svg(ascii_math: AsciiMath<'_>, options: &SvgOptions) -> String {
+    let exprs: Vec<_> = ascii_math.collect();
+    render::svg::Layout::render(&exprs, options)
+}
+
+/// Stylesheet for the output of [`highlight_html`]. It has to be included in the page that
+/// displays the highlighted source.
+#[cfg(feature = "std")]
+pub const HIGHLIGHT_STYLESHEET: &str = include_str!("render/highlight.css");
+
+/// Highlight the AsciiMath source with `<span class>` elements, one for each token, the way the
+/// parser sees it. The output is meant to be styled with [`HIGHLIGHT_STYLESHEET`], and placed in
+/// an element that keeps the whitespace, like `<pre>` or `<code>`.
+#[cfg(feature = "std")]
+pub fn highlight_html(input: &str) -> String {
+    render::highlight::html(input)
+}
+
+/// Highlight the AsciiMath source with ANSI escape sequences, for output in terminals. See
+/// [`tokens::tokenize`] for the tokens to highlight in other ways.
+#[cfg(feature = "std")]
+pub fn highlight_ansi(input: &str) -> String {
+    render::highlight::ansi(input)
 }
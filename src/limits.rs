@@ -0,0 +1,102 @@
+//! Limits on the resources used for parsing and rendering of untrusted input.
+
+use core::fmt::Display;
+
+/// Limits on the size of the input, the parsed expressions and the rendered output, for parsing
+/// and rendering of untrusted input, see [`parse_limited`](crate::parse_limited) and
+/// [`render_mathml_limited`](crate::render_mathml_limited).
+///
+/// The parser and the renderers are recursive, so deeply nested input like `sqrt sqrt sqrt ...`
+/// or thousands of `(` would overflow the stack without a limit on the nesting depth. The default
+/// limits are generous for handwritten formulas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    /// Maximum length of the input in bytes.
+    pub max_input_len: usize,
+
+    /// Maximum nesting depth of the expressions. Every grouping, operator argument and script
+    /// nests one level deeper, i.e. `sqrt(x)` is nested three levels deep.
+    pub max_depth: usize,
+
+    /// Maximum number of tokens in the input, not counting whitespace.
+    pub max_tokens: usize,
+
+    /// Maximum length of the rendered output in bytes. Rendering stops as soon as the output
+    /// exceeds it.
+    pub max_output_len: usize,
+}
+
+impl Limits {
+    /// Limits that allow any input, like the functions without limits.
+    pub const UNLIMITED: Limits = Limits {
+        max_input_len: usize::MAX,
+        max_depth: usize::MAX,
+        max_tokens: usize::MAX,
+        max_output_len: usize::MAX,
+    };
+
+    /// Checks the length of the input.
+    pub(crate) fn check_input(&self, input: &str) -> Result<(), LimitError> {
+        if input.len() > self.max_input_len {
+            return Err(LimitError::InputTooLong {
+                max: self.max_input_len,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_input_len: 16 * 1024,
+            max_depth: 64,
+            max_tokens: 4096,
+            max_output_len: 1024 * 1024,
+        }
+    }
+}
+
+/// Error returned when the input exceeds one of the [`Limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitError {
+    /// The input is longer than the maximum length in bytes.
+    InputTooLong {
+        /// The exceeded limit.
+        max: usize,
+    },
+
+    /// The expressions are nested deeper than the maximum depth.
+    TooDeep {
+        /// The exceeded limit.
+        max: usize,
+    },
+
+    /// The input has more tokens than the maximum number of tokens.
+    TooManyTokens {
+        /// The exceeded limit.
+        max: usize,
+    },
+
+    /// The rendered output is longer than the maximum length in bytes.
+    OutputTooLong {
+        /// The exceeded limit.
+        max: usize,
+    },
+}
+
+impl Display for LimitError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LimitError::InputTooLong { max } => write!(f, "input is longer than {max} bytes"),
+            LimitError::TooDeep { max } => {
+                write!(f, "expressions are nested deeper than {max} levels")
+            }
+            LimitError::TooManyTokens { max } => write!(f, "input has more than {max} tokens"),
+            LimitError::OutputTooLong { max } => write!(f, "output is longer than {max} bytes"),
+        }
+    }
+}
+
+impl core::error::Error for LimitError {}
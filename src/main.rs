@@ -1,18 +1,77 @@
-use alemat::{BufMathMlWriter, DisplayAttr, MathMl, MathMlAttr, Writer};
+/// Output formats supported by the command line tool.
+enum Format {
+    MathMl,
+    Html,
+    Typst,
+    Omml,
+    Art,
+    Svg,
+}
+
+impl Format {
+    fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "mathml" => Some(Format::MathMl),
+            "html" => Some(Format::Html),
+            "typst" => Some(Format::Typst),
+            "omml" => Some(Format::Omml),
+            "art" => Some(Format::Art),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
 
 fn main() {
     let mut args = std::env::args().peekable();
     args.next(); // skip program name
 
-    let is_block = match args.peek() {
-        Some(arg) => {
-            matches!(arg.as_str(), "--block" | "-b")
+    let mut format = Format::MathMl;
+    let mut mathml_options = mathemascii::RenderOptions::default();
+    let mut art_options = mathemascii::ArtOptions::default();
+    let mut svg_options = mathemascii::SvgOptions::default();
+
+    while let Some(arg) = args.peek() {
+        match arg.as_str() {
+            "--block" | "-b" => mathml_options.display = mathemascii::DisplayMode::Block,
+            "--format" | "-f" => {
+                args.next(); // skip format argument
+
+                let value = args.peek().map(String::as_str).unwrap_or_default();
+                format = Format::from_arg(value).unwrap_or_else(|| {
+                    eprintln!(
+                        "Unknown format '{value}', expected one of: mathml, html, typst, omml, art, svg"
+                    );
+                    std::process::exit(1);
+                });
+            }
+            "--width" | "-w" => {
+                args.next(); // skip width argument
+
+                let value = args.peek().map(String::as_str).unwrap_or_default();
+                art_options.width = value.parse().unwrap_or_else(|_| {
+                    eprintln!("Invalid width '{value}', expected a number");
+                    std::process::exit(1);
+                });
+            }
+            "--ascii" | "-a" => art_options.ascii = true,
+            "--font-size" => {
+                args.next(); // skip font size argument
+
+                let value = args.peek().map(String::as_str).unwrap_or_default();
+                svg_options.font_size = value.parse().unwrap_or_else(|_| {
+                    eprintln!("Invalid font size '{value}', expected a number");
+                    std::process::exit(1);
+                });
+            }
+            "--color" => {
+                args.next(); // skip color argument
+
+                svg_options.color = args.peek().cloned().unwrap_or_default();
+            }
+            _ => break,
         }
-        None => false,
-    };
 
-    if is_block {
-        // skip blocks argument
         args.next();
     }
 
@@ -20,16 +79,14 @@ fn main() {
 
     let ascii_math = mathemascii::parse(&input);
 
-    let mut math = MathMl::from(ascii_math);
-
-    if is_block {
-        math.add_attr(MathMlAttr::Display(DisplayAttr::Block));
-    }
-
-    let math_ml = math
-        .write(&mut BufMathMlWriter::default())
-        .map(Writer::finish)
-        .unwrap();
+    let output = match format {
+        Format::MathMl => mathemascii::render_mathml_with(ascii_math, &mathml_options),
+        Format::Html => mathemascii::render_html(ascii_math),
+        Format::Typst => mathemascii::render_typst(ascii_math),
+        Format::Omml => mathemascii::render_omml(ascii_math),
+        Format::Art => mathemascii::render_art(ascii_math, art_options),
+        Format::Svg => mathemascii::render_svg(ascii_math, &svg_options),
+    };
 
-    println!("{math_ml}");
+    println!("{output}");
 }
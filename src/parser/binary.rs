@@ -1,8 +1,4 @@
-use alemat::elements::grouping::Style;
-use alemat::elements::radicals::Radical;
-use alemat::elements::scripted::UnderOver;
-use alemat::elements::{Frac, IntoElements};
-use alemat::{Attribute, Elements};
+use alloc::{borrow::Cow, boxed::Box};
 
 use crate::lexer::keywords::accents::Accent;
 use crate::lexer::keywords::others::Other;
@@ -11,9 +7,9 @@ use crate::{AsciiMath, Var, VarKind};
 
 use super::expr::SimpleExpr;
 
-/// Kinds of unary operators in Ascii math.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum BinaryKind {
+/// Kinds of binary operators in Ascii math.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryKind {
     /// Fraction binary operation, i.e. `frac(a)(b)` or `a/b`.
     Fraction,
 
@@ -23,7 +19,7 @@ pub(crate) enum BinaryKind {
     /// Overset binary operation, i.e. `overset(a)(b)`.
     Overset,
 
-    /// Overset binary operation, i.e. `overset(a)(b)`.
+    /// Underset binary operation, i.e. `underset(a)(b)`.
     Underset,
 
     /// Color binary operation, colors the second expression with the color of the first grouping.
@@ -74,20 +70,47 @@ impl TryFrom<TokenKind> for BinaryKind {
 
 /// Binary operator in Ascii math.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Binary {
+pub struct Binary<'src> {
     pub(crate) kind: BinaryKind,
-    pub(crate) expr_1: Box<SimpleExpr>,
-    pub(crate) expr_2: Box<SimpleExpr>,
+    pub(crate) expr_1: Box<SimpleExpr<'src>>,
+    pub(crate) expr_2: Box<SimpleExpr<'src>>,
     pub(crate) span: Span,
 }
 
-impl Binary {
+impl<'src> Binary<'src> {
     /// Returns the [`Span`] occupied by this binary operator expression.
     pub fn span(&self) -> Span {
         self.span
     }
 
-    pub(crate) fn parse(parser: &mut AsciiMath) -> Option<Self> {
+    /// Returns the kind of the binary operator.
+    pub fn kind(&self) -> BinaryKind {
+        self.kind
+    }
+
+    /// Returns the first expression, i.e. `a` in `frac(a)(b)`. For colors it's a text with the
+    /// color.
+    pub fn first(&self) -> &SimpleExpr<'src> {
+        &self.expr_1
+    }
+
+    /// Returns the second expression, i.e. `b` in `frac(a)(b)`.
+    pub fn second(&self) -> &SimpleExpr<'src> {
+        &self.expr_2
+    }
+
+    /// Returns the binary operator expression with its text copied, so that it no longer borrows
+    /// from the input.
+    pub fn into_owned(self) -> Binary<'static> {
+        Binary {
+            kind: self.kind,
+            expr_1: Box::new(self.expr_1.into_owned()),
+            expr_2: Box::new(self.expr_2.into_owned()),
+            span: self.span,
+        }
+    }
+
+    pub(crate) fn parse(parser: &mut AsciiMath<'src>) -> Option<Self> {
         let token = parser.iter.peek()?;
         let binary_kind = BinaryKind::try_from(token.kind()).ok()?;
 
@@ -98,21 +121,21 @@ impl Binary {
 
         let default_expr = || {
             SimpleExpr::Var(Var {
-                kind: VarKind::UnknownOperator(String::default()),
+                kind: VarKind::UnknownOperator(Cow::Borrowed("")),
                 span,
             })
         };
 
         let expr_1 = match binary_kind {
-            BinaryKind::Color => Box::new(parser.parse_grouping_as_str().unwrap_or_else(|| {
+            BinaryKind::Color => Box::new(parser.parse_grouping_as_str().unwrap_or(
                 SimpleExpr::Var(Var {
-                    kind: VarKind::Text(String::from("black")),
+                    kind: VarKind::Text(Cow::Borrowed("black")),
                     span: Span {
                         start: span.end,
                         end: span.end,
                     },
-                })
-            })),
+                }),
+            )),
             _ => Box::new(parser.parse_simple_expr().unwrap_or_else(default_expr)),
         };
 
@@ -130,55 +153,3 @@ impl Binary {
         Some(binary)
     }
 }
-
-impl IntoElements for Binary {
-    fn into_elements(self) -> Elements {
-        let to_elements = |expr: Box<SimpleExpr>| match *expr {
-            SimpleExpr::Grouping(grp) if grp.is_simple_grp() => grp.ungroup_into_elements(),
-            SimpleExpr::Grouping(grp) => grp.into_elements(),
-            _ => expr.into_elements(),
-        };
-
-        match self.kind {
-            BinaryKind::Fraction => Frac::builder()
-                .num(to_elements(self.expr_1))
-                .denom(to_elements(self.expr_2))
-                .build()
-                .into_elements(),
-            BinaryKind::Root => Radical::builder()
-                .index(to_elements(self.expr_1))
-                .content(to_elements(self.expr_2))
-                .build()
-                .into_elements(),
-            BinaryKind::Overset => UnderOver::builder()
-                .expr(to_elements(self.expr_2))
-                .over(to_elements(self.expr_1))
-                .build()
-                .into_elements(),
-            BinaryKind::Underset => UnderOver::builder()
-                .expr(to_elements(self.expr_2))
-                .under(to_elements(self.expr_1))
-                .build()
-                .into_elements(),
-            BinaryKind::Color => {
-                let SimpleExpr::Var(var) = *self.expr_1 else {
-                    panic!("Expected var with color information");
-                };
-
-                let Var {
-                    kind: VarKind::Text(color),
-                    ..
-                } = var
-                else {
-                    panic!("Expected var with color information");
-                };
-
-                let expr = to_elements(self.expr_2);
-
-                Style::from(expr)
-                    .with_attr([Attribute::MathColor(color)])
-                    .into_elements()
-            }
-        }
-    }
-}
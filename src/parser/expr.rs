@@ -1,10 +1,8 @@
-use alemat::elements::grouping::{Phantom, Row};
-use alemat::elements::scripted::{SubSup, UnderOver};
-use alemat::elements::{ColumnLine, Elements, IntoElements, Table, TableAttr, TableCell, TableRow};
+use alloc::{borrow::Cow, boxed::Box, vec, vec::Vec};
 
 use crate::{
     lexer::{
-        keywords::{functions::Function, groupings::GrpCtxt, operators::Operator, others::Other},
+        keywords::{functions::Function, groupings::Grouping, operators::Operator, others::Other},
         Span,
     },
     GroupingExpr, UnaryKind, VarKind,
@@ -19,18 +17,18 @@ use super::{binary::Binary, iter_ext::IterExt, unary::Unary, var::Var};
 /// * Unary - unary operator applied to an expression, i.e. `sqrt(a)`.
 /// * Binary - binary operator applied to two expressions, i.e. `root(3)(a + b)`.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum SimpleExpr {
+pub enum SimpleExpr<'src> {
     /// Variable - any number, identifier, greek letter etc.
-    Var(Var),
+    Var(Var<'src>),
 
     /// Grouping - any number of expressions surrounded by parentheses, brackets, braces etc.
-    Grouping(GroupingExpr),
+    Grouping(GroupingExpr<'src>),
 
     /// Unary operator applied to an expression, i.e. `sqrt(a)`.
-    Unary(Unary),
+    Unary(Unary<'src>),
 
     /// Binary operator applied to two expressions, i.e. `root(3)(a + b)`.
-    Binary(Binary),
+    Binary(Binary<'src>),
 
     /// Intermediate expression is simply a wrapped [`Expression`].
     /// AsciiMath differs Expression and Intermediate expression, but in this implementation they
@@ -38,10 +36,21 @@ pub enum SimpleExpr {
     /// iterator that produces multiple [`Expression`]s.
     ///
     /// [`AsciiMath`]: crate::AsciiMath
-    Interm(Box<Expression>),
+    Interm(Box<Expression<'src>>),
 }
 
-impl SimpleExpr {
+impl SimpleExpr<'_> {
+    /// Returns the expression with its text copied, so that it no longer borrows from the input.
+    pub fn into_owned(self) -> SimpleExpr<'static> {
+        match self {
+            SimpleExpr::Var(var) => SimpleExpr::Var(var.into_owned()),
+            SimpleExpr::Grouping(grp) => SimpleExpr::Grouping(grp.into_owned()),
+            SimpleExpr::Unary(unary) => SimpleExpr::Unary(unary.into_owned()),
+            SimpleExpr::Binary(binary) => SimpleExpr::Binary(binary.into_owned()),
+            SimpleExpr::Interm(inner) => SimpleExpr::Interm(Box::new(inner.into_owned())),
+        }
+    }
+
     /// Returns the [`Span`] of the expression.
     pub fn span(&self) -> Span {
         match self {
@@ -53,7 +62,10 @@ impl SimpleExpr {
         }
     }
 
-    fn is_underover(&self) -> bool {
+    /// Returns `true` if the scripts of the expression are placed below and above it, like for
+    /// `sum`, instead of to its right side.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn is_underover(&self) -> bool {
         match self {
             SimpleExpr::Var(var) => match var.kind {
                 VarKind::Operator(op) => matches!(
@@ -90,26 +102,51 @@ impl SimpleExpr {
 
 /// The main AsciiMath expression.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Expression {
-    pub(crate) interm: SimpleExpr,
-    pub(crate) subscript: Option<SimpleExpr>,
-    pub(crate) supscript: Option<SimpleExpr>,
+pub struct Expression<'src> {
+    pub(crate) interm: SimpleExpr<'src>,
+    pub(crate) subscript: Option<SimpleExpr<'src>>,
+    pub(crate) supscript: Option<SimpleExpr<'src>>,
 }
 
-impl Expression {
+impl<'src> Expression<'src> {
+    /// Returns the expression with its text copied, so that it no longer borrows from the input.
+    /// Useful for keeping the expression around after the input is dropped.
+    pub fn into_owned(self) -> Expression<'static> {
+        Expression {
+            interm: self.interm.into_owned(),
+            subscript: self.subscript.map(SimpleExpr::into_owned),
+            supscript: self.supscript.map(SimpleExpr::into_owned),
+        }
+    }
+
     /// Returns the [`Span`] of the expression.
     pub fn span(&self) -> Span {
         let span = self.interm.span();
         let start = span.start;
         let mut end = span.end;
 
-        if let Some(supscript) = &self.supscript {
-            end = supscript.span().end;
+        if let Some(script) = self.supscript.as_ref().or(self.subscript.as_ref()) {
+            end = end.max(script.span().end);
         }
 
         Span { start, end }
     }
 
+    /// Returns the expression without its subscript and superscript.
+    pub fn interm(&self) -> &SimpleExpr<'src> {
+        &self.interm
+    }
+
+    /// Returns the subscript of the expression, i.e. `i` in `x_i`.
+    pub fn subscript(&self) -> Option<&SimpleExpr<'src>> {
+        self.subscript.as_ref()
+    }
+
+    /// Returns the superscript of the expression, i.e. `2` in `x^2`.
+    pub fn supscript(&self) -> Option<&SimpleExpr<'src>> {
+        self.supscript.as_ref()
+    }
+
     /// Checks whether the expression has subscript or superscript.
     pub fn is_scripted(&self) -> bool {
         self.subscript.is_some() || self.supscript.is_some()
@@ -136,7 +173,7 @@ impl Expression {
     pub(crate) fn default_with_span(span: Span) -> Self {
         Expression {
             interm: SimpleExpr::Var(Var {
-                kind: VarKind::Text(String::from("")),
+                kind: VarKind::Text(Cow::Borrowed("")),
                 span,
             }),
             subscript: None,
@@ -145,7 +182,7 @@ impl Expression {
     }
 
     /// Returns `true` if the expression is a matrix.
-    fn is_matrix(&self) -> bool {
+    pub(crate) fn is_matrix(&self) -> bool {
         let SimpleExpr::Grouping(ref grp) = self.interm else {
             return false;
         };
@@ -189,64 +226,81 @@ impl Expression {
             _ => false,
         }
     }
+}
 
-    /// Transforms the [`Expression`] into a [`alemat::elements::Table`].
-    ///
-    /// # Panics
-    ///
-    /// If the expressions does not have the form of a matrix (or a vector).
-    fn into_matrix(self) -> Elements {
-        let SimpleExpr::Grouping(grp) = self.interm else {
-            panic!("Expected a matrix.");
-        };
+/// Borrowed view of an [`Expression`] that has the form of a matrix (or a vector), see
+/// [`Expression::as_matrix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<'e> {
+    /// The opening grouping symbol of the matrix.
+    pub left_grouping: Grouping,
 
-        let GroupingExpr {
-            left_grouping,
-            right_grouping,
-            expr,
-            ..
-        } = grp;
+    /// The closing grouping symbol of the matrix.
+    pub right_grouping: Grouping,
 
-        let first_row = expr.first().expect("Matrix row expected.");
+    /// Rows of the matrix. Each cell consists of all the expressions between two commas.
+    pub rows: Vec<Vec<Vec<&'e Expression<'e>>>>,
 
-        // preallocate maximal number of columns
-        let num_of_columns = match &first_row.interm {
-            SimpleExpr::Grouping(grp) => grp.len(),
-            _ => unreachable!(),
+    /// For each column `true` if it is followed by a solid vertical line.
+    pub column_lines: Vec<bool>,
+}
+
+impl<'e> Matrix<'e> {
+    /// Returns the number of columns in the widest row of the matrix.
+    pub fn num_of_columns(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+}
+
+impl Expression<'_> {
+    /// Returns a borrowed [`Matrix`] view of the expression, if the expression has the form of a
+    /// matrix (or a vector). Vertical bars are removed from the cells and recorded as column
+    /// lines, the same way as when rendering into [`alemat::elements::Table`].
+    pub fn as_matrix(&self) -> Option<Matrix<'_>> {
+        if !self.is_matrix() {
+            return None;
+        }
+
+        let SimpleExpr::Grouping(ref grp) = self.interm else {
+            return None;
         };
 
-        let mut column_lines = vec![ColumnLine::Solid; num_of_columns];
+        let num_of_columns = match &grp.expr.first()?.interm {
+            SimpleExpr::Grouping(grp) => grp.len(),
+            _ => return None,
+        };
 
-        let mut table = Table::default();
+        let mut column_lines = vec![true; num_of_columns];
+        let mut rows = Vec::new();
 
         let mut max_len = 0;
         let mut last_was_line = true;
-        for row in expr {
+        for row in &grp.expr {
             if row.is_comma() {
                 continue;
             }
 
-            let SimpleExpr::Grouping(grp) = row.interm else {
-                unreachable!("Expected a matrix row.");
+            let SimpleExpr::Grouping(ref row_grp) = row.interm else {
+                return None;
             };
 
-            let mut table_row = TableRow::default();
+            let mut cells: Vec<Vec<&Expression>> = Vec::new();
 
             let mut inserted = 0;
             let mut prev_line = false;
 
-            last_was_line &= grp
+            last_was_line &= row_grp
                 .expr
                 .last()
                 .map(Expression::is_vertical_bar)
                 .unwrap_or(false);
 
-            for (curr, mut e) in grp.expr.group_by_commas().enumerate() {
-                let is_line = e.len() == 1 && e[0].is_vertical_bar();
+            for (curr, cell) in row_grp.expr.iter().group_by_commas_ref().enumerate() {
+                let is_line = cell.len() == 1 && cell[0].is_vertical_bar();
 
                 if inserted != curr && !is_line {
                     if !prev_line {
-                        column_lines[inserted] = ColumnLine::None;
+                        column_lines[inserted] = false;
                     }
 
                     prev_line = false;
@@ -257,116 +311,27 @@ impl Expression {
                     continue;
                 }
 
-                let cell = match e.len() {
-                    2.. => {
-                        let mut r = Row::default();
-                        for exp in e {
-                            r.add_elements(exp.into_elements());
-                        }
-
-                        TableCell::from(r)
-                    }
-                    1 => {
-                        let e = e.pop().expect("Guaranteed to have one element");
-
-                        TableCell::from(e.into_elements())
-                    }
-                    _ => TableCell::from(Phantom::from(Elements::default())),
-                };
-
-                table_row.add_cell(cell);
-                inserted = table_row.cells().len() - 1;
+                cells.push(cell);
+                inserted = cells.len() - 1;
             }
 
-            max_len = max_len.max(table_row.cells().len());
-            table.add_row(table_row);
+            max_len = max_len.max(cells.len());
+            rows.push(cells);
         }
 
         column_lines.truncate(max_len);
 
         if !last_was_line {
-            column_lines[max_len - 1] = ColumnLine::None;
-        }
-
-        table.add_attr([TableAttr::ColumnLines(column_lines)]);
-
-        alemat::row![
-            GrpCtxt::from((left_grouping, true)),
-            table,
-            GrpCtxt::from((right_grouping, false))
-        ]
-        .into_elements()
-    }
-}
-
-impl IntoElements for Expression {
-    fn into_elements(self) -> Elements {
-        if self.is_matrix() {
-            return self.into_matrix();
-        }
-
-        let is_underover = self.interm.is_underover();
-
-        let inner = if self.is_scripted() {
-            self.interm.into_elements()
-        } else {
-            return self.interm.into_elements();
-        };
-
-        let sub = self.subscript.map(|s| match s {
-            SimpleExpr::Grouping(grp) => grp.ungroup_into_elements(),
-            _ => s.into_elements(),
-        });
-
-        let sup = self.supscript.map(|s| match s {
-            SimpleExpr::Grouping(grp) => grp.ungroup_into_elements(),
-            _ => s.into_elements(),
-        });
-
-        if is_underover {
-            let builder = UnderOver::builder().expr(inner);
-
-            match (sub, sup) {
-                (None, None) => unreachable!(),
-                (None, Some(sup)) => builder.over(sup).build(),
-                (Some(sub), None) => builder.under(sub).build(),
-                (Some(sub), Some(sup)) => builder.under(sub).over(sup).build(),
+            if let Some(last) = column_lines.last_mut() {
+                *last = false;
             }
-            .into_elements()
-        } else {
-            let builder = SubSup::builder().base(inner);
-
-            match (sub, sup) {
-                (None, None) => unreachable!(),
-                (None, Some(sup)) => builder.supscript(sup).build(),
-                (Some(sub), None) => builder.subscript(sub).build(),
-                (Some(sub), Some(sup)) => builder.subscript(sub).supscript(sup).build(),
-            }
-            .into_elements()
         }
-    }
-}
 
-impl IntoElements for SimpleExpr {
-    fn into_elements(self) -> Elements {
-        match self {
-            SimpleExpr::Var(var) => var.into_elements(),
-            SimpleExpr::Grouping(grp) => {
-                let lg = GrpCtxt::from((grp.left_grouping, true)).into_elements();
-                let mut rg = GrpCtxt::from((grp.right_grouping, false)).into_elements();
-
-                let mut elements = lg;
-
-                for e in grp.expr {
-                    elements.append(&mut e.into_elements());
-                }
-
-                elements.append(&mut rg);
-                Row::from(elements).into_elements()
-            }
-            SimpleExpr::Unary(unary) => unary.into_elements(),
-            SimpleExpr::Binary(binary) => binary.into_elements(),
-            SimpleExpr::Interm(inner) => inner.into_elements(),
-        }
+        Some(Matrix {
+            left_grouping: grp.left_grouping,
+            right_grouping: grp.right_grouping,
+            rows,
+            column_lines,
+        })
     }
 }
@@ -1,10 +1,7 @@
-use alemat::{elements::IntoElements, Element, Elements};
+use alloc::vec::Vec;
 
 use crate::{
-    lexer::{
-        keywords::groupings::{Grouping, GrpCtxt},
-        Span,
-    },
+    lexer::{keywords::groupings::Grouping, Span},
     Expression,
 };
 
@@ -13,7 +10,7 @@ use super::iter_ext::IterExt;
 /// AsciiMath grouping expression - any number of [`Expression`]s grouped between two grouping
 /// symbols, such as parentheses, brackets, etc.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct GroupingExpr {
+pub struct GroupingExpr<'src> {
     /// The opening grouping symbol.
     pub left_grouping: Grouping,
 
@@ -21,13 +18,13 @@ pub struct GroupingExpr {
     pub right_grouping: Grouping,
 
     /// The expressions inside the grouping, separated by comma.
-    pub expr: Vec<Expression>,
+    pub expr: Vec<Expression<'src>>,
 
     /// The span of the grouping.
     pub span: Span,
 }
 
-impl GroupingExpr {
+impl<'src> GroupingExpr<'src> {
     /// Replaces the grouping symbols with ignored parentheses.
     /// (See [`Grouping::OpenIgnored`] and [`Grouping::CloseIgnored`])
     pub fn ignored_parentheses(self) -> Self {
@@ -39,17 +36,19 @@ impl GroupingExpr {
     }
 
     /// Returns the group of expressions inside the grouping without the grouping symbols.
-    pub fn ungroup(self) -> Vec<Expression> {
+    pub fn ungroup(self) -> Vec<Expression<'src>> {
         self.expr
     }
 
-    /// Returns an iterator over the group of expression inside the grouping without the grouping
-    /// symbols and mapped by the given function.
-    pub(crate) fn ungroup_into_elements(self) -> Elements {
-        self.ungroup()
-            .into_iter()
-            .map(IntoElements::into_elements)
-            .collect()
+    /// Returns the grouping with the text of its expressions copied, so that it no longer borrows
+    /// from the input.
+    pub fn into_owned(self) -> GroupingExpr<'static> {
+        GroupingExpr {
+            left_grouping: self.left_grouping,
+            right_grouping: self.right_grouping,
+            expr: self.expr.into_iter().map(Expression::into_owned).collect(),
+            span: self.span,
+        }
     }
 
     /// Checks whether the grouping contains any expressions.
@@ -68,7 +67,7 @@ impl GroupingExpr {
     }
 
     /// Returns an iterator over the expressions between the grouping symbols.
-    pub fn iter_inner(&self) -> impl Iterator<Item = &Expression> {
+    pub fn iter_inner(&self) -> impl Iterator<Item = &Expression<'src>> {
         self.expr.iter()
     }
 
@@ -124,28 +123,3 @@ impl GroupingExpr {
         }
     }
 }
-
-impl IntoElements for GroupingExpr {
-    fn into_elements(self) -> alemat::Elements {
-        let mut el = alemat::children![GrpCtxt {
-            grp: self.left_grouping,
-            is_opening: true,
-        }]
-        .into_elements();
-
-        el.append(
-            &mut self
-                .expr
-                .into_iter()
-                .map(IntoElements::into_elements)
-                .collect::<Elements>(),
-        );
-
-        el.push(Element::from(GrpCtxt {
-            grp: self.right_grouping,
-            is_opening: false,
-        }));
-
-        el
-    }
-}
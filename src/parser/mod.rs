@@ -1,29 +1,41 @@
 //! Abstract syntax tree (AST), nodes and their parse implementations.
 
-use std::iter::Peekable;
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
 
 mod binary;
 mod expr;
 mod grouping;
 mod iter_ext;
+mod parsed;
+mod tokens;
 mod unary;
 mod var;
 
-use alemat::MathMl;
 pub use binary::*;
 pub use expr::*;
 pub use grouping::*;
+pub use parsed::*;
 pub use unary::*;
 pub use var::*;
 
 use crate::lexer::keywords::{groupings::Grouping, others::Other};
 use crate::lexer::{Span, Token, TokenIterator, TokenKind};
+use crate::limits::{LimitError, Limits};
 use crate::scanner::Symbols;
 
+use self::tokens::TokenStream;
+
 /// Iterator that parses AsciiMath input and yields [`Expression`]s.
 #[derive(Debug, Clone)]
 pub struct AsciiMath<'src> {
-    iter: Peekable<TokenIterator<'src>>,
+    iter: TokenStream<'src>,
+
+    /// Current nesting depth of the simple expressions, and the maximum depth.
+    depth: usize,
+    max_depth: usize,
+
+    /// Whether the maximum depth was exceeded. The parser yields no more expressions afterwards.
+    too_deep: bool,
 }
 
 impl<'s> AsciiMath<'s> {
@@ -31,13 +43,71 @@ impl<'s> AsciiMath<'s> {
     where
         S: Into<Symbols<'s>>,
     {
+        Self::new(TokenStream::lex(TokenIterator::tokenize(input)))
+    }
+
+    /// Creates a parser that parses the given, already lexed tokens.
+    pub(crate) fn replay(tokens: Vec<Token<'s>>) -> Self {
+        Self::new(TokenStream::replay(tokens))
+    }
+
+    /// Creates a parser that stops parsing once the input exceeds the given limits, see
+    /// [`AsciiMath::exceeded`].
+    pub(crate) fn limited(input: &'s str, limits: &Limits) -> Self {
+        let iter = TokenStream::lex(TokenIterator::tokenize(input));
+        Self::new(iter.with_max_tokens(limits.max_tokens)).with_max_depth(limits.max_depth)
+    }
+
+    /// Stops parsing once the expressions are nested deeper than the given depth, see
+    /// [`AsciiMath::is_too_deep`].
+    pub(crate) fn with_max_depth(self, max_depth: usize) -> Self {
+        AsciiMath { max_depth, ..self }
+    }
+
+    fn new(iter: TokenStream<'s>) -> Self {
         AsciiMath {
-            iter: TokenIterator::tokenize(input).peekable(),
+            iter,
+            depth: 0,
+            max_depth: usize::MAX,
+            too_deep: false,
         }
     }
 
-    fn parse_grouping_as_str(&mut self) -> Option<SimpleExpr> {
-        let mut content = String::default();
+    /// Returns the limit exceeded by the input parsed so far, if any.
+    pub(crate) fn exceeded(&self, limits: &Limits) -> Option<LimitError> {
+        if self.too_deep {
+            Some(LimitError::TooDeep {
+                max: limits.max_depth,
+            })
+        } else if self.iter.is_truncated() {
+            Some(LimitError::TooManyTokens {
+                max: limits.max_tokens,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Whether the maximum depth was exceeded. The parser yields no more expressions afterwards.
+    pub(crate) fn is_too_deep(&self) -> bool {
+        self.too_deep
+    }
+
+    /// Number of tokens consumed by the parser so far.
+    pub(crate) fn consumed(&self) -> usize {
+        self.iter.consumed()
+    }
+
+    /// Whether the parser has reached the end of its tokens.
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.iter.is_exhausted()
+    }
+
+    /// Parses a grouping into a text of its content, without whitespace. The text borrows from the
+    /// input unless there is whitespace between the tokens of the content.
+    fn parse_grouping_as_str(&mut self) -> Option<SimpleExpr<'s>> {
+        let mut content = Cow::Borrowed("");
+        let mut first = None;
         let token = self.iter.next()?;
         let start = token.span().start;
         let end;
@@ -46,17 +116,34 @@ impl<'s> AsciiMath<'s> {
             return None;
         };
 
+        // nested groupings of the same kind are part of the content, i.e. `rgb(0, 0, 0)`
+        let mut depth = 0;
+
         loop {
             let token = self.iter.next()?;
 
-            if let TokenKind::Grouping(closing) = token.kind() {
-                if opening.matches(closing) {
+            if let TokenKind::Grouping(grouping) = token.kind() {
+                if grouping == opening {
+                    depth += 1;
+                } else if opening.matches(grouping) && depth > 0 {
+                    depth -= 1;
+                } else if opening.matches(grouping) {
                     end = token.span().end;
                     break;
                 }
             }
 
-            content.push_str(token.as_str());
+            let first = *first.get_or_insert(token);
+
+            // the content stays borrowed as long as the tokens follow each other directly
+            let joined = first
+                .joined(&token)
+                .filter(|joined| joined.len() == content.len() + token.as_str().len());
+
+            match (&mut content, joined) {
+                (Cow::Borrowed(_), Some(joined)) => content = Cow::Borrowed(joined),
+                (content, _) => content.to_mut().push_str(token.as_str()),
+            }
         }
 
         let var = Var {
@@ -67,7 +154,41 @@ impl<'s> AsciiMath<'s> {
         Some(SimpleExpr::Var(var))
     }
 
-    fn parse_simple_expr(&mut self) -> Option<SimpleExpr> {
+    pub(crate) fn parse_simple_expr(&mut self) -> Option<SimpleExpr<'s>> {
+        if !self.enter() {
+            return None;
+        }
+
+        let expr = self.parse_nested_expr();
+        self.depth -= 1;
+
+        expr
+    }
+
+    /// Enters one more level of nesting, unless the maximum depth is reached. Every level entered
+    /// has to be left by decrementing the depth.
+    fn enter(&mut self) -> bool {
+        if self.too_deep {
+            return false;
+        }
+
+        if self.depth == self.max_depth {
+            self.too_deep = true;
+            return false;
+        }
+
+        self.depth += 1;
+        true
+    }
+
+    /// Whether the next token is the given symbol.
+    fn is_next(&mut self, other: Other) -> bool {
+        self.iter
+            .peek()
+            .is_some_and(|token| token.kind() == TokenKind::Other(other))
+    }
+
+    fn parse_nested_expr(&mut self) -> Option<SimpleExpr<'s>> {
         let token = self.iter.peek()?;
 
         if let (true, Err(_), Err(_)) = (
@@ -131,106 +252,96 @@ impl<'s> AsciiMath<'s> {
         Var::parse(self).map(SimpleExpr::Var)
     }
 
-    fn parse_interm_expr(&mut self) -> Option<Expression> {
-        let s_expr = self.parse_simple_expr()?;
+    /// Parses a simple expression with its scripts, a subscript, a superscript or a subscript
+    /// followed by a superscript. Like asciimath.js, any further script is read as a symbol by the
+    /// next expression, i.e. `x^2_i` is `x^2` followed by `_` and `i`.
+    fn parse_interm_expr(&mut self) -> Option<Expression<'s>> {
+        let interm = self.parse_simple_expr()?;
 
-        let subscript = match self.iter.peek() {
-            Some(token) if matches!(token.kind(), TokenKind::Other(Other::Subscript)) => {
-                self.iter.next();
+        let subscript = match self.is_next(Other::Subscript) {
+            true => {
+                self.iter.next(); // skip subscript token
                 self.parse_simple_expr()
             }
-            _ => None,
+            false => None,
         };
 
-        let supscript = match self.iter.peek() {
-            Some(token) if matches!(token.kind(), TokenKind::Other(Other::Power)) => {
+        let supscript = match self.is_next(Other::Power) {
+            true => {
                 self.iter.next(); // skip supscript token
                 self.parse_simple_expr()
             }
-            _ => None,
+            false => None,
         };
 
-        let interm = Expression {
-            interm: s_expr,
+        Some(Expression {
+            interm,
             subscript,
             supscript,
-        };
-
-        Some(interm)
+        })
     }
 
-    fn parse_expr(&mut self) -> Option<Expression> {
-        let interm = self.parse_interm_expr()?;
-
-        if let Some(next_token) = self.iter.peek() {
-            if matches!(next_token.kind(), TokenKind::Other(Other::ForwardSlash)) {
-                // I/I case -> fraction
-                let numerator = interm;
-
-                self.iter.next(); // skip '/' token
+    /// Parses an expression with its fraction. Scripts bind tighter than fractions, i.e. `a/b^2`
+    /// is `a/(b^2)`. Like asciimath.js, a further `/` is read as a symbol by the next expression,
+    /// i.e. `a/b/c` is `a/b` followed by `/` and `c`.
+    fn parse_expr(&mut self) -> Option<Expression<'s>> {
+        let numerator = self.parse_interm_expr()?;
 
-                let denominator = self.parse_interm_expr().unwrap_or_else(|| {
-                    Expression::default_with_span(Span {
-                        start: numerator.span().end,
-                        end: numerator.span().end,
-                    })
-                });
-
-                let start = numerator.span().start;
-                let end = denominator.span().end;
-
-                // treat intermediate expressions as parenthesised expressions passed to frac:
-                // a_b/c_d == (a_b)/(c_d) == frac{a_b}{c_d}
-                let numerator = if numerator.is_scripted() {
-                    SimpleExpr::Interm(Box::new(numerator))
-                } else {
-                    numerator.interm
-                };
+        if !self.is_next(Other::ForwardSlash) {
+            return Some(numerator);
+        }
 
-                let denominator = if denominator.is_scripted() {
-                    SimpleExpr::Interm(Box::new(denominator))
-                } else {
-                    denominator.interm
-                };
+        // I/I case -> fraction
+        self.iter.next(); // skip '/' token
+
+        let denominator = self.parse_interm_expr().unwrap_or_else(|| {
+            Expression::default_with_span(Span {
+                start: numerator.span().end,
+                end: numerator.span().end,
+            })
+        });
+
+        let start = numerator.span().start;
+        let end = denominator.span().end;
+
+        // treat intermediate expressions as parenthesised expressions passed to frac:
+        // a_b/c_d == (a_b)/(c_d) == frac{a_b}{c_d}
+        let numerator = if numerator.is_scripted() {
+            SimpleExpr::Interm(Box::new(numerator))
+        } else {
+            numerator.interm
+        };
 
-                let binary = Binary {
-                    kind: BinaryKind::Fraction,
-                    expr_1: Box::new(numerator),
-                    expr_2: Box::new(denominator),
-                    span: Span { start, end },
-                };
+        let denominator = if denominator.is_scripted() {
+            SimpleExpr::Interm(Box::new(denominator))
+        } else {
+            denominator.interm
+        };
 
-                return Some(Expression {
-                    interm: SimpleExpr::Binary(binary),
-                    subscript: None,
-                    supscript: None,
-                });
-            }
-        }
+        let binary = Binary {
+            kind: BinaryKind::Fraction,
+            expr_1: Box::new(numerator),
+            expr_2: Box::new(denominator),
+            span: Span { start, end },
+        };
 
-        Some(interm)
+        Some(Expression {
+            interm: SimpleExpr::Binary(binary),
+            subscript: None,
+            supscript: None,
+        })
     }
 }
 
-impl Iterator for AsciiMath<'_> {
-    type Item = Expression;
+impl<'s> Iterator for AsciiMath<'s> {
+    type Item = Expression<'s>;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.parse_expr()
     }
 }
 
-impl From<AsciiMath<'_>> for MathMl {
-    fn from(value: AsciiMath<'_>) -> Self {
-        let mut mathml = MathMl::default();
-
-        for expr in value {
-            mathml.append_content(expr);
-        }
-
-        mathml
-    }
-}
-
+// the snapshot tests use the deprecated `assert_display_snapshot!`
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests;
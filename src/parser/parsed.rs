@@ -0,0 +1,150 @@
+use alloc::{sync::Arc, vec::Vec};
+
+#[cfg(feature = "mathml")]
+use crate::render::mathml::RenderOptions;
+#[cfg(feature = "std")]
+use crate::render::{self, art::ArtOptions, svg::SvgOptions};
+use crate::{Expression, LimitError, Limits};
+
+/// Configuration of a [`Parser`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Config {
+    /// Limits on the parsed input. By default the input is not limited, like with
+    /// [`parse`](crate::parse). The limit on the output length is only checked by
+    /// [`render_mathml_limited`](crate::render_mathml_limited), the output of [`ParsedMath`] is
+    /// bounded by the limits on the input.
+    pub limits: Limits,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            limits: Limits::UNLIMITED,
+        }
+    }
+}
+
+/// Reusable parser of AsciiMath input. Unlike [`parse`](crate::parse), the parser is configured
+/// once, and produces owned [`ParsedMath`], which doesn't borrow the input. The parser is `Send`
+/// and `Sync`, so it can be shared between threads:
+///
+/// ```
+/// use mathemascii::{Config, Limits, Parser};
+///
+/// let parser = Parser::new(Config {
+///     limits: Limits::default(),
+/// });
+///
+/// let math = parser.parse("sum_(i=0)^n i").unwrap();
+///
+/// # #[cfg(feature = "std")] {
+/// let typst = math.render_typst();
+/// let html = math.render_html();
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Parser {
+    config: Config,
+}
+
+impl Parser {
+    /// Creates a parser with the given configuration.
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// Returns the configuration of the parser.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Parses the whole input as a single math block.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`LimitError`] of the first limit of the configuration the input exceeds.
+    pub fn parse(&self, input: &str) -> Result<ParsedMath, LimitError> {
+        crate::parse_limited(input, &self.config.limits).map(ParsedMath::from)
+    }
+}
+
+/// Parsed AsciiMath produced by a [`Parser`]. It owns its expressions and shares them between
+/// its clones, so it's cheap to clone, e.g. to store it in caches. It can be rendered any number
+/// of times into any output format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedMath {
+    exprs: Arc<[Expression<'static>]>,
+}
+
+impl ParsedMath {
+    /// Returns the parsed expressions.
+    pub fn exprs(&self) -> &[Expression<'static>] {
+        &self.exprs
+    }
+
+    /// Renders the expressions into a string of mathml, see
+    /// [`render_mathml`](crate::render_mathml).
+    #[cfg(feature = "mathml")]
+    pub fn render_mathml(&self) -> String {
+        self.render_mathml_with(&RenderOptions::default())
+    }
+
+    /// Renders the expressions into a string of mathml that is safe to insert into HTML, see
+    /// [`render_mathml_safe`](crate::render_mathml_safe).
+    #[cfg(feature = "mathml")]
+    pub fn render_mathml_safe(&self) -> String {
+        self.render_mathml_with(&RenderOptions {
+            safe: true,
+            ..Default::default()
+        })
+    }
+
+    /// Renders the expressions into a string of mathml with the given options, see
+    /// [`render_mathml_with`](crate::render_mathml_with).
+    #[cfg(feature = "mathml")]
+    pub fn render_mathml_with(&self, options: &RenderOptions) -> String {
+        render::mathml::render(self.exprs.iter(), options)
+    }
+
+    /// Renders the expressions into Typst math markup, see
+    /// [`render_typst`](crate::render_typst).
+    #[cfg(feature = "std")]
+    pub fn render_typst(&self) -> String {
+        render::typst::Typst::render(self.exprs.iter())
+    }
+
+    /// Renders the expressions into an OMML `<m:oMath>` element, see
+    /// [`render_omml`](crate::render_omml).
+    #[cfg(feature = "std")]
+    pub fn render_omml(&self) -> String {
+        render::omml::Omml::render(self.exprs.iter())
+    }
+
+    /// Renders the expressions into HTML `<span>` elements, see
+    /// [`render_html`](crate::render_html).
+    #[cfg(feature = "std")]
+    pub fn render_html(&self) -> String {
+        render::html::Html::render(self.exprs.iter())
+    }
+
+    /// Renders the expressions into multi-line text art, see [`render_art`](crate::render_art).
+    #[cfg(feature = "std")]
+    pub fn render_art(&self, options: ArtOptions) -> String {
+        render::art::Art::render(self.exprs.iter(), options)
+    }
+
+    /// Renders the expressions into a standalone SVG image, see
+    /// [`render_svg`](crate::render_svg).
+    #[cfg(feature = "std")]
+    pub fn render_svg(&self, options: &SvgOptions) -> String {
+        render::svg::Layout::render(self.exprs.iter(), options)
+    }
+}
+
+impl From<Vec<Expression<'_>>> for ParsedMath {
+    fn from(exprs: Vec<Expression<'_>>) -> Self {
+        Self {
+            exprs: exprs.into_iter().map(Expression::into_owned).collect(),
+        }
+    }
+}
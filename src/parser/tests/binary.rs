@@ -3,3 +3,7 @@ use super::Snapshot;
 super::test_snap!(frac, "frac 1.44 2 frac(x)(3.14)");
 super::test_snap!(ubrace, "ubrace(1+2+3+4)_(\"4 terms\")");
 super::test_snap!(obrace, "obrace(1+2+3+4)_(\"4 terms\")");
+super::test_snap!(
+    color_nested_groupings,
+    "color(rgb(0, 0, 0))(x) color([red])(y)"
+);
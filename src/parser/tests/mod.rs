@@ -9,6 +9,7 @@ use super::{
 };
 
 mod binary;
+mod precedence;
 mod special_cases;
 mod sub_sup_scripts;
 mod unary;
@@ -49,7 +50,7 @@ impl std::fmt::Display for Snapshot<(&str, AsciiMath<'_>)> {
     }
 }
 
-impl std::fmt::Display for Snapshot<&Vec<Expression>> {
+impl std::fmt::Display for Snapshot<&Vec<Expression<'_>>> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for expr in self.0 {
             f.write_fmt(format_args!("{}", Snapshot(expr)))?;
@@ -60,7 +61,7 @@ impl std::fmt::Display for Snapshot<&Vec<Expression>> {
     }
 }
 
-impl std::fmt::Display for Snapshot<&Expression> {
+impl std::fmt::Display for Snapshot<&Expression<'_>> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("Expression {\n")?;
 
@@ -103,7 +104,7 @@ impl std::fmt::Display for Snapshot<&Expression> {
     }
 }
 
-impl std::fmt::Display for Snapshot<&SimpleExpr> {
+impl std::fmt::Display for Snapshot<&SimpleExpr<'_>> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.0 {
             SimpleExpr::Var(var) => f.write_fmt(format_args!("{}", Snapshot(var))),
@@ -137,33 +138,33 @@ impl std::fmt::Display for Snapshot<&SimpleExpr> {
     }
 }
 
-impl std::fmt::Display for Snapshot<&Var> {
+impl std::fmt::Display for Snapshot<&Var<'_>> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{:?}", self.0.kind))
     }
 }
 
-impl std::fmt::Display for Snapshot<VarKind> {
+impl std::fmt::Display for Snapshot<VarKind<'_>> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let snap = match &self.0 {
             VarKind::Function(f) => format!("{:?}", TokenKind::from(*f)),
-            VarKind::Number(num) => num.clone(),
+            VarKind::Number(num) => num.to_string(),
             VarKind::Greek(greek) => format!("{:?}", TokenKind::from(*greek)),
-            VarKind::Variable(var) => var.clone(),
+            VarKind::Variable(var) => var.to_string(),
             VarKind::Arrow(arr) => format!("{:?}", TokenKind::from(*arr)),
             VarKind::Relation(rel) => format!("{:?}", TokenKind::from(*rel)),
             VarKind::Logical(log) => format!("{:?}", TokenKind::from(*log)),
             VarKind::Operator(op) => format!("{:?}", TokenKind::from(*op)),
             VarKind::Other(ot) => format!("{:?}", TokenKind::from(*ot)),
             VarKind::Text(t) => format!("'{t}'"),
-            VarKind::UnknownOperator(op) => op.clone(),
+            VarKind::UnknownOperator(op) => op.to_string(),
         };
 
         f.write_str(&snap)
     }
 }
 
-impl std::fmt::Display for Snapshot<&Unary> {
+impl std::fmt::Display for Snapshot<&Unary<'_>> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{:?}", self.0.kind))?;
         f.write_str("(\n")?;
@@ -182,7 +183,7 @@ impl std::fmt::Display for Snapshot<&Unary> {
     }
 }
 
-impl std::fmt::Display for Snapshot<&Binary> {
+impl std::fmt::Display for Snapshot<&Binary<'_>> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{:?}", self.0.kind))?;
         f.write_str("(\n")?;
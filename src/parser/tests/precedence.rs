@@ -0,0 +1,12 @@
+use super::Snapshot;
+
+super::test_snap!(chained_fractions, "a/b/c");
+super::test_snap!(negated_fraction, "-a/b");
+super::test_snap!(scripted_denominator, "a/b^2");
+super::test_snap!(scripted_fractions, "a_1/b_2/c");
+super::test_snap!(scripted_numerator, "sum_(i=1)^n/2");
+super::test_snap!(sup_before_sub, "x^2_i");
+super::test_snap!(sub_before_sup, "x_i^2");
+super::test_snap!(repeated_subscripts, "x_1_2");
+super::test_snap!(repeated_supscripts, "x^2^3");
+super::test_snap!(repeated_scripts, "x^2_i^3");
@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use crate::lexer::{Token, TokenIterator};
+
+/// Where the tokens of the [`TokenStream`] come from.
+#[derive(Debug, Clone)]
+enum Source<'src> {
+    /// Tokens are lexed from the input as they are needed.
+    Lexer(TokenIterator<'src>),
+
+    /// Already lexed tokens are replayed, e.g. when a [`Document`] reparses only a part of its
+    /// source.
+    ///
+    /// [`Document`]: crate::Document
+    Replay(alloc::vec::IntoIter<Token<'src>>),
+}
+
+/// Peekable stream of tokens consumed by the parser. Keeps track of the number of consumed
+/// tokens, so that parsed expressions can be mapped back to the tokens they were parsed from.
+#[derive(Debug, Clone)]
+pub(crate) struct TokenStream<'src> {
+    source: Source<'src>,
+    peeked: Option<Option<Token<'src>>>,
+    consumed: usize,
+    exhausted: bool,
+
+    /// Number of tokens pulled from the source, and the maximum number of tokens to pull.
+    pulled: usize,
+    max_tokens: usize,
+
+    /// Whether the source has more tokens than the maximum.
+    truncated: bool,
+}
+
+impl<'src> TokenStream<'src> {
+    pub(crate) fn lex(lexer: TokenIterator<'src>) -> Self {
+        Self {
+            source: Source::Lexer(lexer),
+            peeked: None,
+            consumed: 0,
+            exhausted: false,
+            pulled: 0,
+            max_tokens: usize::MAX,
+            truncated: false,
+        }
+    }
+
+    pub(crate) fn replay(tokens: Vec<Token<'src>>) -> Self {
+        Self {
+            source: Source::Replay(tokens.into_iter()),
+            peeked: None,
+            consumed: 0,
+            exhausted: false,
+            pulled: 0,
+            max_tokens: usize::MAX,
+            truncated: false,
+        }
+    }
+
+    /// Limits the number of tokens in the stream. The stream ends after the maximum number of
+    /// tokens, and [`TokenStream::is_truncated`] tells whether there were more.
+    pub(crate) fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Whether the stream ended early, because the source has more tokens than the maximum.
+    pub(crate) fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Number of tokens returned by [`TokenStream::next`] so far. Peeked tokens are not counted.
+    pub(crate) fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Whether the end of the stream was reached, either by peeking or by consuming tokens.
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn pull(&mut self) -> Option<Token<'src>> {
+        let token = match &mut self.source {
+            Source::Lexer(lexer) => lexer.next(),
+            Source::Replay(tokens) => tokens.next(),
+        };
+
+        if token.is_some() && self.pulled == self.max_tokens {
+            self.truncated = true;
+            self.exhausted = true;
+            return None;
+        }
+
+        self.pulled += usize::from(token.is_some());
+        self.exhausted |= token.is_none();
+        token
+    }
+
+    pub(crate) fn peek(&mut self) -> Option<&Token<'src>> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.pull());
+        }
+
+        self.peeked.as_ref().and_then(Option::as_ref)
+    }
+}
+
+impl<'src> Iterator for TokenStream<'src> {
+    type Item = Token<'src>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.pull(),
+        };
+
+        if token.is_some() {
+            self.consumed += 1;
+        }
+
+        token
+    }
+}
@@ -1,9 +1,4 @@
-use alemat::attributes::MathVariant;
-use alemat::elements::grouping::{Row, Style};
-use alemat::elements::radicals::Radical;
-use alemat::elements::scripted::UnderOver;
-use alemat::elements::{IntoElements, Num, Operator};
-use alemat::{Attribute, Elements};
+use alloc::{borrow::Cow, boxed::Box};
 
 use crate::lexer::keywords::accents::Accent;
 use crate::lexer::keywords::font_commands::FontCommand;
@@ -15,35 +10,73 @@ use crate::{Var, VarKind};
 use super::{expr::SimpleExpr, AsciiMath};
 
 /// Kinds of unary operators in Ascii math.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum UnaryKind {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryKind {
     // accents:
+    /// Hat accent, i.e. `hat(a)`.
     Hat,
+
+    /// Line over the expression, i.e. `bar(a)`.
     Overline,
+
+    /// Line under the expression, i.e. `ul(a)`.
     Underline,
+
+    /// Vector arrow over the expression, i.e. `vec(a)`.
     Vector,
+
+    /// Tilde accent, i.e. `tilde(a)`.
     Tilde,
+
+    /// Dot accent, i.e. `dot(a)`.
     Dot,
+
+    /// Double dot accent, i.e. `ddot(a)`.
     DoubleDot,
+
+    /// Brace under the expression, i.e. `ubrace(a)`.
     Underbrace,
+
+    /// Brace over the expression, i.e. `obrace(a)`.
     Overbrace,
+
+    /// Crossed out expression, i.e. `cancel(a)`.
     Cancel,
 
     // others
+    /// Square root, i.e. `sqrt(a)`.
     SquareRoot,
 
     // groupings
+    /// Absolute value, i.e. `abs(a)`.
     Absolute,
+
+    /// Floor function, i.e. `floor(a)`.
     Floor,
+
+    /// Ceiling function, i.e. `ceil(a)`.
     Ceiling,
+
+    /// Norm, i.e. `norm(a)`.
     Norm,
 
     // font commands
+    /// Bold font, i.e. `bb(a)`.
     Bold,
+
+    /// Blackboard bold font, i.e. `bbb(a)`.
     BlackboardBold,
+
+    /// Calligraphic font, i.e. `cc(a)`.
     Calligraphic,
+
+    /// Typewriter font, i.e. `tt(a)`.
     Typewriter,
+
+    /// Gothic (fraktur) font, i.e. `fr(a)`.
     Gothic,
+
+    /// Sans-serif font, i.e. `sf(a)`.
     SansSerif,
 }
 
@@ -132,25 +165,45 @@ impl TryFrom<TokenKind> for UnaryKind {
 
 /// Unary operator in Ascii math.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Unary {
+pub struct Unary<'src> {
     /// Kind of unary operator.
     pub(crate) kind: UnaryKind,
 
     /// Expression that is being operated on.
-    pub(crate) expr: Box<SimpleExpr>,
+    pub(crate) expr: Box<SimpleExpr<'src>>,
 
     /// Span occupied by this unary operator expression.
     pub(crate) span: Span,
 }
 
-impl Unary {
+impl<'src> Unary<'src> {
     /// Returns the [`Span`] occupied by this unary operator expression.
     pub fn span(&self) -> Span {
         self.span
     }
 
+    /// Returns the kind of the unary operator.
+    pub fn kind(&self) -> UnaryKind {
+        self.kind
+    }
+
+    /// Returns the expression that is being operated on.
+    pub fn expr(&self) -> &SimpleExpr<'src> {
+        &self.expr
+    }
+
+    /// Returns the unary operator expression with its text copied, so that it no longer borrows
+    /// from the input.
+    pub fn into_owned(self) -> Unary<'static> {
+        Unary {
+            kind: self.kind,
+            expr: Box::new(self.expr.into_owned()),
+            span: self.span,
+        }
+    }
+
     /// Parses a unary operator expression.
-    pub(crate) fn parse(parser: &mut AsciiMath) -> Option<Self> {
+    pub(crate) fn parse(parser: &mut AsciiMath<'src>) -> Option<Self> {
         let token = parser.iter.peek()?;
         let unary_kind = UnaryKind::try_from(token.kind()).ok()?;
 
@@ -159,16 +212,14 @@ impl Unary {
 
         parser.iter.next(); // skip unary token
 
-        let expr = parser.parse_simple_expr().unwrap_or_else(|| {
-            // empty operator per default
-            SimpleExpr::Var(Var {
-                kind: VarKind::UnknownOperator(String::default()),
-                span: Span {
-                    start: span.end,
-                    end: span.end,
-                },
-            })
-        });
+        // empty operator per default
+        let expr = parser.parse_simple_expr().unwrap_or(SimpleExpr::Var(Var {
+            kind: VarKind::UnknownOperator(Cow::Borrowed("")),
+            span: Span {
+                start: span.end,
+                end: span.end,
+            },
+        }));
 
         let expr = Box::new(expr);
 
@@ -181,114 +232,3 @@ impl Unary {
         })
     }
 }
-
-impl IntoElements for Unary {
-    fn into_elements(self) -> Elements {
-        use alemat::children;
-
-        let mut inner = match *self.expr {
-            SimpleExpr::Grouping(grp) if grp.is_simple_grp() => grp.ungroup_into_elements(),
-            SimpleExpr::Grouping(grp) => grp.into_elements(),
-            _ => self.expr.into_elements(),
-        };
-
-        match self.kind {
-            UnaryKind::Hat => children![UnderOver::builder()
-                .expr(inner)
-                .over(Operator::hat())
-                .build()]
-            .into_elements(),
-            UnaryKind::Overline => children![UnderOver::builder()
-                .expr(inner)
-                .over(Operator::bar())
-                .build()]
-            .into_elements(),
-            UnaryKind::Underline => children![UnderOver::builder()
-                .expr(inner)
-                .under(Operator::bar())
-                .build()]
-            .into_elements(),
-            UnaryKind::Vector => children![UnderOver::builder()
-                .expr(inner)
-                .over(Operator::rarrow())
-                .build()]
-            .into_elements(),
-            UnaryKind::Tilde => children![UnderOver::builder()
-                .expr(inner)
-                .over(Operator::tilde())
-                .build()]
-            .into_elements(),
-            UnaryKind::Dot => children![UnderOver::builder()
-                .expr(inner)
-                .over(Operator::dot())
-                .build()]
-            .into_elements(),
-            UnaryKind::DoubleDot => children![UnderOver::builder()
-                .expr(inner)
-                .over(Operator::double_dot())
-                .build()]
-            .into_elements(),
-            UnaryKind::Underbrace => children![UnderOver::builder()
-                .expr(inner)
-                .under(Operator::ubrace())
-                .build()]
-            .into_elements(),
-            UnaryKind::Overbrace => children![UnderOver::builder()
-                .expr(inner)
-                .over(Operator::obrace())
-                .build()]
-            .into_elements(),
-            UnaryKind::Cancel => {
-                unimplemented!("<menclose> tag is non-standard and not recommended.")
-            }
-            UnaryKind::SquareRoot => children![Radical::builder()
-                .content(inner)
-                .index(Num::from(2))
-                .build()]
-            .into_elements(),
-            UnaryKind::Absolute => {
-                let mut el = Operator::vert_bar().into_elements();
-                el.append(&mut inner);
-                el.push(Operator::vert_bar().into());
-                el
-            }
-            UnaryKind::Floor => {
-                let mut el = Operator::lfloor().into_elements();
-                el.append(&mut inner);
-                el.push(Operator::rfloor().into());
-                el
-            }
-            UnaryKind::Ceiling => {
-                let mut el = Operator::lceiling().into_elements();
-                el.append(&mut inner);
-                el.push(Operator::rceiling().into());
-                el
-            }
-            UnaryKind::Norm => {
-                let mut el = Operator::norm().into_elements();
-                el.append(&mut inner);
-                el.push(Operator::norm().into());
-                Row::from(el).into_elements()
-            }
-            UnaryKind::Bold => Style::from(inner)
-                .with_attr([Attribute::MathVariant(MathVariant::Bold)])
-                .into_elements(),
-            UnaryKind::BlackboardBold => Style::from(inner)
-                .with_attr([Attribute::MathVariant(MathVariant::DoubleStruck)])
-                .into_elements(),
-            UnaryKind::Calligraphic => Style::from(inner)
-                .with_attr([Attribute::MathVariant(MathVariant::Script)])
-                .into_elements(),
-            UnaryKind::Typewriter => Style::from(inner)
-                // NOTE: not sure if monospace is the one AsciiMath uses here
-                .with_attr([Attribute::MathVariant(MathVariant::Monospace)])
-                .into_elements(),
-            UnaryKind::Gothic => Style::from(inner)
-                .with_attr([Attribute::MathVariant(MathVariant::Fraktur)])
-                .into_elements(),
-            UnaryKind::SansSerif => Style::from(inner)
-                .with_attr([Attribute::MathVariant(MathVariant::SansSerif)])
-                .into_elements(),
-        }
-    }
-}
@@ -1,29 +1,29 @@
-use alemat::elements::IntoElements;
+use alloc::borrow::Cow;
 
 use crate::lexer::{
     keywords::{
         arrows::Arrow, functions::Function, greeks::Greek, logicals::Logical, operators::Operator,
         others::Other, relations::Relation,
     },
-    Span, Token, TokenKind,
+    unescape, Span, Token, TokenKind,
 };
 
 use super::AsciiMath;
 
 /// Kinds of "variables" in Ascii math.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub enum VarKind {
+pub enum VarKind<'src> {
     /// Function identifier, i.e. `f` in `f(x)`.
     Function(Function),
 
     /// Number, i.e. `1` in `1 + 2`.
-    Number(String),
+    Number(Cow<'src, str>),
 
     /// Greek letter, i.e. `alpha` or `pi`.
     Greek(Greek),
 
     /// Variable identifier, almost any letter i.e. `x` in `f(x)`.
-    Variable(String),
+    Variable(Cow<'src, str>),
 
     /// Arrow, i.e. `->` or `=>`.
     Arrow(Arrow),
@@ -39,17 +39,17 @@ pub enum VarKind {
 
     /// Non-letter symbols not recognized by other keyword definitions fall back to operator, i.e.
     /// `;` or `.`.
-    UnknownOperator(String),
+    UnknownOperator(Cow<'src, str>),
 
     /// Other symbols, like comma (`,`) for example.
     Other(Other),
 
     /// Text, i.e. `"hello"` or `text(hello)`.
-    Text(String),
+    Text(Cow<'src, str>),
 }
 
-impl From<Token<'_>> for VarKind {
-    fn from(token: Token) -> Self {
+impl<'src> From<Token<'src>> for VarKind<'src> {
+    fn from(token: Token<'src>) -> Self {
         match token.kind() {
             TokenKind::Function(f) => Self::Function(f),
             TokenKind::Number => Self::Number(token.as_str().into()),
@@ -61,28 +61,48 @@ impl From<Token<'_>> for VarKind {
             TokenKind::Operator(op) => Self::Operator(op),
             TokenKind::UnknownOperator => Self::UnknownOperator(token.as_str().into()),
             TokenKind::Other(other) => match other {
-                Other::Text => Self::Text(token.as_str().into()),
+                Other::Text => Self::Text(unescape(token.as_str())),
                 _ => Self::Other(other),
             },
 
-            _ => return Self::UnknownOperator(token.as_str().into()),
+            _ => Self::UnknownOperator(token.as_str().into()),
+        }
+    }
+}
+
+impl VarKind<'_> {
+    /// Returns the variable kind with its text copied, so that it no longer borrows from the
+    /// input.
+    pub fn into_owned(self) -> VarKind<'static> {
+        match self {
+            VarKind::Function(f) => VarKind::Function(f),
+            VarKind::Number(n) => VarKind::Number(Cow::Owned(n.into_owned())),
+            VarKind::Greek(g) => VarKind::Greek(g),
+            VarKind::Variable(v) => VarKind::Variable(Cow::Owned(v.into_owned())),
+            VarKind::Arrow(a) => VarKind::Arrow(a),
+            VarKind::Relation(r) => VarKind::Relation(r),
+            VarKind::Logical(l) => VarKind::Logical(l),
+            VarKind::Operator(op) => VarKind::Operator(op),
+            VarKind::UnknownOperator(op) => VarKind::UnknownOperator(Cow::Owned(op.into_owned())),
+            VarKind::Other(other) => VarKind::Other(other),
+            VarKind::Text(t) => VarKind::Text(Cow::Owned(t.into_owned())),
         }
     }
 }
 
 /// Variable in Ascii math, meaning a symbol that stands on it's own.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Var {
+pub struct Var<'src> {
     /// Kind of variable.
-    pub kind: VarKind,
+    pub kind: VarKind<'src>,
 
     /// Span occupied by this variable.
     pub span: Span,
 }
 
-impl Var {
+impl<'src> Var<'src> {
     /// Parses a variable from Ascii math input.
-    pub(crate) fn parse(parser: &mut AsciiMath) -> Option<Self> {
+    pub(crate) fn parse(parser: &mut AsciiMath<'src>) -> Option<Self> {
         let token = parser.iter.next()?;
 
         let var_kind = VarKind::from(token);
@@ -93,6 +113,14 @@ impl Var {
         })
     }
 
+    /// Returns the variable with its text copied, so that it no longer borrows from the input.
+    pub fn into_owned(self) -> Var<'static> {
+        Var {
+            kind: self.kind.into_owned(),
+            span: self.span,
+        }
+    }
+
     /// Returns the [`Span`] occupied by this variable.
     pub fn span(&self) -> Span {
         self.span
@@ -106,25 +134,3 @@ impl Var {
         }
     }
 }
-
-impl IntoElements for Var {
-    fn into_elements(self) -> alemat::Elements {
-        use alemat::elements::*;
-
-        match self.kind {
-            VarKind::Function(i) => Ident::builder().ident(i.as_ref()).build().into_elements(),
-            VarKind::Greek(greek) => Ident::from(greek).into_elements(),
-            VarKind::Variable(i) => Ident::builder().ident(i).build().into_elements(),
-
-            VarKind::Relation(rel) => Operator::from(rel).into_elements(),
-            VarKind::Logical(log) => Operator::from(log).into_elements(),
-            VarKind::Operator(op) => Operator::from(op).into_elements(),
-            VarKind::Arrow(arrow) => Operator::from(arrow).into_elements(),
-
-            VarKind::Other(ot) => [ot].into_elements(),
-            VarKind::Text(txt) => Text::from(txt).into_elements(),
-            VarKind::Number(num) => Num::from(num.as_str()).into_elements(),
-            VarKind::UnknownOperator(op) => Operator::from(op).into_elements(),
-        }
-    }
-}
@@ -0,0 +1,708 @@
+//! Rendering of the abstract syntax tree into two-dimensional text art, similar to the pretty
+//! printing of SymPy. Fractions get horizontal bars, scripts are raised and lowered, limits of
+//! big operators are stacked and groupings grow with their content.
+//!
+//! The layout is done with [`Block`]s: rectangles of text with a baseline, which are composed
+//! horizontally (aligned on their baselines) and vertically (centered).
+
+use crate::{
+    lexer::keywords::{operators::Operator, others::Other},
+    parser::Matrix,
+    Binary, BinaryKind, Expression, GroupingExpr, SimpleExpr, Unary, UnaryKind, Var, VarKind,
+};
+
+use super::{Class, Variant};
+
+/// Options for rendering text art with [`render_art`](crate::render_art).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtOptions {
+    /// Maximal width of the output in characters. Wider output is broken between the top-level
+    /// expressions, so single expressions wider than this are still kept on one line.
+    pub width: usize,
+
+    /// Use only ASCII characters, for terminals and logs without Unicode support. Symbols without
+    /// ASCII representation are written as their AsciiMath keyword, e.g. `alpha` or `oo`.
+    pub ascii: bool,
+}
+
+impl Default for ArtOptions {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            ascii: false,
+        }
+    }
+}
+
+/// Rectangle of text with a baseline. All lines have the same width in characters.
+#[derive(Debug, Clone, Default)]
+struct Block {
+    lines: Vec<String>,
+    width: usize,
+
+    /// Index of the line that is aligned with the surrounding content.
+    baseline: usize,
+}
+
+impl Block {
+    fn text(text: impl Into<String>) -> Self {
+        let text = text.into();
+
+        Self {
+            width: text.chars().count(),
+            lines: vec![text],
+            baseline: 0,
+        }
+    }
+
+    /// Creates a block from the given lines, padding them to the same width.
+    fn from_lines(lines: Vec<String>, baseline: usize) -> Self {
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+        let lines = lines
+            .into_iter()
+            .map(|line| {
+                let pad = width - line.chars().count();
+                line + &" ".repeat(pad)
+            })
+            .collect();
+
+        Self {
+            lines,
+            width,
+            baseline,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Composes the blocks horizontally, aligning their baselines.
+    fn hcat(blocks: impl IntoIterator<Item = Block>) -> Self {
+        let blocks: Vec<_> = blocks.into_iter().filter(|b| !b.is_empty()).collect();
+
+        let top = blocks.iter().map(|b| b.baseline).max().unwrap_or(0);
+        let height = blocks
+            .iter()
+            .map(|b| top - b.baseline + b.height())
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = vec![String::new(); height];
+
+        for block in &blocks {
+            let offset = top - block.baseline;
+
+            for (i, line) in lines.iter_mut().enumerate() {
+                match i.checked_sub(offset).and_then(|i| block.lines.get(i)) {
+                    Some(content) => line.push_str(content),
+                    None => line.push_str(&" ".repeat(block.width)),
+                }
+            }
+        }
+
+        Self {
+            lines,
+            width: blocks.iter().map(|b| b.width).sum(),
+            baseline: top,
+        }
+    }
+
+    /// Stacks the blocks vertically, centering them horizontally. The baseline of the block at
+    /// index `base` becomes the baseline of the result.
+    fn stack(blocks: Vec<Block>, base: usize) -> Self {
+        let width = blocks.iter().map(|b| b.width).max().unwrap_or(0);
+        let baseline =
+            blocks[..base].iter().map(Block::height).sum::<usize>() + blocks[base].baseline;
+
+        let lines = blocks
+            .into_iter()
+            .flat_map(|block| block.centered(width).lines)
+            .collect();
+
+        Self {
+            lines,
+            width,
+            baseline,
+        }
+    }
+
+    /// Pads the block with spaces on both sides to the given width.
+    fn centered(self, width: usize) -> Self {
+        let left = (width.saturating_sub(self.width)) / 2;
+        let right = width.saturating_sub(self.width + left);
+
+        let lines = self
+            .lines
+            .into_iter()
+            .map(|line| format!("{}{line}{}", " ".repeat(left), " ".repeat(right)))
+            .collect();
+
+        Self {
+            lines,
+            width: width.max(self.width),
+            baseline: self.baseline,
+        }
+    }
+
+    /// Block of the given height containing the same text in every line.
+    fn column(text: &str, height: usize, baseline: usize) -> Self {
+        Self::from_lines(vec![text.to_string(); height], baseline)
+    }
+
+    fn into_string(self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Renders [`Expression`]s into text art.
+#[derive(Debug, Default)]
+pub(crate) struct Art {
+    options: ArtOptions,
+
+    /// Font variant selected by the enclosing font command, if any.
+    variant: Option<Variant>,
+}
+
+impl Art {
+    /// Renders the given expressions, breaking the output into multiple chunks separated by
+    /// empty lines if it is wider than configured.
+    pub(crate) fn render<'e, I>(exprs: I, options: ArtOptions) -> String
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        let mut art = Art {
+            options,
+            variant: None,
+        };
+
+        let mut chunks: Vec<Vec<Block>> = vec![];
+        let mut chunk_width = 0;
+
+        for (gap, block) in art.spaced(exprs) {
+            match chunks.last_mut() {
+                Some(chunk) if chunk_width + gap + block.width <= options.width => {
+                    chunk_width += gap + block.width;
+                    chunk.push(Block::text(" ".repeat(gap)));
+                    chunk.push(block);
+                }
+                _ => {
+                    chunk_width = block.width;
+                    chunks.push(vec![block]);
+                }
+            }
+        }
+
+        chunks
+            .into_iter()
+            .map(|chunk| Block::hcat(chunk).into_string())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Renders the expressions together with the number of spaces preceding each of them.
+    fn spaced<'e, I>(&mut self, exprs: I) -> Vec<(usize, Block)>
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        let mut prev = None;
+        let mut blocks = vec![];
+
+        for expr in exprs {
+            let block = self.expr(expr);
+
+            if block.is_empty() {
+                continue;
+            }
+
+            let class = Class::of(expr).after(prev);
+            let gap = prev.map(|prev| gap(prev, class)).unwrap_or(0);
+            blocks.push((gap, block));
+            prev = Some(class);
+        }
+
+        blocks
+    }
+
+    fn row<'e, I>(&mut self, exprs: I) -> Block
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        let blocks = self.spaced(exprs).into_iter().flat_map(|(gap, block)| {
+            let gap = (gap > 0).then(|| Block::text(" ".repeat(gap)));
+            gap.into_iter().chain(Some(block))
+        });
+
+        Block::hcat(blocks)
+    }
+
+    /// Picks the ASCII fallback of the symbol, if only ASCII output is allowed.
+    fn symbol(&self, unicode: &str, keyword: &str) -> String {
+        if self.options.ascii && !unicode.is_ascii() {
+            keyword.to_string()
+        } else {
+            unicode.to_string()
+        }
+    }
+
+    /// Picks the Unicode or the ASCII variant of a drawing character.
+    fn draw(&self, unicode: &'static str, ascii: &'static str) -> &'static str {
+        if self.options.ascii {
+            ascii
+        } else {
+            unicode
+        }
+    }
+
+    /// Text of identifiers, with the font variant applied.
+    fn ident(&self, text: &str) -> Block {
+        match self.variant {
+            Some(variant) if !self.options.ascii => {
+                Block::text(text.chars().map(|c| variant.apply(c)).collect::<String>())
+            }
+            _ => Block::text(text),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expression) -> Block {
+        if let Some(matrix) = expr.as_matrix() {
+            return self.matrix(&matrix);
+        }
+
+        let base = self.simple(&expr.interm);
+
+        if !expr.is_scripted() {
+            return base;
+        }
+
+        let sub = expr.subscript.as_ref().map(|sub| self.script(sub));
+        let sup = expr.supscript.as_ref().map(|sup| self.script(sup));
+
+        if expr.interm.is_underover() {
+            let base_idx = usize::from(sup.is_some());
+            let blocks = sup.into_iter().chain(Some(base)).chain(sub).collect();
+            return Block::stack(blocks, base_idx);
+        }
+
+        // the superscript is placed above the baseline, the subscript below it
+        let mut lines = vec![];
+        let baseline = sup.as_ref().map(Block::height).unwrap_or(0);
+
+        lines.extend(sup.into_iter().flat_map(|sup| sup.lines));
+        lines.push(String::new());
+        lines.extend(sub.into_iter().flat_map(|sub| sub.lines));
+
+        Block::hcat([base, Block::from_lines(lines, baseline)])
+    }
+
+    fn simple(&mut self, expr: &SimpleExpr) -> Block {
+        match expr {
+            SimpleExpr::Var(var) => self.var(var),
+            SimpleExpr::Grouping(grp) => self.grouping(grp),
+            SimpleExpr::Unary(unary) => self.unary(unary),
+            SimpleExpr::Binary(binary) => self.binary(binary),
+            SimpleExpr::Interm(inner) => self.expr(inner),
+        }
+    }
+
+    /// Renders an argument of unary or binary operators. Simple groupings are rendered without
+    /// the grouping symbols.
+    fn arg(&mut self, expr: &SimpleExpr) -> Block {
+        match expr {
+            SimpleExpr::Grouping(grp) if grp.is_simple_grp() => self.row(&grp.expr),
+            _ => self.simple(expr),
+        }
+    }
+
+    /// Renders a subscript or superscript. Groupings are rendered without the grouping symbols.
+    fn script(&mut self, expr: &SimpleExpr) -> Block {
+        match expr {
+            SimpleExpr::Grouping(grp) => self.row(&grp.expr),
+            _ => self.simple(expr),
+        }
+    }
+
+    fn var(&mut self, var: &Var) -> Block {
+        match &var.kind {
+            VarKind::Function(func) => self.ident(func.as_ref()),
+            VarKind::Number(num) => self.ident(num),
+            VarKind::Greek(greek) => self.ident(&self.symbol(greek.as_unicode(), greek.as_ref())),
+            VarKind::Variable(var) => self.ident(var),
+            VarKind::Arrow(arrow) => Block::text(self.symbol(arrow.as_unicode(), arrow.as_ref())),
+            VarKind::Relation(rel) => Block::text(self.symbol(rel.as_unicode(), rel.as_ref())),
+            VarKind::Logical(log) => Block::text(self.symbol(log.as_unicode(), log.as_ref())),
+            VarKind::Operator(Operator::Sum) => Block::from_lines(
+                vec![
+                    "___".into(),
+                    self.draw("╲  ", "\\  ").into(),
+                    self.draw("╱__", "/__").into(),
+                ],
+                1,
+            ),
+            VarKind::Operator(Operator::Prod) => Block::from_lines(
+                vec![
+                    self.draw("┬─┬", "___").into(),
+                    self.draw("│ │", "| |").into(),
+                    self.draw("│ │", "| |").into(),
+                ],
+                1,
+            ),
+            VarKind::Operator(op) => Block::text(self.symbol(op.as_unicode(), op.as_ref())),
+            VarKind::Other(Other::Integral) => Block::from_lines(
+                vec![
+                    self.draw("⌠", " /").into(),
+                    self.draw("⎮", " |").into(),
+                    self.draw("⌡", "/ ").into(),
+                ],
+                1,
+            ),
+            VarKind::Other(Other::OIntegral) => Block::from_lines(
+                vec![
+                    self.draw("⌠", " /").into(),
+                    self.draw("∮", " o").into(),
+                    self.draw("⌡", "/ ").into(),
+                ],
+                1,
+            ),
+            VarKind::Other(other) => self.ident(&self.symbol(other.as_unicode(), other.as_ref())),
+            VarKind::Text(text) => self.ident(text),
+            VarKind::UnknownOperator(op) if op.is_empty() => Block::default(),
+            VarKind::UnknownOperator(op) => Block::text(op.as_ref()),
+        }
+    }
+
+    fn grouping(&mut self, grp: &GroupingExpr) -> Block {
+        let content = self.row(&grp.expr);
+
+        let left = grp.left_grouping.as_unicode(true);
+        let right = grp.right_grouping.as_unicode(false);
+
+        self.delimited(left, content, right)
+    }
+
+    /// Surrounds the content with the delimiters, stretched to the height of the content.
+    fn delimited(&self, left: &str, content: Block, right: &str) -> Block {
+        let height = content.height().max(1);
+        let baseline = content.baseline;
+
+        Block::hcat([
+            self.delimiter(left, height, baseline),
+            content,
+            self.delimiter(right, height, baseline),
+        ])
+    }
+
+    fn delimiter(&self, delimiter: &str, height: usize, baseline: usize) -> Block {
+        if delimiter.is_empty() {
+            return Block::default();
+        }
+
+        if height == 1 {
+            let ascii = match delimiter {
+                "⟨" => "<",
+                "⟩" => ">",
+                "‖" => "||",
+                "⌊" => "|_",
+                "⌋" => "_|",
+                "⌈" => "|~",
+                "⌉" => "~|",
+                other => other,
+            };
+
+            return Block::text(self.symbol(delimiter, ascii));
+        }
+
+        // top, extension, bottom and middle piece of the delimiter
+        let (top, ext, bottom, middle) = match (delimiter, self.options.ascii) {
+            ("(", false) => ("⎛", "⎜", "⎝", None),
+            (")", false) => ("⎞", "⎟", "⎠", None),
+            ("[", false) => ("⎡", "⎢", "⎣", None),
+            ("]", false) => ("⎤", "⎥", "⎦", None),
+            ("{", false) => ("⎧", "⎪", "⎩", Some("⎨")),
+            ("}", false) => ("⎫", "⎪", "⎭", Some("⎬")),
+            ("|", false) => ("│", "│", "│", None),
+            ("⌊", false) => ("⎢", "⎢", "⎣", None),
+            ("⌋", false) => ("⎥", "⎥", "⎦", None),
+            ("⌈", false) => ("⎡", "⎢", "⎢", None),
+            ("⌉", false) => ("⎤", "⎥", "⎥", None),
+            ("⟨", false) => ("╱", "╱", "╲", Some("╲")),
+            ("⟩", false) => ("╲", "╲", "╱", Some("╱")),
+            ("(", true) => ("/", "|", "\\", None),
+            (")", true) => ("\\", "|", "/", None),
+            ("{", true) => ("/", "|", "\\", Some("<")),
+            ("}", true) => ("\\", "|", "/", Some(">")),
+            ("⌊", true) => ("|", "|", "|_", None),
+            ("⌋", true) => (" |", " |", "_|", None),
+            ("⌈", true) => ("|~", "|", "|", None),
+            ("⌉", true) => ("~|", " |", " |", None),
+            ("⟨", true) => ("/", "/", "\\", Some("\\")),
+            ("⟩", true) => ("\\", "\\", "/", Some("/")),
+            ("‖", true) => ("||", "||", "||", None),
+            (other, _) => (other, other, other, None),
+        };
+
+        let lines = (0..height)
+            .map(|i| {
+                let is_angle = matches!(delimiter, "⟨" | "⟩");
+
+                let piece = match middle {
+                    // angles change direction in the middle
+                    Some(middle) if is_angle && i >= height / 2 => middle,
+                    _ if is_angle => top,
+                    Some(middle) if height > 2 && i == baseline.clamp(1, height - 2) => middle,
+                    _ if i == 0 => top,
+                    _ if i == height - 1 => bottom,
+                    _ => ext,
+                };
+
+                piece.to_string()
+            })
+            .collect();
+
+        Block::from_lines(lines, baseline)
+    }
+
+    fn unary(&mut self, unary: &Unary) -> Block {
+        if let Some(variant) = Variant::from_unary(unary.kind) {
+            let outer = self.variant.replace(variant);
+            let block = self.arg(&unary.expr);
+            self.variant = outer;
+            return block;
+        }
+
+        let arg = self.arg(&unary.expr);
+
+        match unary.kind {
+            UnaryKind::Hat => self.accent("^", arg),
+            UnaryKind::Vector => self.accent(self.draw("→", "->"), arg),
+            UnaryKind::Tilde => self.accent("~", arg),
+            UnaryKind::Dot => self.accent(self.draw("˙", "."), arg),
+            UnaryKind::DoubleDot => self.accent(self.draw("¨", ".."), arg),
+            UnaryKind::Overline => {
+                let line = Block::text("_".repeat(arg.width));
+                Block::stack(vec![line, arg], 1)
+            }
+            UnaryKind::Underline => {
+                let line = Block::text(self.draw("‾", "-").repeat(arg.width));
+                Block::stack(vec![arg, line], 0)
+            }
+            UnaryKind::Underbrace => {
+                let brace = self.brace(arg.width, false);
+                Block::stack(vec![arg, brace], 0)
+            }
+            UnaryKind::Overbrace => {
+                let brace = self.brace(arg.width, true);
+                Block::stack(vec![brace, arg], 1)
+            }
+            UnaryKind::Cancel => self.cancel(arg),
+            UnaryKind::SquareRoot => self.radical(arg),
+            UnaryKind::Absolute => self.delimited("|", arg, "|"),
+            UnaryKind::Floor => self.delimited("⌊", arg, "⌋"),
+            UnaryKind::Ceiling => self.delimited("⌈", arg, "⌉"),
+            UnaryKind::Norm => self.delimited("‖", arg, "‖"),
+
+            // font commands are handled above
+            _ => arg,
+        }
+    }
+
+    /// Places the accent above the block.
+    fn accent(&self, accent: &str, block: Block) -> Block {
+        Block::stack(vec![Block::text(accent), block], 1)
+    }
+
+    /// Horizontal brace of the given width, with its tip pointing away from the braced content.
+    fn brace(&self, width: usize, is_over: bool) -> Block {
+        let width = width.max(3);
+        let tip = width / 2;
+
+        let (left, line, tip_char, right) = match (is_over, self.options.ascii) {
+            (true, false) => ('╭', '─', '┴', '╮'),
+            (false, false) => ('╰', '─', '┬', '╯'),
+            (true, true) => ('/', '-', '^', '\\'),
+            (false, true) => ('\\', '-', 'v', '/'),
+        };
+
+        let brace = (0..width)
+            .map(|i| match i {
+                0 => left,
+                _ if i == width - 1 => right,
+                _ if i == tip => tip_char,
+                _ => line,
+            })
+            .collect::<String>();
+
+        Block::text(brace)
+    }
+
+    /// Strikes the block through with a diagonal line, drawn at its corners.
+    fn cancel(&self, block: Block) -> Block {
+        let stroke = self.draw("╱", "/");
+        let width = block.width;
+
+        let mut lines = vec![format!("{}{stroke}", " ".repeat(width + 1))];
+        lines.extend(block.lines.iter().map(|line| format!(" {line} ")));
+        lines.push(format!("{stroke}{}", " ".repeat(width + 1)));
+
+        Block::from_lines(lines, block.baseline + 1)
+    }
+
+    /// Draws the radical sign in front of the block and a line above it.
+    fn radical(&self, block: Block) -> Block {
+        let height = block.height().max(1);
+
+        let signs: Vec<String> = if height == 1 && !self.options.ascii {
+            vec!["√".into()]
+        } else {
+            let (up, down) = (self.draw("╱", "/"), self.draw("╲", "\\"));
+
+            (0..height)
+                .map(|i| {
+                    let indent = " ".repeat(height - 1 - i);
+
+                    if i == height - 1 {
+                        format!("{down}{up}{}", " ".repeat(i))
+                    } else {
+                        format!(" {indent}{up}{}", " ".repeat(i))
+                    }
+                })
+                .collect()
+        };
+
+        let sign_width = signs[0].chars().count();
+        let mut lines = vec![format!(
+            "{}{}",
+            " ".repeat(sign_width),
+            "_".repeat(block.width)
+        )];
+
+        for (i, sign) in signs.into_iter().enumerate() {
+            let content = block.lines.get(i).map(String::as_str).unwrap_or_default();
+            lines.push(format!("{sign}{content}"));
+        }
+
+        Block::from_lines(lines, block.baseline + 1)
+    }
+
+    fn binary(&mut self, binary: &Binary) -> Block {
+        let first = self.arg(&binary.expr_1);
+        let second = self.arg(&binary.expr_2);
+
+        match binary.kind {
+            BinaryKind::Fraction => {
+                let width = first.width.max(second.width) + 2;
+                let bar = Block::text(self.draw("─", "-").repeat(width));
+                let baseline = first.height();
+
+                let mut frac = Block::stack(vec![first, bar, second], 1);
+                frac.baseline = baseline;
+                frac
+            }
+            BinaryKind::Root => {
+                let root = self.radical(second);
+
+                // the index is placed above the lower end of the radical sign
+                let mut index = first;
+                let target_row = root.height().saturating_sub(2);
+                index.baseline = (index.height() + root.baseline).saturating_sub(target_row + 1);
+
+                Block::hcat([index, root])
+            }
+            BinaryKind::Overset => Block::stack(vec![first, second], 1),
+            BinaryKind::Underset => Block::stack(vec![second, first], 0),
+            BinaryKind::Color => second,
+        }
+    }
+
+    fn matrix(&mut self, matrix: &Matrix<'_>) -> Block {
+        let columns = matrix.num_of_columns();
+
+        let cells: Vec<Vec<Block>> = matrix
+            .rows
+            .iter()
+            .map(|row| {
+                (0..columns)
+                    .map(|i| match row.get(i) {
+                        Some(cell) => self.row(cell.iter().copied()),
+                        None => Block::default(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let widths: Vec<usize> = (0..columns)
+            .map(|i| cells.iter().map(|row| row[i].width).max().unwrap_or(0))
+            .collect();
+
+        let is_tall = cells.iter().flatten().any(|cell| cell.height() > 1);
+
+        let mut rows = vec![];
+
+        for (row_idx, row) in cells.into_iter().enumerate() {
+            let ascent = row.iter().map(|c| c.baseline).max().unwrap_or(0);
+            let height = row
+                .iter()
+                .map(|c| ascent - c.baseline + c.height())
+                .max()
+                .unwrap_or(1)
+                .max(1);
+
+            let mut blocks = vec![];
+
+            for (i, cell) in row.into_iter().enumerate() {
+                if i > 0 {
+                    let has_line = matrix.column_lines.get(i - 1).copied().unwrap_or(false);
+                    let separator = if has_line {
+                        self.draw(" │ ", " | ")
+                    } else {
+                        "  "
+                    };
+
+                    blocks.push(Block::column(separator, height, ascent));
+                }
+
+                let mut cell = cell.centered(widths[i]);
+
+                // empty cells still have to occupy a line
+                if cell.is_empty() {
+                    cell = Block::text(" ".repeat(widths[i]));
+                }
+
+                blocks.push(cell);
+            }
+
+            if is_tall && row_idx > 0 {
+                rows.push(Block::text(""));
+            }
+
+            rows.push(Block::hcat(blocks));
+        }
+
+        let mut content = Block::stack(rows, 0);
+        content.baseline = content.height().saturating_sub(1) / 2;
+
+        let left = matrix.left_grouping.as_unicode(true);
+        let right = matrix.right_grouping.as_unicode(false);
+
+        self.delimited(left, content, right)
+    }
+}
+
+/// Number of spaces between expressions of the given classes.
+fn gap(prev: Class, next: Class) -> usize {
+    match (prev, next) {
+        (Class::Rel | Class::Bin | Class::Punct, _) | (_, Class::Rel | Class::Bin) => 1,
+        (Class::Func, Class::Group) => 0,
+        (Class::Func | Class::Large, _) => 1,
+        _ => 0,
+    }
+}
@@ -0,0 +1,132 @@
+//! Syntax highlighting of AsciiMath source, based on the tokens seen by the parser.
+
+use crate::{
+    keywords::{Category, Other, Variant},
+    tokens::{self, TokenKind},
+};
+
+use super::escape_into;
+
+/// Highlighting class of a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Number,
+    Variable,
+    Text,
+    /// Greek letters and other symbols standing on their own, like `oo`.
+    Symbol,
+    Function,
+    Operator,
+    /// Relations, arrows and logical connectives.
+    Relation,
+    Bracket,
+    /// Keywords that take arguments, like `frac`, `hat` or `bb`.
+    Command,
+}
+
+impl Style {
+    /// Returns the style of the token kind, or `None` for whitespace.
+    fn of(kind: TokenKind) -> Option<Self> {
+        let variant = match kind {
+            TokenKind::Whitespace => return None,
+            TokenKind::Number => return Some(Style::Number),
+            TokenKind::Variable => return Some(Style::Variable),
+            TokenKind::Text => return Some(Style::Text),
+            TokenKind::UnknownOperator => return Some(Style::Operator),
+            TokenKind::Keyword(variant) => variant,
+        };
+
+        let style = match variant {
+            Variant::Other(
+                Other::Fraction | Other::SquareRoot | Other::Root | Other::Text | Other::Quote,
+            ) => Style::Command,
+            Variant::Other(
+                Other::Comma | Other::ForwardSlash | Other::Power | Other::Subscript,
+            ) => Style::Operator,
+            Variant::Other(Other::PlusMinus) => Style::Operator,
+            _ => match variant.category() {
+                Category::Greek | Category::Other => Style::Symbol,
+                Category::Function => Style::Function,
+                Category::Operator => Style::Operator,
+                Category::Relation | Category::Arrow | Category::Logical => Style::Relation,
+                Category::Grouping => Style::Bracket,
+                Category::Accent | Category::FontCommand => Style::Command,
+            },
+        };
+
+        Some(style)
+    }
+
+    /// Returns the class of the `<span>` element in HTML output.
+    fn class(self) -> &'static str {
+        match self {
+            Style::Number => "am-number",
+            Style::Variable => "am-variable",
+            Style::Text => "am-text",
+            Style::Symbol => "am-symbol",
+            Style::Function => "am-function",
+            Style::Operator => "am-operator",
+            Style::Relation => "am-relation",
+            Style::Bracket => "am-bracket",
+            Style::Command => "am-command",
+        }
+    }
+
+    /// Returns the parameters of the ANSI escape sequence selecting the color of the style, or
+    /// `None` for the default color.
+    fn ansi(self) -> Option<&'static str> {
+        let color = match self {
+            Style::Number => "36",
+            Style::Variable => return None,
+            Style::Text => "32",
+            Style::Symbol => "35",
+            Style::Function => "34",
+            Style::Operator => "33",
+            Style::Relation => "1;33",
+            Style::Bracket => "90",
+            Style::Command => "1;34",
+        };
+
+        Some(color)
+    }
+}
+
+/// Highlights the source with ANSI escape sequences, for output in terminals.
+pub(crate) fn ansi(input: &str) -> String {
+    let mut out = String::with_capacity(input.len() * 2);
+
+    for token in tokens::tokenize(input) {
+        match Style::of(token.kind).and_then(Style::ansi) {
+            Some(color) => {
+                out.push_str("\x1b[");
+                out.push_str(color);
+                out.push('m');
+                out.push_str(token.text);
+                out.push_str("\x1b[0m");
+            }
+            None => out.push_str(token.text),
+        }
+    }
+
+    out
+}
+
+/// Highlights the source with `<span class>` elements, styled with the highlighting stylesheet.
+pub(crate) fn html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len() * 4);
+
+    for token in tokens::tokenize(input) {
+        match Style::of(token.kind) {
+            Some(style) => {
+                out.push_str("<span class=\"");
+                out.push_str(style.class());
+                out.push_str("\">");
+                escape_into(&mut out, token.text);
+                out.push_str("</span>");
+            }
+            None => escape_into(&mut out, token.text),
+        }
+    }
+
+    out
+}
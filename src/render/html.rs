@@ -0,0 +1,331 @@
+//! Rendering of the abstract syntax tree into HTML `<span>` elements, styled with the stylesheet
+//! found in `html.css`. Useful for environments that don't support MathMl.
+
+use crate::{
+    lexer::keywords::{
+        functions::Function, groupings::Grouping, operators::Operator, others::Other,
+    },
+    parser::Matrix,
+    Binary, BinaryKind, Expression, GroupingExpr, SimpleExpr, Unary, UnaryKind, Var, VarKind,
+};
+
+use super::{escape_into, is_css_color, keep_spaces, Variant};
+
+/// Renders [`Expression`]s into HTML.
+#[derive(Debug, Default)]
+pub(crate) struct Html {
+    out: String,
+
+    /// Font variant selected by the enclosing font command, if any.
+    variant: Option<Variant>,
+}
+
+impl Html {
+    /// Renders all the given expressions into a single `<span class="mathemascii">` element.
+    pub(crate) fn render<'e, I>(exprs: I) -> String
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        let mut html = Html::default();
+        html.span("mathemascii", |this| this.exprs(exprs));
+        html.out
+    }
+
+    /// Writes a `<span>` element with the given class and the content written by `content`.
+    fn span(&mut self, class: &str, content: impl FnOnce(&mut Self)) {
+        self.out.push_str("<span class=\"");
+        self.out.push_str(class);
+        self.out.push_str("\">");
+
+        content(self);
+
+        self.out.push_str("</span>");
+    }
+
+    /// Writes a `<span>` element with the given class containing the given text.
+    fn leaf(&mut self, class: &str, text: &str) {
+        let variant = self.variant;
+
+        self.span(class, |this| match variant {
+            Some(variant) => {
+                let text: String = text.chars().map(|c| variant.apply(c)).collect();
+                escape_into(&mut this.out, &text);
+            }
+            None => escape_into(&mut this.out, text),
+        });
+    }
+
+    fn exprs<'e, I>(&mut self, exprs: I)
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        for expr in exprs {
+            self.expr(expr);
+        }
+    }
+
+    fn expr(&mut self, expr: &Expression) {
+        if let Some(matrix) = expr.as_matrix() {
+            return self.matrix(&matrix);
+        }
+
+        if !expr.is_scripted() {
+            return self.simple(&expr.interm);
+        }
+
+        if expr.interm.is_underover() {
+            return self.span("munderover", |this| {
+                if let Some(sup) = &expr.supscript {
+                    this.span("mover", |this| this.script(sup));
+                }
+
+                this.span("mbase", |this| this.simple(&expr.interm));
+
+                if let Some(sub) = &expr.subscript {
+                    this.span("munder", |this| this.script(sub));
+                }
+            });
+        }
+
+        self.span("msubsup", |this| {
+            this.span("mbase", |this| this.simple(&expr.interm));
+
+            match (&expr.subscript, &expr.supscript) {
+                (Some(sub), Some(sup)) => this.span("mscripts", |this| {
+                    this.span("msup", |this| this.script(sup));
+                    this.span("msub", |this| this.script(sub));
+                }),
+                (Some(sub), None) => this.span("msub", |this| this.script(sub)),
+                (None, Some(sup)) => this.span("msup", |this| this.script(sup)),
+                (None, None) => {}
+            }
+        });
+    }
+
+    fn simple(&mut self, expr: &SimpleExpr) {
+        match expr {
+            SimpleExpr::Var(var) => self.var(var),
+            SimpleExpr::Grouping(grp) => self.grouping(grp),
+            SimpleExpr::Unary(unary) => self.unary(unary),
+            SimpleExpr::Binary(binary) => self.binary(binary),
+            SimpleExpr::Interm(inner) => self.expr(inner),
+        }
+    }
+
+    /// Writes an argument of unary or binary operators. Simple groupings are written without the
+    /// grouping symbols.
+    fn arg(&mut self, expr: &SimpleExpr) {
+        match expr {
+            SimpleExpr::Grouping(grp) if grp.is_simple_grp() => {
+                self.span("mrow", |this| this.exprs(&grp.expr))
+            }
+            _ => self.simple(expr),
+        }
+    }
+
+    /// Writes a subscript or superscript. Groupings are written without the grouping symbols.
+    fn script(&mut self, expr: &SimpleExpr) {
+        match expr {
+            SimpleExpr::Grouping(grp) => self.exprs(&grp.expr),
+            _ => self.simple(expr),
+        }
+    }
+
+    fn var(&mut self, var: &Var) {
+        match &var.kind {
+            VarKind::Function(func @ (Function::F | Function::G)) => {
+                self.leaf(self.ident_class(), func.as_ref())
+            }
+            VarKind::Function(func) => self.leaf("mi mop", func.as_ref()),
+            VarKind::Number(num) => self.leaf("mn", num),
+            VarKind::Greek(greek) => self.leaf(self.ident_class(), greek.as_unicode()),
+            VarKind::Variable(var) => self.leaf(self.ident_class(), var),
+            VarKind::Arrow(arrow) => self.leaf("mo mrel", arrow.as_unicode()),
+            VarKind::Relation(rel) => self.leaf("mo mrel", rel.as_unicode()),
+            VarKind::Logical(log) => self.leaf("mo mrel", log.as_unicode()),
+            VarKind::Operator(
+                op @ (Operator::Sum
+                | Operator::Prod
+                | Operator::BigWedge
+                | Operator::BigCap
+                | Operator::BigCup),
+            ) => self.leaf("mo mop-large", op.as_unicode()),
+            VarKind::Operator(op) => self.leaf("mo mbin", op.as_unicode()),
+            VarKind::Other(other) => {
+                let class = match other {
+                    Other::Comma => "mo mpunct",
+                    Other::Integral | Other::OIntegral => "mo mop-large",
+                    Other::EmptySet
+                    | Other::Infinity
+                    | Other::Aleph
+                    | Other::Complex
+                    | Other::Natural
+                    | Other::Rational
+                    | Other::Irrational
+                    | Other::Integer => "mi",
+                    _ => "mo",
+                };
+
+                self.leaf(class, other.as_unicode())
+            }
+            VarKind::Text(text) => self.leaf("mtext", &keep_spaces(text)),
+            VarKind::UnknownOperator(op) if op.is_empty() => {}
+            VarKind::UnknownOperator(op) => self.leaf("mo", op),
+        }
+    }
+
+    /// Identifiers are italic, unless a font command is applied to them.
+    fn ident_class(&self) -> &'static str {
+        match self.variant {
+            Some(_) => "mi",
+            None => "mi mvar",
+        }
+    }
+
+    fn delimiter(&mut self, grp: Grouping, is_opening: bool) {
+        let delimiter = grp.as_unicode(is_opening);
+
+        if !delimiter.is_empty() {
+            let class = if is_opening { "mo mopen" } else { "mo mclose" };
+            self.leaf(class, delimiter);
+        }
+    }
+
+    fn grouping(&mut self, grp: &GroupingExpr) {
+        self.span("mrow", |this| {
+            this.delimiter(grp.left_grouping, true);
+            this.exprs(&grp.expr);
+            this.delimiter(grp.right_grouping, false);
+        });
+    }
+
+    /// Writes the expression with the given symbol placed above it.
+    fn accent(&mut self, accent: &str, expr: &SimpleExpr) {
+        self.span("munderover", |this| {
+            this.leaf("mover maccent", accent);
+            this.span("mbase", |this| this.arg(expr));
+        });
+    }
+
+    fn unary(&mut self, unary: &Unary) {
+        if let Some(variant) = Variant::from_unary(unary.kind) {
+            let outer = self.variant.replace(variant);
+            self.arg(&unary.expr);
+            self.variant = outer;
+            return;
+        }
+
+        let delimited = |this: &mut Self, left: &str, right: &str| {
+            this.span("mrow", |this| {
+                this.leaf("mo mopen", left);
+                this.arg(&unary.expr);
+                this.leaf("mo mclose", right);
+            })
+        };
+
+        match unary.kind {
+            UnaryKind::Hat => self.accent("ˆ", &unary.expr),
+            UnaryKind::Vector => self.accent("→", &unary.expr),
+            UnaryKind::Tilde => self.accent("˜", &unary.expr),
+            UnaryKind::Dot => self.accent("˙", &unary.expr),
+            UnaryKind::DoubleDot => self.accent("¨", &unary.expr),
+            UnaryKind::Overline => self.span("moverline", |this| this.arg(&unary.expr)),
+            UnaryKind::Underline => self.span("munderline", |this| this.arg(&unary.expr)),
+            UnaryKind::Underbrace => self.span("munderover", |this| {
+                this.span("mbase", |this| this.arg(&unary.expr));
+                this.leaf("munder mbrace", "⏟");
+            }),
+            UnaryKind::Overbrace => self.span("munderover", |this| {
+                this.leaf("mover mbrace", "⏞");
+                this.span("mbase", |this| this.arg(&unary.expr));
+            }),
+            UnaryKind::Cancel => self.span("mcancel", |this| this.arg(&unary.expr)),
+            UnaryKind::SquareRoot => self.span("msqrt", |this| {
+                this.leaf("msqrt-sign", "√");
+                this.span("msqrt-body", |this| this.arg(&unary.expr));
+            }),
+            UnaryKind::Absolute => delimited(self, "|", "|"),
+            UnaryKind::Floor => delimited(self, "⌊", "⌋"),
+            UnaryKind::Ceiling => delimited(self, "⌈", "⌉"),
+            UnaryKind::Norm => delimited(self, "‖", "‖"),
+
+            // font commands are handled above
+            _ => self.arg(&unary.expr),
+        }
+    }
+
+    fn binary(&mut self, binary: &Binary) {
+        match binary.kind {
+            BinaryKind::Fraction => self.span("mfrac", |this| {
+                this.span("mfrac-num", |this| this.arg(&binary.expr_1));
+                this.span("mfrac-den", |this| this.arg(&binary.expr_2));
+            }),
+            BinaryKind::Root => self.span("mroot", |this| {
+                this.span("mroot-index", |this| this.arg(&binary.expr_1));
+                this.leaf("msqrt-sign", "√");
+                this.span("msqrt-body", |this| this.arg(&binary.expr_2));
+            }),
+            BinaryKind::Overset => self.span("munderover", |this| {
+                this.span("mover", |this| this.arg(&binary.expr_1));
+                this.span("mbase", |this| this.arg(&binary.expr_2));
+            }),
+            BinaryKind::Underset => self.span("munderover", |this| {
+                this.span("mbase", |this| this.arg(&binary.expr_2));
+                this.span("munder", |this| this.arg(&binary.expr_1));
+            }),
+            BinaryKind::Color => {
+                let color = match &*binary.expr_1 {
+                    SimpleExpr::Var(Var {
+                        kind: VarKind::Text(color),
+                        ..
+                    }) => Some(color.trim()).filter(|color| is_css_color(color)),
+                    _ => None,
+                };
+
+                match color {
+                    Some(color) => {
+                        self.out.push_str("<span class=\"mstyle\" style=\"color:");
+                        self.out.push_str(color);
+                        self.out.push_str("\">");
+                        self.arg(&binary.expr_2);
+                        self.out.push_str("</span>");
+                    }
+                    None => self.arg(&binary.expr_2),
+                }
+            }
+        }
+    }
+
+    fn matrix(&mut self, matrix: &Matrix<'_>) {
+        let columns = matrix.num_of_columns();
+
+        self.span("mrow", |this| {
+            this.delimiter(matrix.left_grouping, true);
+
+            this.out.push_str(&format!(
+                "<span class=\"mtable\" style=\"grid-template-columns:repeat({columns},auto)\">"
+            ));
+
+            for row in &matrix.rows {
+                for i in 0..columns {
+                    let has_line = matrix.column_lines.get(i).copied().unwrap_or(false);
+                    let class = if has_line && i + 1 < columns {
+                        "mtd mtd-line"
+                    } else {
+                        "mtd"
+                    };
+
+                    // shorter rows are padded with empty cells to keep the grid aligned
+                    this.span(class, |this| {
+                        if let Some(cell) = row.get(i) {
+                            this.exprs(cell.iter().copied());
+                        }
+                    });
+                }
+            }
+
+            this.out.push_str("</span>");
+            this.delimiter(matrix.right_grouping, false);
+        });
+    }
+}
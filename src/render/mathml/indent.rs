@@ -0,0 +1,178 @@
+//! Indentation of MathMl markup while it's written, see
+//! [`RenderOptions::indent`](super::RenderOptions::indent).
+
+use core::fmt::{self, Write};
+
+/// Sink indenting the markup written into it, before writing it into the output. Tags and texts
+/// are kept until they are complete, the rest is written by [`Indented::finish`].
+pub(super) struct Indented<'w, W: ?Sized> {
+    out: &'w mut W,
+    indenter: Indenter,
+
+    /// Start of a tag or a text that is not complete yet.
+    partial: String,
+}
+
+impl<'w, W: Write + ?Sized> Indented<'w, W> {
+    pub(super) fn new(out: &'w mut W, width: usize) -> Self {
+        Self {
+            out,
+            indenter: Indenter::new(width),
+            partial: String::new(),
+        }
+    }
+
+    /// Writes the tag or text that is not complete yet, once all markup was written.
+    pub(super) fn finish(mut self) -> fmt::Result {
+        if !self.partial.is_empty() {
+            self.indenter.part(&self.partial, self.out)?;
+        }
+
+        self.indenter.finish(self.out)
+    }
+}
+
+impl<W: Write + ?Sized> Write for Indented<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.partial.push_str(s);
+
+        loop {
+            // a tag ends with `>`, a text ends where the next tag starts
+            let end = match self.partial.starts_with('<') {
+                true => self.partial.find('>').map(|i| i + 1),
+                false => self.partial.find('<'),
+            };
+
+            match end {
+                Some(end) if end > 0 => {
+                    self.indenter.part(&self.partial[..end], self.out)?;
+                    self.partial.drain(..end);
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Indentation of markup, with every element in its own line and the nested elements indented by
+/// the given number of spaces. Elements that contain only text stay in a single line. The markup
+/// is given as a sequence of complete tags and texts.
+struct Indenter {
+    width: usize,
+    depth: usize,
+
+    /// Whether a line was written already, lines are separated by line breaks.
+    started: bool,
+
+    /// Opening tag, and the text after it, waiting for the next part to decide whether they stay
+    /// in a single line with the closing tag.
+    pending: String,
+    state: Pending,
+}
+
+#[derive(Clone, Copy)]
+enum Pending {
+    None,
+    Open,
+    /// Opening tag followed by text, which starts at the given index.
+    OpenText(usize),
+}
+
+impl Indenter {
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            depth: 0,
+            started: false,
+            pending: String::new(),
+            state: Pending::None,
+        }
+    }
+
+    /// Writes the part that is still pending, once all markup was written.
+    fn finish<W: Write + ?Sized>(&mut self, out: &mut W) -> fmt::Result {
+        self.flush(out)
+    }
+
+    fn part<W: Write + ?Sized>(&mut self, part: &str, out: &mut W) -> fmt::Result {
+        let is_text = !part.starts_with('<');
+        let is_close = part.starts_with("</");
+
+        match self.state {
+            // elements without nested elements stay in a single line
+            Pending::Open | Pending::OpenText(_) if is_close => {
+                self.pending.push_str(part);
+                self.state = Pending::None;
+                return self.line(Line::Pending, out);
+            }
+            Pending::Open if is_text => {
+                self.state = Pending::OpenText(self.pending.len());
+                self.pending.push_str(part);
+                return Ok(());
+            }
+            _ => self.flush(out)?,
+        }
+
+        if is_text || part.ends_with("/>") {
+            return self.line(Line::Part(part), out);
+        }
+
+        if is_close {
+            self.depth = self.depth.saturating_sub(1);
+            return self.line(Line::Part(part), out);
+        }
+
+        self.pending.push_str(part);
+        self.state = Pending::Open;
+        Ok(())
+    }
+
+    /// Writes the pending opening tag in its own line, followed by the text after it, if any.
+    fn flush<W: Write + ?Sized>(&mut self, out: &mut W) -> fmt::Result {
+        match core::mem::replace(&mut self.state, Pending::None) {
+            Pending::None => Ok(()),
+            Pending::Open => {
+                self.line(Line::Pending, out)?;
+                self.depth += 1;
+                Ok(())
+            }
+            Pending::OpenText(text) => {
+                self.line(Line::PendingUntil(text), out)?;
+                self.depth += 1;
+                self.line(Line::PendingFrom(text), out)
+            }
+        }
+    }
+
+    fn line<W: Write + ?Sized>(&mut self, line: Line<'_>, out: &mut W) -> fmt::Result {
+        if self.started {
+            out.write_char('\n')?;
+        }
+
+        self.started = true;
+        write!(out, "{:1$}", "", self.depth * self.width)?;
+
+        match line {
+            Line::Part(part) => out.write_str(part),
+            Line::Pending => {
+                out.write_str(&self.pending)?;
+                self.pending.clear();
+                Ok(())
+            }
+            Line::PendingUntil(end) => out.write_str(&self.pending[..end]),
+            Line::PendingFrom(start) => {
+                out.write_str(&self.pending[start..])?;
+                self.pending.clear();
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Content of a line written by the [`Indenter`].
+enum Line<'p> {
+    Part(&'p str),
+    Pending,
+    PendingUntil(usize),
+    PendingFrom(usize),
+}
@@ -0,0 +1,564 @@
+//! Writing of the abstract syntax tree as MathMl markup. The markup is written into the output
+//! while the tree is walked, without building the elements first.
+//!
+//! The markup is the same as the one of the elements of [`MathMlRenderer`] written by alemat,
+//! except for `cancel`, which alemat has no element for.
+//!
+//! [`MathMlRenderer`]: crate::renderer::MathMlRenderer
+
+use core::fmt::{self, Write};
+
+use crate::{
+    lexer::keywords::{
+        arrows::Arrow, greeks::Greek, groupings::Grouping, logicals::Logical, operators::Operator,
+        others::Other, relations::Relation,
+    },
+    parser::Matrix,
+    render::keep_spaces,
+    Binary, BinaryKind, Expression, GroupingExpr, SimpleExpr, Unary, UnaryKind, Var, VarKind,
+};
+
+/// Writes expressions as MathMl markup into the output.
+pub(crate) struct Markup<'w, W: ?Sized> {
+    out: &'w mut W,
+
+    /// Whether groupings are wrapped in `<mrow>` elements, see
+    /// [`RenderOptions::wrap_groupings`](super::RenderOptions::wrap_groupings).
+    wrap_groupings: bool,
+}
+
+impl<'w, W: Write + ?Sized> Markup<'w, W> {
+    pub(crate) fn new(out: &'w mut W, wrap_groupings: bool) -> Self {
+        Self {
+            out,
+            wrap_groupings,
+        }
+    }
+
+    /// Writes the elements of the expression.
+    pub(crate) fn write(&mut self, expr: &Expression) -> fmt::Result {
+        self.expr(expr, !self.wrap_groupings)
+    }
+
+    /// Writes an element with the given attributes, and the content written by `content`. The
+    /// attributes are written as they are, with a leading space.
+    fn element<F>(&mut self, tag: &str, attrs: &str, content: F) -> fmt::Result
+    where
+        F: FnOnce(&mut Self) -> fmt::Result,
+    {
+        write!(self.out, "<{tag}{attrs}>")?;
+        content(self)?;
+        write!(self.out, "</{tag}>")
+    }
+
+    /// Writes an element containing the given text.
+    fn leaf(&mut self, tag: &str, text: &str) -> fmt::Result {
+        write!(self.out, "<{tag}>{text}</{tag}>")
+    }
+
+    /// Writes a `<mrow>` with the content written by `content`. Rows in the sequence of elements
+    /// of an expression are left out if they are `flat`, and their content is part of the
+    /// sequence, so its rows are left out too. Rows anywhere else are always written.
+    fn row<F>(&mut self, flat: bool, content: F) -> fmt::Result
+    where
+        F: FnOnce(&mut Self, bool) -> fmt::Result,
+    {
+        match flat {
+            true => content(self, true),
+            false => self.element("mrow", "", |this| content(this, false)),
+        }
+    }
+
+    /// Writes a child of an element, in a `<mrow>` if it consists of more than one element.
+    fn child<F>(&mut self, len: usize, content: F) -> fmt::Result
+    where
+        F: FnOnce(&mut Self) -> fmt::Result,
+    {
+        match len {
+            0 | 1 => content(self),
+            _ => self.element("mrow", "", content),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expression, flat: bool) -> fmt::Result {
+        if let Some(matrix) = expr.as_matrix() {
+            return self.matrix(&matrix, flat);
+        }
+
+        if !expr.is_scripted() {
+            return self.simple(&expr.interm, flat);
+        }
+
+        // alemat writes a space after the tag name of under- and overscripts
+        let (tag, attrs) = match (&expr.subscript, &expr.supscript) {
+            (Some(_), Some(_)) if expr.interm.is_underover() => ("munderover", " "),
+            (Some(_), None) if expr.interm.is_underover() => ("munder", " "),
+            (None, _) if expr.interm.is_underover() => ("mover", " "),
+            (Some(_), Some(_)) => ("msubsup", ""),
+            (Some(_), None) => ("msub", ""),
+            (None, _) => ("msup", ""),
+        };
+
+        self.element(tag, attrs, |this| {
+            this.child(len_simple(&expr.interm), |this| {
+                this.simple(&expr.interm, false)
+            })?;
+
+            for script in [&expr.subscript, &expr.supscript].into_iter().flatten() {
+                match script {
+                    SimpleExpr::Grouping(grp) => {
+                        this.child(len_ungrouped(grp), |this| this.ungrouped(grp, false))?
+                    }
+                    _ => this.child(len_simple(script), |this| this.simple(script, false))?,
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    fn simple(&mut self, expr: &SimpleExpr, flat: bool) -> fmt::Result {
+        match expr {
+            SimpleExpr::Var(var) => self.var(var, flat),
+            SimpleExpr::Grouping(grp) => self.row(flat, |this, flat| this.fenced(grp, flat)),
+            SimpleExpr::Unary(unary) => self.unary(unary, flat),
+            SimpleExpr::Binary(binary) => self.binary(binary),
+            SimpleExpr::Interm(expr) => self.expr(expr, flat),
+        }
+    }
+
+    /// Writes the argument of an operator, see [`mathml::argument`].
+    ///
+    /// [`mathml::argument`]: crate::renderer::mathml::argument
+    fn argument(&mut self, expr: &SimpleExpr, flat: bool) -> fmt::Result {
+        match expr {
+            SimpleExpr::Grouping(grp) if grp.is_simple_grp() => self.ungrouped(grp, flat),
+            SimpleExpr::Grouping(grp) => self.fenced(grp, flat),
+            _ => self.simple(expr, flat),
+        }
+    }
+
+    /// Writes the expressions between the grouping symbols, without the symbols.
+    fn ungrouped(&mut self, grp: &GroupingExpr, flat: bool) -> fmt::Result {
+        grp.expr.iter().try_for_each(|expr| self.expr(expr, flat))
+    }
+
+    /// Writes the grouping symbols and the expressions between them.
+    fn fenced(&mut self, grp: &GroupingExpr, flat: bool) -> fmt::Result {
+        self.grouping_symbol(grp.left_grouping, true)?;
+        self.ungrouped(grp, flat)?;
+        self.grouping_symbol(grp.right_grouping, false)
+    }
+
+    fn var(&mut self, var: &Var, flat: bool) -> fmt::Result {
+        match &var.kind {
+            VarKind::Function(func) => self.leaf("mi", func.as_ref()),
+            VarKind::Greek(grk) => self.leaf("mi", greek(*grk)),
+            VarKind::Variable(ident) => self.leaf("mi", ident),
+
+            VarKind::Relation(rel) => self.leaf("mo", relation(*rel)),
+            VarKind::Logical(log) => self.leaf("mo", logical(*log)),
+            VarKind::Operator(op) => self.leaf("mo", operator(*op)),
+            VarKind::Arrow(arr) => self.leaf("mo", arrow(*arr)),
+
+            VarKind::Other(other) => self.other(*other, flat),
+            VarKind::Text(text) => self.leaf("mtext", &keep_spaces(text)),
+            VarKind::Number(num) => self.leaf("mn", num),
+            VarKind::UnknownOperator(op) => self.leaf("mo", op),
+        }
+    }
+
+    fn other(&mut self, other: Other, flat: bool) -> fmt::Result {
+        let op = match other {
+            Other::VerticalBars => {
+                return self.row(flat, |this, _| {
+                    this.out.write_str("<mo>|</mo><mo> </mo><mo>|</mo>")
+                })
+            }
+            Other::VerticalBarsWide => {
+                return self.row(flat, |this, _| {
+                    this.out.write_str("<mo>|</mo><mo>  </mo><mo>|</mo>")
+                })
+            }
+            Other::EmptySet => return self.leaf("mi", "∅"),
+            Other::Infinity => return self.leaf("mi", "∞"),
+            Other::Aleph => return self.leaf("mi", "ℵ"),
+            Other::Complex => return self.leaf("mi", "ℂ"),
+            Other::Natural => return self.leaf("mi", "ℕ"),
+            Other::Rational => return self.leaf("mi", "ℚ"),
+            Other::Irrational => return self.leaf("mi", "ℝ"),
+            Other::Integer => return self.leaf("mi", "ℤ"),
+
+            Other::Comma => ",",
+            Other::ForwardSlash => "/",
+            Other::Integral => "∫",
+            Other::OIntegral => "∮",
+            Other::Partial => "∂",
+            Other::Nabla => "∇",
+            Other::PlusMinus => "±",
+            Other::Therefore => "∴",
+            Other::Because => "∵",
+            Other::LowDots => "...",
+            Other::CenterDots => "⋯",
+            Other::VerticalDots => "⋮",
+            Other::DiagonalDots => "⋱",
+            Other::VerticalBar => "|",
+            Other::Angle => "∠",
+            Other::Frown => "⌢",
+            Other::Triangle => "△",
+            Other::Diamond => "◇",
+            Other::Square => "□",
+            Other::LeftFloor => "⌊",
+            Other::RightFloor => "⌋",
+            Other::LeftCeiling => "⌈",
+            Other::RightCeiling => "⌉",
+
+            // symbols that are part of the grammar are written as they are
+            Other::Fraction
+            | Other::Power
+            | Other::Subscript
+            | Other::SquareRoot
+            | Other::Root
+            | Other::Text
+            | Other::Quote => other.as_ref(),
+        };
+
+        self.leaf("mo", op)
+    }
+
+    fn grouping_symbol(&mut self, grp: Grouping, is_opening: bool) -> fmt::Result {
+        let op = match grp {
+            Grouping::OpenIgnored => return self.out.write_str("<mphantom><mo>{</mo></mphantom>"),
+            Grouping::CloseIgnored => return self.out.write_str("<mphantom><mo>}</mo></mphantom>"),
+
+            Grouping::OpenParen => "(",
+            Grouping::CloseParen => ")",
+            Grouping::OpenBracket => "[",
+            Grouping::CloseBracket => "]",
+            Grouping::OpenBrace => "{",
+            Grouping::CloseBrace => "}",
+            Grouping::LeftAngled => "⟨",
+            Grouping::RightAngled => "⟩",
+            Grouping::Absolute => "|",
+            Grouping::Floor if is_opening => "⌊",
+            Grouping::Floor => "⌋",
+            Grouping::Ceiling if is_opening => "⌈",
+            Grouping::Ceiling => "⌉",
+            Grouping::NormFn | Grouping::Norm => "∥",
+        };
+
+        self.leaf("mo", op)
+    }
+
+    fn unary(&mut self, unary: &Unary, flat: bool) -> fmt::Result {
+        let accent = |this: &mut Self, tag: &str, accent: &str| {
+            this.element(tag, " ", |this| {
+                this.child(len_argument(&unary.expr), |this| {
+                    this.argument(&unary.expr, false)
+                })?;
+                this.leaf("mo", accent)
+            })
+        };
+
+        let delimited = |this: &mut Self, flat: bool, left: &str, right: &str| {
+            this.leaf("mo", left)?;
+            this.argument(&unary.expr, flat)?;
+            this.leaf("mo", right)
+        };
+
+        let variant = match unary.kind {
+            UnaryKind::Hat => return accent(self, "mover", "^"),
+            UnaryKind::Overline => return accent(self, "mover", "¯"),
+            UnaryKind::Underline => return accent(self, "munder", "¯"),
+            UnaryKind::Vector => return accent(self, "mover", "→"),
+            UnaryKind::Tilde => return accent(self, "mover", "~"),
+            UnaryKind::Dot => return accent(self, "mover", "⋅"),
+            UnaryKind::DoubleDot => return accent(self, "mover", "¨"),
+            UnaryKind::Underbrace => return accent(self, "munder", "⏟"),
+            UnaryKind::Overbrace => return accent(self, "mover", "⏞"),
+            UnaryKind::Cancel => {
+                let attrs = " notation=\"updiagonalstrike\"";
+                return self.element("menclose", attrs, |this| match len_argument(&unary.expr) {
+                    1 => this.argument(&unary.expr, false),
+                    _ => this.element("mrow", "", |this| this.argument(&unary.expr, false)),
+                });
+            }
+            UnaryKind::SquareRoot => {
+                return self.element("msqrt", "", |this| this.argument(&unary.expr, false))
+            }
+            UnaryKind::Absolute => return delimited(self, flat, "|", "|"),
+            UnaryKind::Floor => return delimited(self, flat, "⌊", "⌋"),
+            UnaryKind::Ceiling => return delimited(self, flat, "⌈", "⌉"),
+            UnaryKind::Norm => return self.row(flat, |this, flat| delimited(this, flat, "∥", "∥")),
+            UnaryKind::Bold => "bold",
+            UnaryKind::BlackboardBold => "double-struck",
+            UnaryKind::Calligraphic => "script",
+            UnaryKind::Typewriter => "monospace",
+            UnaryKind::Gothic => "fraktur",
+            UnaryKind::SansSerif => "sans-serif",
+        };
+
+        let attrs = format!(" mathvariant=\"{variant}\"");
+        self.element("mstyle", &attrs, |this| this.argument(&unary.expr, false))
+    }
+
+    fn binary(&mut self, binary: &Binary) -> fmt::Result {
+        let (first, second) = (&*binary.expr_1, &*binary.expr_2);
+
+        let arg = |this: &mut Self, expr: &SimpleExpr| {
+            this.child(len_argument(expr), |this| this.argument(expr, false))
+        };
+
+        match binary.kind {
+            BinaryKind::Fraction => self.element("mfrac", "", |this| {
+                arg(this, first)?;
+                arg(this, second)
+            }),
+            // alemat writes roots with the index 2 as square roots
+            BinaryKind::Root if is_two(first) => {
+                self.element("msqrt", "", |this| this.argument(second, false))
+            }
+            BinaryKind::Root => self.element("mroot", "", |this| {
+                arg(this, second)?;
+                arg(this, first)
+            }),
+            BinaryKind::Overset => self.element("mover", " ", |this| {
+                arg(this, second)?;
+                arg(this, first)
+            }),
+            BinaryKind::Underset => self.element("munder", " ", |this| {
+                arg(this, second)?;
+                arg(this, first)
+            }),
+            BinaryKind::Color => {
+                // the parser reads the color as text, empty colors are removed by sanitization
+                let attrs = match first {
+                    SimpleExpr::Var(Var {
+                        kind: VarKind::Text(color),
+                        ..
+                    }) if !color.is_empty() => format!(" mathcolor=\"{color}\""),
+                    _ => String::new(),
+                };
+
+                self.element("mstyle", &attrs, |this| this.argument(second, false))
+            }
+        }
+    }
+
+    /// Writes the matrix into a table between the grouping symbols of the matrix, see
+    /// [`mathml::matrix`](crate::renderer::mathml::matrix).
+    fn matrix(&mut self, matrix: &Matrix<'_>, flat: bool) -> fmt::Result {
+        let mut attrs = String::from(" columnlines=\"");
+
+        for &line in &matrix.column_lines {
+            attrs.push_str(if line { "solid " } else { "none " });
+        }
+
+        attrs.push('"');
+
+        self.row(flat, |this, _| {
+            this.grouping_symbol(matrix.left_grouping, true)?;
+
+            this.element("mtable", &attrs, |this| {
+                matrix.rows.iter().try_for_each(|row| {
+                    this.element("mtr", "", |this| {
+                        row.iter().try_for_each(|cell| {
+                            this.element("mtd", "", |this| match cell.len() {
+                                2.. => this.element("mrow", "", |this| {
+                                    cell.iter().try_for_each(|expr| this.expr(expr, false))
+                                }),
+                                1 => this.expr(cell[0], false),
+                                _ => this.out.write_str("<mphantom></mphantom>"),
+                            })
+                        })
+                    })
+                })
+            })?;
+
+            this.grouping_symbol(matrix.right_grouping, false)
+        })
+    }
+}
+
+/// Returns the number of elements the expression is written as, when it's not part of the
+/// sequence of elements of an expression.
+fn len_expr(expr: &Expression) -> usize {
+    match expr.is_scripted() || expr.is_matrix() {
+        true => 1,
+        false => len_simple(&expr.interm),
+    }
+}
+
+fn len_simple(expr: &SimpleExpr) -> usize {
+    match expr {
+        SimpleExpr::Unary(Unary {
+            kind: UnaryKind::Absolute | UnaryKind::Floor | UnaryKind::Ceiling,
+            expr,
+            ..
+        }) => len_argument(expr) + 2,
+        SimpleExpr::Interm(expr) => len_expr(expr),
+        _ => 1,
+    }
+}
+
+fn len_argument(expr: &SimpleExpr) -> usize {
+    match expr {
+        SimpleExpr::Grouping(grp) if grp.is_simple_grp() => len_ungrouped(grp),
+        SimpleExpr::Grouping(grp) => len_ungrouped(grp) + 2,
+        _ => len_simple(expr),
+    }
+}
+
+fn len_ungrouped(grp: &GroupingExpr) -> usize {
+    grp.expr.iter().map(len_expr).sum()
+}
+
+/// Checks whether the argument is written as the single number 2.
+fn is_two(expr: &SimpleExpr) -> bool {
+    match expr {
+        SimpleExpr::Grouping(grp) if grp.is_simple_grp() => match &grp.expr[..] {
+            [expr] => !expr.is_scripted() && !expr.is_matrix() && is_number_two(&expr.interm),
+            _ => false,
+        },
+        _ => is_number_two(expr),
+    }
+}
+
+fn is_number_two(expr: &SimpleExpr) -> bool {
+    match expr {
+        SimpleExpr::Var(Var {
+            kind: VarKind::Number(num),
+            ..
+        }) => num.parse::<f32>().is_ok_and(|num| num == 2.0),
+        SimpleExpr::Interm(expr) => {
+            !expr.is_scripted() && !expr.is_matrix() && is_number_two(&expr.interm)
+        }
+        _ => false,
+    }
+}
+
+fn greek(greek: Greek) -> &'static str {
+    match greek {
+        Greek::Alpha => "𝛼",
+        Greek::Beta => "𝛽",
+        Greek::Gamma => "𝛾",
+        Greek::BigGamma => "Γ",
+        Greek::Delta => "𝛿",
+        Greek::Epsilon => "𝜀",
+        Greek::Varepsilon => "𝜖",
+        Greek::Zeta => "𝜁",
+        Greek::Eta => "𝜂",
+        Greek::Theta => "𝜃",
+        Greek::Vartheta => "𝜗",
+        Greek::Iota => "𝜄",
+        Greek::Kappa => "𝜅",
+        Greek::Lambda => "𝜆",
+        Greek::Mu => "𝜇",
+        Greek::Nu => "𝜈",
+        Greek::Xi => "𝜉",
+        Greek::BigXi => "Ξ",
+        Greek::Pi => "𝜋",
+        Greek::BigPi => "Π",
+        Greek::Rho => "𝜌",
+        Greek::Sigma => "𝜎",
+        Greek::BigSigma => "Σ",
+        Greek::Tau => "𝜏",
+        Greek::Upsilon => "𝜐",
+        Greek::Phi => "𝜑",
+        Greek::BigPhi => "Φ",
+        Greek::Varphi => "𝜙",
+        Greek::Chi => "𝜓",
+        Greek::Psi => "ψ",
+        Greek::BigPsi => "Ψ",
+        Greek::Omega => "ω",
+        Greek::BigOmega => "Ω",
+    }
+}
+fn operator(operator: Operator) -> &'static str {
+    match operator {
+        Operator::Plus => "+",
+        Operator::Minus => "-",
+        Operator::Prime => "'",
+        Operator::Dot => "⋅",
+        Operator::Asterisk => "∗",
+        Operator::Star => "⋆",
+        Operator::ForwardSlashLiteral => "/",
+        Operator::Backslash => "∖",
+        Operator::Times => "×",
+        Operator::Divide => "÷",
+        Operator::LTimes => "⋉",
+        Operator::RTimes => "⋊",
+        Operator::Bowtie => "⋈",
+        Operator::Circle => "∘",
+        Operator::OPlus => "⊕",
+        Operator::OTimes => "⊗",
+        Operator::ODot => "⊙",
+        Operator::Sum => "∑",
+        Operator::Prod => "∏",
+        Operator::Wedge => "∧",
+        Operator::BigWedge => "⋀",
+        Operator::Cap => "∩",
+        Operator::BigCap => "⋂",
+        Operator::Cup => "∪",
+        Operator::BigCup => "⋃",
+    }
+}
+fn relation(relation: Relation) -> &'static str {
+    match relation {
+        Relation::Eq => "=",
+        Relation::NotEq => "≠",
+        Relation::Define => "≔",
+        Relation::LessThan => "<",
+        Relation::GreaterThan => ">",
+        Relation::LessEqualThan => "≤",
+        Relation::GreaterEqualThan => "≥",
+        Relation::MuchLessThan => "m<",
+        Relation::MuchGreaterThan => "m>",
+        Relation::Prec => "≺",
+        Relation::PrecEq => "≼",
+        Relation::Succ => "≻",
+        Relation::SuccEq => "≽",
+        Relation::In => "∈",
+        Relation::NotIn => "∉",
+        Relation::Subset => "⊂",
+        Relation::Superset => "⊃",
+        Relation::SubsetEq => "⊆",
+        Relation::SupersetEq => "⊇",
+        Relation::Equivalent => "≡",
+        Relation::Congruent => "≅",
+        Relation::Approximate => "≈",
+        Relation::Prop => "∝",
+    }
+}
+fn logical(logical: Logical) -> &'static str {
+    match logical {
+        Logical::And => "∧",
+        Logical::Or => "∨",
+        Logical::Not => "¬",
+        Logical::Implies => "⇒",
+        Logical::If => "if",
+        Logical::IfAndOnlyIf => "⇔",
+        Logical::ForAll => "∀",
+        Logical::Exists => "∃",
+        Logical::Bottom => "⊥",
+        Logical::Top => "⊤",
+        Logical::VerticalDash => "⊢",
+        Logical::Models => "⊨",
+    }
+}
+fn arrow(arrow: Arrow) -> &'static str {
+    match arrow {
+        Arrow::Up => "↑",
+        Arrow::Down => "↓",
+        Arrow::Right => "→",
+        Arrow::RightTail => "↣",
+        Arrow::TwoHeadRight => "↠",
+        Arrow::TwoHeadRightTail => "⤖",
+        Arrow::MapsTo => "↦",
+        Arrow::Left => "←",
+        Arrow::LeftRight => "↔",
+        Arrow::BigRight => "⇒",
+        Arrow::BigLeft => "⇐",
+        Arrow::BigLeftRight => "⇔",
+    }
+}
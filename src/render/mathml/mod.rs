@@ -0,0 +1,253 @@
+//! Writing of the expressions as MathMl markup, with the options of the output.
+
+mod indent;
+mod markup;
+
+use core::{
+    borrow::Borrow,
+    fmt::{self, Write},
+};
+use std::{borrow::Cow, io};
+
+use crate::{Expression, LimitError, Limits};
+
+use self::{indent::Indented, markup::Markup};
+use super::escape_into;
+
+/// Options for rendering MathMl with [`render_mathml_with`](crate::render_mathml_with).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Whether the math is displayed in its own block, or inline with the surrounding text.
+    pub display: DisplayMode,
+
+    /// Alternative text of the math, e.g. the AsciiMath source, for systems that don't render
+    /// MathMl.
+    pub alttext: Option<String>,
+
+    /// Whether the `math` element declares the MathMl namespace, as required when it's embedded
+    /// into XML documents like XHTML or EPUB.
+    pub xmlns: bool,
+
+    /// Number of spaces the nested elements are indented with, one element per line. By default,
+    /// the output is written in a single line.
+    pub indent: Option<usize>,
+
+    /// Direction of the math, e.g. for right-to-left languages.
+    pub dir: Option<Direction>,
+
+    /// Font size of the math, as any CSS length or percentage, e.g. `1.2em` or `120%`.
+    pub mathsize: Option<String>,
+
+    /// Whether groupings, like `(a + b)`, are wrapped in `<mrow>` elements. Groupings that are
+    /// the base of a script or the argument of a command are always wrapped.
+    pub wrap_groupings: bool,
+
+    /// Whether the output is sanitized for untrusted input, see
+    /// [`render_mathml_safe`](crate::render_mathml_safe).
+    pub safe: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            display: DisplayMode::Inline,
+            alttext: None,
+            xmlns: false,
+            indent: None,
+            dir: None,
+            mathsize: None,
+            wrap_groupings: true,
+            safe: false,
+        }
+    }
+}
+
+/// How the math is displayed, see [`RenderOptions::display`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DisplayMode {
+    /// Displayed inside the surrounding text, in compact style. This is the default of MathMl,
+    /// so no attribute is written.
+    #[default]
+    Inline,
+
+    /// Displayed in its own block, i.e. `display="block"`.
+    Block,
+}
+
+/// Direction of the math, see [`RenderOptions::dir`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left to right, i.e. `dir="ltr"`.
+    LeftToRight,
+
+    /// Right to left, i.e. `dir="rtl"`.
+    RightToLeft,
+}
+
+/// Namespace of MathMl, declared with [`RenderOptions::xmlns`].
+const XMLNS: &str = "http://www.w3.org/1998/Math/MathML";
+
+/// Renders the expressions into a string of mathml with the given options.
+pub(crate) fn render<'src>(
+    exprs: impl IntoIterator<Item = impl Borrow<Expression<'src>>>,
+    options: &RenderOptions,
+) -> String {
+    let mut output = String::new();
+
+    write(exprs, options, &mut output).expect("writing into a string does not fail");
+
+    output
+}
+
+/// Renders the expressions into a string of mathml with the given options, within the maximum
+/// output length of the limits. Writing stops at the expression that exceeds it.
+pub(crate) fn render_limited<'src>(
+    exprs: impl IntoIterator<Item = impl Borrow<Expression<'src>>>,
+    options: &RenderOptions,
+    limits: &Limits,
+) -> Result<String, LimitError> {
+    let mut output = Bounded {
+        output: String::new(),
+        max: limits.max_output_len,
+    };
+
+    match write(exprs, options, &mut output) {
+        Ok(()) => Ok(output.output),
+        Err(fmt::Error) => Err(LimitError::OutputTooLong {
+            max: limits.max_output_len,
+        }),
+    }
+}
+
+/// Sink that fails instead of growing longer than the maximum length in bytes.
+struct Bounded {
+    output: String,
+    max: usize,
+}
+
+impl Write for Bounded {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.len() > self.max - self.output.len() {
+            return Err(fmt::Error);
+        }
+
+        self.output.push_str(s);
+        Ok(())
+    }
+}
+
+/// Writes the expressions into the sink as a `<math>` element, with the given options. The
+/// markup is written while the expressions are walked, nothing is kept besides the expression
+/// that's being written.
+pub(crate) fn write<'src, W>(
+    exprs: impl IntoIterator<Item = impl Borrow<Expression<'src>>>,
+    options: &RenderOptions,
+    out: &mut W,
+) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    match options.indent {
+        Some(width) => {
+            let mut indented = Indented::new(out, width);
+            write_math(exprs, options, &mut indented)?;
+            indented.finish()
+        }
+        None => write_math(exprs, options, out),
+    }
+}
+
+fn write_math<'src, W>(
+    exprs: impl IntoIterator<Item = impl Borrow<Expression<'src>>>,
+    options: &RenderOptions,
+    out: &mut W,
+) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    write_open(options, out)?;
+
+    for expr in exprs {
+        // only sanitized expressions are copied
+        let mut expr = Cow::Borrowed(expr.borrow());
+
+        if options.safe {
+            super::sanitize::sanitize(expr.to_mut());
+        }
+
+        Markup::new(out, options.wrap_groupings).write(&expr)?;
+    }
+
+    out.write_str("</math>")
+}
+
+/// Writes the expressions into the io sink as a `<math>` element, with the given options.
+pub(crate) fn write_io<'src, W>(
+    exprs: impl IntoIterator<Item = impl Borrow<Expression<'src>>>,
+    options: &RenderOptions,
+    out: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write + ?Sized,
+{
+    let mut adapter = IoAdapter { out, error: None };
+
+    match write(exprs, options, &mut adapter) {
+        Ok(()) => Ok(()),
+        Err(fmt::Error) => Err(adapter
+            .error
+            .unwrap_or_else(|| io::Error::other("formatting failed"))),
+    }
+}
+
+/// Adapter writing into an io sink, that keeps the io error, since [`fmt::Error`] carries none.
+struct IoAdapter<'w, W: ?Sized> {
+    out: &'w mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write + ?Sized> Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.out.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Writes the opening `<math>` tag with the attributes selected by the options, in the order
+/// alemat writes them.
+fn write_open<W: Write + ?Sized>(options: &RenderOptions, out: &mut W) -> fmt::Result {
+    out.write_str("<math")?;
+
+    if options.xmlns {
+        write!(out, " xmlns=\"{XMLNS}\"")?;
+    }
+
+    if options.display == DisplayMode::Block {
+        out.write_str(" display=\"block\"")?;
+    }
+
+    // attributes are written as they are, so their values are escaped
+    if let Some(alttext) = &options.alttext {
+        write!(out, " alttext=\"{}\"", escape(alttext))?;
+    }
+
+    match options.dir {
+        Some(Direction::LeftToRight) => out.write_str(" dir=\"ltr\"")?,
+        Some(Direction::RightToLeft) => out.write_str(" dir=\"rtl\"")?,
+        None => {}
+    }
+
+    if let Some(mathsize) = &options.mathsize {
+        write!(out, " mathsize=\"{}\"", escape(mathsize))?;
+    }
+
+    out.write_char('>')
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    escape_into(&mut escaped, value);
+    escaped
+}
@@ -0,0 +1,263 @@
+//! Renderers of the abstract syntax tree into output formats other than MathMl, and the options
+//! and sanitization of the MathMl output.
+
+use crate::{
+    lexer::keywords::{functions::Function, logicals::Logical, operators::Operator, others::Other},
+    Expression, SimpleExpr, UnaryKind, VarKind,
+};
+
+pub(crate) mod art;
+pub(crate) mod highlight;
+pub(crate) mod html;
+#[cfg(feature = "mathml")]
+pub(crate) mod mathml;
+pub(crate) mod omml;
+#[cfg(feature = "mathml")]
+pub(crate) mod sanitize;
+pub(crate) mod svg;
+pub(crate) mod typst;
+
+/// Font variants of letters and digits selected with font commands, e.g. `bbb` or `fr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Variant {
+    Bold,
+    DoubleStruck,
+    Script,
+    Fraktur,
+    SansSerif,
+    Monospace,
+}
+
+impl Variant {
+    /// Returns the variant selected by the given unary operator, if it is a font command.
+    pub(crate) fn from_unary(kind: UnaryKind) -> Option<Self> {
+        let variant = match kind {
+            UnaryKind::Bold => Variant::Bold,
+            UnaryKind::BlackboardBold => Variant::DoubleStruck,
+            UnaryKind::Calligraphic => Variant::Script,
+            UnaryKind::Typewriter => Variant::Monospace,
+            UnaryKind::Gothic => Variant::Fraktur,
+            UnaryKind::SansSerif => Variant::SansSerif,
+            _ => return None,
+        };
+
+        Some(variant)
+    }
+
+    /// Maps ASCII letters and digits to the corresponding characters of the Unicode
+    /// "Mathematical Alphanumeric Symbols" block. Other characters are returned unchanged.
+    pub(crate) fn apply(self, c: char) -> char {
+        // letters that are not part of the block, because they were already encoded before
+        let exception = match (self, c) {
+            (Variant::Script, 'B') => Some('ℬ'),
+            (Variant::Script, 'E') => Some('ℰ'),
+            (Variant::Script, 'F') => Some('ℱ'),
+            (Variant::Script, 'H') => Some('ℋ'),
+            (Variant::Script, 'I') => Some('ℐ'),
+            (Variant::Script, 'L') => Some('ℒ'),
+            (Variant::Script, 'M') => Some('ℳ'),
+            (Variant::Script, 'R') => Some('ℛ'),
+            (Variant::Script, 'e') => Some('ℯ'),
+            (Variant::Script, 'g') => Some('ℊ'),
+            (Variant::Script, 'o') => Some('ℴ'),
+            (Variant::Fraktur, 'C') => Some('ℭ'),
+            (Variant::Fraktur, 'H') => Some('ℌ'),
+            (Variant::Fraktur, 'I') => Some('ℑ'),
+            (Variant::Fraktur, 'R') => Some('ℜ'),
+            (Variant::Fraktur, 'Z') => Some('ℨ'),
+            (Variant::DoubleStruck, 'C') => Some('ℂ'),
+            (Variant::DoubleStruck, 'H') => Some('ℍ'),
+            (Variant::DoubleStruck, 'N') => Some('ℕ'),
+            (Variant::DoubleStruck, 'P') => Some('ℙ'),
+            (Variant::DoubleStruck, 'Q') => Some('ℚ'),
+            (Variant::DoubleStruck, 'R') => Some('ℝ'),
+            (Variant::DoubleStruck, 'Z') => Some('ℤ'),
+            _ => None,
+        };
+
+        if let Some(exception) = exception {
+            return exception;
+        }
+
+        // first capital letter, first small letter and first digit of the variant
+        let (upper, lower, digit) = match self {
+            Variant::Bold => (0x1D400, 0x1D41A, Some(0x1D7CE)),
+            Variant::Script => (0x1D49C, 0x1D4B6, None),
+            Variant::Fraktur => (0x1D504, 0x1D51E, None),
+            Variant::DoubleStruck => (0x1D538, 0x1D552, Some(0x1D7D8)),
+            Variant::SansSerif => (0x1D5A0, 0x1D5BA, Some(0x1D7E2)),
+            Variant::Monospace => (0x1D670, 0x1D68A, Some(0x1D7F6)),
+        };
+
+        let code = match c {
+            'A'..='Z' => upper + (c as u32 - 'A' as u32),
+            'a'..='z' => lower + (c as u32 - 'a' as u32),
+            '0'..='9' => match digit {
+                Some(digit) => digit + (c as u32 - '0' as u32),
+                None => return c,
+            },
+            _ => return c,
+        };
+
+        char::from_u32(code).unwrap_or(c)
+    }
+}
+
+/// Spacing class of an expression, used by renderers that lay out the expressions themselves to
+/// decide about the spaces around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Class {
+    Ord,
+    Group,
+    Bin,
+    /// Binary operator used as prefix, e.g. in `-x`.
+    Unary,
+    Rel,
+    Punct,
+    Func,
+    Large,
+}
+
+impl Class {
+    pub(crate) fn of(expr: &Expression) -> Self {
+        if expr.is_matrix() {
+            return Class::Ord;
+        }
+
+        let var = match &expr.interm {
+            SimpleExpr::Var(var) => var,
+            SimpleExpr::Grouping(_) => return Class::Group,
+            _ => return Class::Ord,
+        };
+
+        match &var.kind {
+            VarKind::Function(Function::F | Function::G) => Class::Ord,
+            VarKind::Function(_) => Class::Func,
+            VarKind::Arrow(_) | VarKind::Relation(_) => Class::Rel,
+            VarKind::Logical(
+                Logical::Not | Logical::ForAll | Logical::Exists | Logical::Bottom | Logical::Top,
+            ) => Class::Ord,
+            VarKind::Logical(_) => Class::Rel,
+            VarKind::Operator(
+                Operator::Sum
+                | Operator::Prod
+                | Operator::BigWedge
+                | Operator::BigCap
+                | Operator::BigCup,
+            ) => Class::Large,
+            VarKind::Operator(Operator::Prime) => Class::Ord,
+            VarKind::Operator(_) => Class::Bin,
+            VarKind::Other(Other::Comma) => Class::Punct,
+            VarKind::Other(Other::Integral | Other::OIntegral) => Class::Large,
+            VarKind::Other(Other::PlusMinus) => Class::Bin,
+            _ => Class::Ord,
+        }
+    }
+
+    /// Adjusts the class depending on the class of the preceding expression. Binary operators
+    /// without a left operand are used as prefix operators.
+    pub(crate) fn after(self, prev: Option<Class>) -> Self {
+        match (prev, self) {
+            (None | Some(Class::Bin | Class::Unary | Class::Rel | Class::Punct), Class::Bin)
+            | (Some(Class::Func | Class::Large), Class::Bin) => Class::Unary,
+            (_, class) => class,
+        }
+    }
+}
+
+/// Named colors of CSS, and the `currentcolor` and `transparent` keywords, sorted.
+#[rustfmt::skip]
+const NAMED_COLORS: &[&str] = &[
+    "aliceblue", "antiquewhite", "aqua", "aquamarine", "azure", "beige", "bisque", "black",
+    "blanchedalmond", "blue", "blueviolet", "brown", "burlywood", "cadetblue", "chartreuse",
+    "chocolate", "coral", "cornflowerblue", "cornsilk", "crimson", "currentcolor", "cyan",
+    "darkblue", "darkcyan", "darkgoldenrod", "darkgray", "darkgreen", "darkgrey", "darkkhaki",
+    "darkmagenta", "darkolivegreen", "darkorange", "darkorchid", "darkred", "darksalmon",
+    "darkseagreen", "darkslateblue", "darkslategray", "darkslategrey", "darkturquoise",
+    "darkviolet", "deeppink", "deepskyblue", "dimgray", "dimgrey", "dodgerblue", "firebrick",
+    "floralwhite", "forestgreen", "fuchsia", "gainsboro", "ghostwhite", "gold", "goldenrod", "gray",
+    "green", "greenyellow", "grey", "honeydew", "hotpink", "indianred", "indigo", "ivory", "khaki",
+    "lavender", "lavenderblush", "lawngreen", "lemonchiffon", "lightblue", "lightcoral",
+    "lightcyan", "lightgoldenrodyellow", "lightgray", "lightgreen", "lightgrey", "lightpink",
+    "lightsalmon", "lightseagreen", "lightskyblue", "lightslategray", "lightslategrey",
+    "lightsteelblue", "lightyellow", "lime", "limegreen", "linen", "magenta", "maroon",
+    "mediumaquamarine", "mediumblue", "mediumorchid", "mediumpurple", "mediumseagreen",
+    "mediumslateblue", "mediumspringgreen", "mediumturquoise", "mediumvioletred", "midnightblue",
+    "mintcream", "mistyrose", "moccasin", "navajowhite", "navy", "oldlace", "olive", "olivedrab",
+    "orange", "orangered", "orchid", "palegoldenrod", "palegreen", "paleturquoise", "palevioletred",
+    "papayawhip", "peachpuff", "peru", "pink", "plum", "powderblue", "purple", "rebeccapurple",
+    "red", "rosybrown", "royalblue", "saddlebrown", "salmon", "sandybrown", "seagreen", "seashell",
+    "sienna", "silver", "skyblue", "slateblue", "slategray", "slategrey", "snow", "springgreen",
+    "steelblue", "tan", "teal", "thistle", "tomato", "transparent", "turquoise", "violet", "wheat",
+    "white", "whitesmoke", "yellow", "yellowgreen",
+];
+
+/// Checks whether the color is valid CSS color syntax, so it can't break out of attributes it's
+/// written into. Accepts the named colors of CSS like `red`, hex values like `#f00` or `#ff000080`,
+/// and color functions with numeric arguments like `rgb(255, 0, 0)` or `hsl(0 100% 50% / 0.5)`.
+pub(crate) fn is_css_color(color: &str) -> bool {
+    if let Some(hex) = color.strip_prefix('#') {
+        return matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+
+    if let Some((name, args)) = color.split_once('(') {
+        let Some(args) = args.strip_suffix(')') else {
+            return false;
+        };
+
+        return matches!(
+            name.to_ascii_lowercase().as_str(),
+            "rgb" | "rgba" | "hsl" | "hsla" | "hwb" | "lab" | "lch" | "oklab" | "oklch"
+        ) && args
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '%' | '/' | '+' | '-' | ' '));
+    }
+
+    // CSS color names are case-insensitive, i.e. `RebeccaPurple`
+    NAMED_COLORS
+        .binary_search(&color.to_ascii_lowercase().as_str())
+        .is_ok()
+}
+
+/// Checks whether the character is a control character that is not valid in XML and HTML. Tabs
+/// and line breaks are valid.
+pub(crate) fn is_invalid_control(c: char) -> bool {
+    c.is_control() && !matches!(c, '\t' | '\n' | '\r')
+}
+
+/// Replaces the leading and trailing spaces of a text with non-breaking spaces, so they're not
+/// collapsed by MathMl, HTML and SVG renderers.
+pub(crate) fn keep_spaces(text: &str) -> String {
+    let trimmed = text.trim_matches(' ');
+
+    if trimmed.len() == text.len() {
+        return text.to_string();
+    }
+
+    let leading = text.len() - text.trim_start_matches(' ').len();
+    let trailing = text.len() - text.trim_end_matches(' ').len();
+
+    // a text of spaces only has them counted as both leading and trailing
+    let trailing = trailing.min(text.len() - leading);
+
+    let mut out = "\u{a0}".repeat(leading);
+    out.push_str(trimmed);
+    out.push_str(&"\u{a0}".repeat(trailing));
+    out
+}
+
+/// Escapes the characters that have special meaning in HTML and XML, and strips the control
+/// characters that are not valid in them.
+pub(crate) fn escape_into(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            c if is_invalid_control(c) => {}
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
@@ -0,0 +1,555 @@
+//! Rendering of the abstract syntax tree into Office Math Markup Language (OMML), the native
+//! equation format of Microsoft Word documents.
+
+use crate::{
+    lexer::keywords::{functions::Function, operators::Operator, others::Other},
+    parser::Matrix,
+    Binary, BinaryKind, Expression, GroupingExpr, SimpleExpr, Unary, UnaryKind, Var, VarKind,
+};
+
+const MATH_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/math";
+const WORD_NS: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+
+/// Properties of the runs (`m:r` elements) that are currently being written.
+#[derive(Debug, Default, Clone)]
+struct RunProps {
+    /// Value of the `m:scr` property, i.e. `double-struck`.
+    script: Option<&'static str>,
+    bold: bool,
+
+    /// Color of the run as a hex value without the leading `#`.
+    color: Option<String>,
+}
+
+/// Renders [`Expression`]s into an `m:oMath` element.
+#[derive(Debug, Default)]
+pub(crate) struct Omml {
+    out: String,
+    props: RunProps,
+}
+
+impl Omml {
+    /// Renders all the given expressions into a single `m:oMath` element.
+    pub(crate) fn render<'e, I>(exprs: I) -> String
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        let exprs: Vec<_> = exprs.into_iter().collect();
+        let mut omml = Omml::default();
+
+        omml.out.push_str("<m:oMath xmlns:m=\"");
+        omml.out.push_str(MATH_NS);
+        omml.out.push_str("\" xmlns:w=\"");
+        omml.out.push_str(WORD_NS);
+        omml.out.push_str("\">");
+        omml.exprs(&exprs);
+        omml.out.push_str("</m:oMath>");
+
+        omml.out
+    }
+
+    /// Writes an element with the given tag, with the content written by `content`.
+    fn elem(&mut self, tag: &str, content: impl FnOnce(&mut Self)) {
+        self.out.push('<');
+        self.out.push_str(tag);
+        self.out.push('>');
+
+        content(self);
+
+        self.out.push_str("</");
+        self.out.push_str(tag);
+        self.out.push('>');
+    }
+
+    /// Writes an empty element with the given tag and `m:val` attribute.
+    fn val(&mut self, tag: &str, val: &str) {
+        self.out.push('<');
+        self.out.push_str(tag);
+        self.out.push_str(" m:val=\"");
+        escape_into(&mut self.out, val);
+        self.out.push_str("\"/>");
+    }
+
+    fn exprs(&mut self, exprs: &[&Expression]) {
+        let mut iter = exprs.iter().peekable();
+
+        while let Some(expr) = iter.next() {
+            match nary_chr(expr) {
+                Some(chr) => {
+                    // the following expression is the operand of the n-ary operator
+                    let body = iter.next_if(|e| is_nary_operand(e));
+                    self.nary(chr, expr, body.copied());
+                }
+                None => self.expr(expr),
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &Expression) {
+        if let Some(matrix) = expr.as_matrix() {
+            return self.matrix(&matrix);
+        }
+
+        if let Some(chr) = nary_chr(expr) {
+            return self.nary(chr, expr, None);
+        }
+
+        if expr.interm.is_underover() || is_brace(&expr.interm) {
+            return self.limits(expr);
+        }
+
+        match (&expr.subscript, &expr.supscript) {
+            (None, None) => self.simple(&expr.interm),
+            (Some(sub), None) => self.elem("m:sSub", |this| {
+                this.elem("m:e", |this| this.simple(&expr.interm));
+                this.elem("m:sub", |this| this.script(sub));
+            }),
+            (None, Some(sup)) => self.elem("m:sSup", |this| {
+                this.elem("m:e", |this| this.simple(&expr.interm));
+                this.elem("m:sup", |this| this.script(sup));
+            }),
+            (Some(sub), Some(sup)) => self.elem("m:sSubSup", |this| {
+                this.elem("m:e", |this| this.simple(&expr.interm));
+                this.elem("m:sub", |this| this.script(sub));
+                this.elem("m:sup", |this| this.script(sup));
+            }),
+        }
+    }
+
+    /// Writes the expression with its subscript placed below and superscript placed above.
+    fn limits(&mut self, expr: &Expression) {
+        let lower = |this: &mut Self| match &expr.subscript {
+            Some(sub) => this.elem("m:limLow", |this| {
+                this.elem("m:e", |this| this.simple(&expr.interm));
+                this.elem("m:lim", |this| this.script(sub));
+            }),
+            None => this.simple(&expr.interm),
+        };
+
+        match &expr.supscript {
+            Some(sup) => self.elem("m:limUpp", |this| {
+                this.elem("m:e", lower);
+                this.elem("m:lim", |this| this.script(sup));
+            }),
+            None => lower(self),
+        }
+    }
+
+    fn nary(&mut self, chr: &str, expr: &Expression, body: Option<&Expression>) {
+        self.elem("m:nary", |this| {
+            this.elem("m:naryPr", |this| {
+                this.val("m:chr", chr);
+
+                let lim_loc = if expr.interm.is_underover() {
+                    "undOvr"
+                } else {
+                    "subSup"
+                };
+                this.val("m:limLoc", lim_loc);
+
+                if expr.subscript.is_none() {
+                    this.val("m:subHide", "1");
+                }
+
+                if expr.supscript.is_none() {
+                    this.val("m:supHide", "1");
+                }
+            });
+
+            this.elem("m:sub", |this| {
+                if let Some(sub) = &expr.subscript {
+                    this.script(sub);
+                }
+            });
+
+            this.elem("m:sup", |this| {
+                if let Some(sup) = &expr.supscript {
+                    this.script(sup);
+                }
+            });
+
+            this.elem("m:e", |this| {
+                if let Some(body) = body {
+                    this.expr(body);
+                }
+            });
+        });
+    }
+
+    fn simple(&mut self, expr: &SimpleExpr) {
+        match expr {
+            SimpleExpr::Var(var) => self.var(var),
+            SimpleExpr::Grouping(grp) => self.grouping(grp),
+            SimpleExpr::Unary(unary) => self.unary(unary),
+            SimpleExpr::Binary(binary) => self.binary(binary),
+            SimpleExpr::Interm(inner) => self.expr(inner),
+        }
+    }
+
+    /// Writes an argument of unary or binary operators. Simple groupings are written without the
+    /// grouping symbols.
+    fn arg(&mut self, expr: &SimpleExpr) {
+        match expr {
+            SimpleExpr::Grouping(grp) if grp.is_simple_grp() => {
+                self.exprs(&grp.expr.iter().collect::<Vec<_>>())
+            }
+            _ => self.simple(expr),
+        }
+    }
+
+    /// Writes a subscript or superscript. Groupings are written without the grouping symbols.
+    fn script(&mut self, expr: &SimpleExpr) {
+        match expr {
+            SimpleExpr::Grouping(grp) => self.exprs(&grp.expr.iter().collect::<Vec<_>>()),
+            _ => self.simple(expr),
+        }
+    }
+
+    /// Writes a run with the given text. Upright runs are used for function names and text.
+    fn run(&mut self, text: &str, upright: bool) {
+        self.elem("m:r", |this| {
+            let style = match (this.props.bold, upright) {
+                (true, _) => Some("b"),
+                (false, true) => Some("p"),
+                (false, false) => None,
+            };
+
+            if this.props.script.is_some() || style.is_some() {
+                this.elem("m:rPr", |this| {
+                    if let Some(script) = this.props.script {
+                        this.val("m:scr", script);
+                    }
+
+                    if let Some(style) = style {
+                        this.val("m:sty", style);
+                    }
+                });
+            }
+
+            if let Some(color) = this.props.color.clone() {
+                this.out.push_str("<w:rPr><w:color w:val=\"");
+                this.out.push_str(&color);
+                this.out.push_str("\"/></w:rPr>");
+            }
+
+            if text.starts_with(char::is_whitespace) || text.ends_with(char::is_whitespace) {
+                this.out.push_str("<m:t xml:space=\"preserve\">");
+            } else {
+                this.out.push_str("<m:t>");
+            }
+
+            escape_into(&mut this.out, text);
+            this.out.push_str("</m:t>");
+        });
+    }
+
+    fn var(&mut self, var: &Var) {
+        match &var.kind {
+            VarKind::Function(func @ (Function::F | Function::G)) => self.run(func.as_ref(), false),
+            VarKind::Function(func) => self.run(func.as_ref(), true),
+            VarKind::Number(num) => self.run(num, false),
+            VarKind::Greek(greek) => self.run(greek.as_unicode(), false),
+            VarKind::Variable(var) => self.run(var, false),
+            VarKind::Arrow(arrow) => self.run(arrow.as_unicode(), false),
+            VarKind::Relation(rel) => self.run(rel.as_unicode(), false),
+            VarKind::Logical(log) => self.run(log.as_unicode(), false),
+            VarKind::Operator(op) => self.run(op.as_unicode(), false),
+            VarKind::Other(other) => self.run(other.as_unicode(), false),
+            VarKind::Text(text) => self.run(text, true),
+            VarKind::UnknownOperator(op) if op.is_empty() => {}
+            VarKind::UnknownOperator(op) => self.run(op, false),
+        }
+    }
+
+    /// Writes a delimiter (`m:d`) element. Expressions separated by commas are written into
+    /// separate `m:e` elements.
+    fn delimited(&mut self, beg: &str, end: &str, exprs: &[&Expression]) {
+        self.elem("m:d", |this| {
+            this.elem("m:dPr", |this| {
+                this.val("m:begChr", beg);
+                this.val("m:sepChr", ",");
+                this.val("m:endChr", end);
+            });
+
+            for part in exprs.split(|e| e.is_comma()) {
+                this.elem("m:e", |this| this.exprs(part));
+            }
+        });
+    }
+
+    fn grouping(&mut self, grp: &GroupingExpr) {
+        let exprs: Vec<_> = grp.expr.iter().collect();
+
+        self.delimited(
+            grp.left_grouping.as_unicode(true),
+            grp.right_grouping.as_unicode(false),
+            &exprs,
+        );
+    }
+
+    fn unary(&mut self, unary: &Unary) {
+        let accent = match unary.kind {
+            UnaryKind::Hat => Some("\u{0302}"),
+            UnaryKind::Overline => Some("\u{0305}"),
+            UnaryKind::Vector => Some("\u{20D7}"),
+            UnaryKind::Tilde => Some("\u{0303}"),
+            UnaryKind::Dot => Some("\u{0307}"),
+            UnaryKind::DoubleDot => Some("\u{0308}"),
+            _ => None,
+        };
+
+        if let Some(accent) = accent {
+            return self.elem("m:acc", |this| {
+                this.elem("m:accPr", |this| this.val("m:chr", accent));
+                this.elem("m:e", |this| this.arg(&unary.expr));
+            });
+        }
+
+        let delimiters = match unary.kind {
+            UnaryKind::Absolute => Some(("|", "|")),
+            UnaryKind::Floor => Some(("⌊", "⌋")),
+            UnaryKind::Ceiling => Some(("⌈", "⌉")),
+            UnaryKind::Norm => Some(("‖", "‖")),
+            _ => None,
+        };
+
+        if let Some((beg, end)) = delimiters {
+            return self.elem("m:d", |this| {
+                this.elem("m:dPr", |this| {
+                    this.val("m:begChr", beg);
+                    this.val("m:endChr", end);
+                });
+                this.elem("m:e", |this| this.arg(&unary.expr));
+            });
+        }
+
+        let script = match unary.kind {
+            UnaryKind::BlackboardBold => Some("double-struck"),
+            UnaryKind::Calligraphic => Some("script"),
+            UnaryKind::Typewriter => Some("monospace"),
+            UnaryKind::Gothic => Some("fraktur"),
+            UnaryKind::SansSerif => Some("sans-serif"),
+            _ => None,
+        };
+
+        if script.is_some() || unary.kind == UnaryKind::Bold {
+            let props = self.props.clone();
+
+            match script {
+                Some(script) => self.props.script = Some(script),
+                None => self.props.bold = true,
+            }
+
+            self.arg(&unary.expr);
+            self.props = props;
+            return;
+        }
+
+        match unary.kind {
+            UnaryKind::Underline => self.elem("m:bar", |this| {
+                this.elem("m:barPr", |this| this.val("m:pos", "bot"));
+                this.elem("m:e", |this| this.arg(&unary.expr));
+            }),
+            UnaryKind::Underbrace | UnaryKind::Overbrace => self.elem("m:groupChr", |this| {
+                this.elem("m:groupChrPr", |this| {
+                    if unary.kind == UnaryKind::Underbrace {
+                        this.val("m:chr", "⏟");
+                        this.val("m:pos", "bot");
+                        this.val("m:vertJc", "top");
+                    } else {
+                        this.val("m:chr", "⏞");
+                        this.val("m:pos", "top");
+                        this.val("m:vertJc", "bot");
+                    }
+                });
+                this.elem("m:e", |this| this.arg(&unary.expr));
+            }),
+            UnaryKind::Cancel => self.elem("m:borderBox", |this| {
+                this.elem("m:borderBoxPr", |this| {
+                    for hide in ["m:hideTop", "m:hideBot", "m:hideLeft", "m:hideRight"] {
+                        this.val(hide, "1");
+                    }
+                    this.val("m:strikeBLTR", "1");
+                });
+                this.elem("m:e", |this| this.arg(&unary.expr));
+            }),
+            UnaryKind::SquareRoot => self.elem("m:rad", |this| {
+                this.elem("m:radPr", |this| this.val("m:degHide", "1"));
+                this.elem("m:deg", |_| {});
+                this.elem("m:e", |this| this.arg(&unary.expr));
+            }),
+            _ => self.arg(&unary.expr),
+        }
+    }
+
+    fn binary(&mut self, binary: &Binary) {
+        match binary.kind {
+            BinaryKind::Fraction => self.elem("m:f", |this| {
+                this.elem("m:num", |this| this.arg(&binary.expr_1));
+                this.elem("m:den", |this| this.arg(&binary.expr_2));
+            }),
+            BinaryKind::Root => self.elem("m:rad", |this| {
+                this.elem("m:deg", |this| this.arg(&binary.expr_1));
+                this.elem("m:e", |this| this.arg(&binary.expr_2));
+            }),
+            BinaryKind::Overset => self.elem("m:limUpp", |this| {
+                this.elem("m:e", |this| this.arg(&binary.expr_2));
+                this.elem("m:lim", |this| this.arg(&binary.expr_1));
+            }),
+            BinaryKind::Underset => self.elem("m:limLow", |this| {
+                this.elem("m:e", |this| this.arg(&binary.expr_2));
+                this.elem("m:lim", |this| this.arg(&binary.expr_1));
+            }),
+            BinaryKind::Color => {
+                let props = self.props.clone();
+
+                if let SimpleExpr::Var(Var {
+                    kind: VarKind::Text(color),
+                    ..
+                }) = &*binary.expr_1
+                {
+                    self.props.color = hex_color(color).or(props.color.clone());
+                }
+
+                self.arg(&binary.expr_2);
+                self.props = props;
+            }
+        }
+    }
+
+    fn matrix(&mut self, matrix: &Matrix<'_>) {
+        let columns = matrix.num_of_columns();
+
+        self.elem("m:d", |this| {
+            this.elem("m:dPr", |this| {
+                this.val("m:begChr", matrix.left_grouping.as_unicode(true));
+                this.val("m:endChr", matrix.right_grouping.as_unicode(false));
+            });
+
+            this.elem("m:e", |this| {
+                this.elem("m:m", |this| {
+                    for row in &matrix.rows {
+                        this.elem("m:mr", |this| {
+                            for (i, cell) in row.iter().enumerate() {
+                                this.elem("m:e", |this| this.exprs(cell));
+
+                                // OMML matrices have no column lines, so they are written as
+                                // separate columns containing a vertical bar
+                                let has_line = matrix.column_lines.get(i).copied();
+                                if has_line.unwrap_or(false) && i + 1 < columns {
+                                    this.elem("m:e", |this| this.run("|", false));
+                                }
+                            }
+                        });
+                    }
+                });
+            });
+        });
+    }
+}
+
+/// Returns the character of the n-ary operator, if the expression is one.
+fn nary_chr(expr: &Expression) -> Option<&'static str> {
+    let SimpleExpr::Var(ref var) = expr.interm else {
+        return None;
+    };
+
+    match var.kind {
+        VarKind::Operator(
+            op @ (Operator::Sum
+            | Operator::Prod
+            | Operator::BigWedge
+            | Operator::BigCap
+            | Operator::BigCup),
+        ) => Some(op.as_unicode()),
+        VarKind::Other(other @ (Other::Integral | Other::OIntegral)) => Some(other.as_unicode()),
+        _ => None,
+    }
+}
+
+/// Checks whether the expression can be the operand of a preceding n-ary operator.
+fn is_nary_operand(expr: &Expression) -> bool {
+    let SimpleExpr::Var(ref var) = expr.interm else {
+        return true;
+    };
+
+    !matches!(
+        var.kind,
+        VarKind::Relation(_)
+            | VarKind::Arrow(_)
+            | VarKind::Logical(_)
+            | VarKind::Operator(_)
+            | VarKind::Other(Other::Comma)
+    )
+}
+
+/// Checks whether the expression is an under- or overbrace.
+fn is_brace(expr: &SimpleExpr) -> bool {
+    matches!(
+        expr,
+        SimpleExpr::Unary(Unary {
+            kind: UnaryKind::Underbrace | UnaryKind::Overbrace,
+            ..
+        })
+    )
+}
+
+/// Converts the color given in AsciiMath into a hex color value as used by Word. Returns `None`
+/// for unknown colors.
+fn hex_color(color: &str) -> Option<String> {
+    const NAMED: &[(&str, &str)] = &[
+        ("black", "000000"),
+        ("silver", "C0C0C0"),
+        ("gray", "808080"),
+        ("grey", "808080"),
+        ("white", "FFFFFF"),
+        ("maroon", "800000"),
+        ("red", "FF0000"),
+        ("purple", "800080"),
+        ("fuchsia", "FF00FF"),
+        ("magenta", "FF00FF"),
+        ("green", "008000"),
+        ("lime", "00FF00"),
+        ("olive", "808000"),
+        ("yellow", "FFFF00"),
+        ("navy", "000080"),
+        ("blue", "0000FF"),
+        ("teal", "008080"),
+        ("aqua", "00FFFF"),
+        ("cyan", "00FFFF"),
+        ("orange", "FFA500"),
+    ];
+
+    let color = color.trim();
+
+    if let Some(hex) = color.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        return match hex.len() {
+            3 => Some(hex.chars().flat_map(|c| [c, c]).collect::<String>()),
+            6 => Some(hex.to_string()),
+            _ => None,
+        }
+        .map(|hex| hex.to_ascii_uppercase());
+    }
+
+    NAMED
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(color))
+        .map(|(_, hex)| hex.to_string())
+}
+
+/// Escapes the characters that have special meaning in XML.
+fn escape_into(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+}
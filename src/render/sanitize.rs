@@ -0,0 +1,88 @@
+//! Sanitization of the abstract syntax tree for the safe MathMl output, see
+//! [`render_mathml_safe`](crate::render_mathml_safe).
+//!
+//! The MathMl writer copies the content of texts, numbers, identifiers and operators, as well as
+//! colors, into the output as they are. Sanitization escapes the content in place instead, strips
+//! the control characters and removes the colors that are not valid CSS colors.
+
+use alloc::borrow::Cow;
+
+use crate::{
+    lexer::keywords::relations::Relation, Binary, BinaryKind, Expression, SimpleExpr, Var, VarKind,
+};
+
+use super::{escape_into, is_css_color};
+
+/// Sanitizes the expression, and all its nested expressions.
+pub(crate) fn sanitize(expr: &mut Expression) {
+    let scripts = [expr.subscript.as_mut(), expr.supscript.as_mut()];
+
+    for simple in scripts.into_iter().flatten() {
+        sanitize_simple(simple);
+    }
+
+    sanitize_simple(&mut expr.interm);
+}
+
+fn sanitize_simple(expr: &mut SimpleExpr) {
+    match expr {
+        SimpleExpr::Var(var) => sanitize_var(var),
+        SimpleExpr::Grouping(grp) => grp.expr.iter_mut().for_each(sanitize),
+        SimpleExpr::Unary(unary) => sanitize_simple(&mut unary.expr),
+        SimpleExpr::Binary(Binary {
+            kind: BinaryKind::Color,
+            expr_1,
+            expr_2,
+            ..
+        }) => {
+            if let SimpleExpr::Var(Var {
+                kind: VarKind::Text(color),
+                ..
+            }) = &mut **expr_1
+            {
+                let trimmed = color.trim();
+
+                // an empty color is not written into the output
+                *color = match is_css_color(trimmed) {
+                    true => trimmed.to_string().into(),
+                    false => Cow::Borrowed(""),
+                };
+            }
+
+            sanitize_simple(expr_2);
+        }
+        SimpleExpr::Binary(binary) => {
+            sanitize_simple(&mut binary.expr_1);
+            sanitize_simple(&mut binary.expr_2);
+        }
+        SimpleExpr::Interm(expr) => sanitize(expr),
+    }
+}
+
+fn sanitize_var(var: &mut Var) {
+    // the only keywords written with `<` or `>`, as the operators `<` and `m<`
+    let escaped = match var.kind {
+        VarKind::Relation(Relation::LessThan) => Some("&lt;"),
+        VarKind::Relation(Relation::GreaterThan) => Some("&gt;"),
+        VarKind::Relation(Relation::MuchLessThan) => Some("m&lt;"),
+        VarKind::Relation(Relation::MuchGreaterThan) => Some("m&gt;"),
+        _ => None,
+    };
+
+    if let Some(escaped) = escaped {
+        var.kind = VarKind::UnknownOperator(Cow::Borrowed(escaped));
+        return;
+    }
+
+    let content = match &mut var.kind {
+        VarKind::Number(content)
+        | VarKind::Variable(content)
+        | VarKind::UnknownOperator(content)
+        | VarKind::Text(content) => content,
+        _ => return,
+    };
+
+    let mut escaped = String::with_capacity(content.len());
+    escape_into(&mut escaped, content);
+    *content = escaped.into();
+}
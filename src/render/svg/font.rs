@@ -0,0 +1,562 @@
+//! Built-in stroke font of the SVG output. Glyphs are drawn as stroked paths instead of text, so
+//! the images look the same in every viewer without installed fonts, and the layout is measured
+//! with the outlines that are actually drawn.
+//!
+//! The font covers ASCII, the Greek letters and the symbols of the AsciiMath keywords. Letters and
+//! digits of the "Mathematical Alphanumeric Symbols" block are drawn from the plain ones: bold and
+//! fraktur with a thicker stroke, italic and script slanted, double-struck with a doubled stroke
+//! and monospace with a fixed advance. Other characters are drawn as a box.
+
+use super::{
+    metrics::{AXIS_HEIGHT, RULE_THICKNESS},
+    Segment, Shape,
+};
+
+/// Font units per em.
+const UNITS_PER_EM: f32 = 1000.0;
+
+/// Stroke of regular glyphs, as thick as fraction bars and other rules.
+const STROKE: f32 = RULE_THICKNESS;
+
+/// Stroke of bold glyphs.
+const BOLD_STROKE: f32 = 1.6 * RULE_THICKNESS;
+
+/// Horizontal shift of slanted glyphs per unit of height above the math axis.
+const SLANT: f32 = 0.2;
+
+/// Height of the math axis in font units, slanted glyphs are slanted around it.
+const AXIS: f32 = AXIS_HEIGHT * UNITS_PER_EM;
+
+/// Distance between the strokes of double-struck glyphs, in font units.
+const DOUBLE_OFFSET: f32 = 60.0;
+
+/// Advance width of monospace glyphs, in font units.
+const MONO_ADVANCE: f32 = 600.0;
+
+/// Glyph drawn for characters that are not covered by the font.
+const NOTDEF: (u16, &str) = (500, "M100 0L400 0L400 680L100 680L100 0");
+
+/// Dimensions of laid out text.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct Glyph {
+    pub(super) width: f32,
+    pub(super) height: f32,
+    pub(super) depth: f32,
+}
+
+/// Style of a glyph that is drawn from a plain letter or digit.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Style {
+    bold: bool,
+    slanted: bool,
+    double: bool,
+    mono: bool,
+}
+
+impl Style {
+    const PLAIN: Style = Style::new(false, false, false, false);
+    const BOLD: Style = Style::new(true, false, false, false);
+    const SLANTED: Style = Style::new(false, true, false, false);
+    const BOLD_SLANTED: Style = Style::new(true, true, false, false);
+    const DOUBLE: Style = Style::new(false, false, true, false);
+    const MONO: Style = Style::new(false, false, false, true);
+
+    const fn new(bold: bool, slanted: bool, double: bool, mono: bool) -> Self {
+        Self {
+            bold,
+            slanted,
+            double,
+            mono,
+        }
+    }
+}
+
+/// Styles of the alphabets in the "Mathematical Alphanumeric Symbols" block, starting at U+1D400:
+/// bold, italic, bold italic, script, bold script, fraktur, double-struck, bold fraktur, sans-serif,
+/// sans-serif bold, sans-serif italic, sans-serif bold italic and monospace.
+const LETTER_STYLES: [Style; 13] = [
+    Style::BOLD,
+    Style::SLANTED,
+    Style::BOLD_SLANTED,
+    Style::SLANTED,
+    Style::BOLD_SLANTED,
+    Style::BOLD,
+    Style::DOUBLE,
+    Style::BOLD,
+    Style::PLAIN,
+    Style::BOLD,
+    Style::SLANTED,
+    Style::BOLD_SLANTED,
+    Style::MONO,
+];
+
+/// Styles of the digits in the "Mathematical Alphanumeric Symbols" block, starting at U+1D7CE:
+/// bold, double-struck, sans-serif, sans-serif bold and monospace.
+const DIGIT_STYLES: [Style; 5] = [
+    Style::BOLD,
+    Style::DOUBLE,
+    Style::PLAIN,
+    Style::BOLD,
+    Style::MONO,
+];
+
+/// Returns the plain character the glyph is drawn from, and the style it is drawn with.
+fn decompose(c: char) -> (char, Style) {
+    let letterlike = match c {
+        'ℬ' | 'ℰ' | 'ℱ' | 'ℋ' | 'ℐ' | 'ℒ' | 'ℳ' | 'ℛ' | 'ℯ' | 'ℊ' | 'ℴ' | 'ℎ' => {
+            Some(Style::SLANTED)
+        }
+        'ℭ' | 'ℌ' | 'ℑ' | 'ℜ' | 'ℨ' => Some(Style::BOLD),
+        'ℂ' | 'ℍ' | 'ℕ' | 'ℙ' | 'ℚ' | 'ℝ' | 'ℤ' => Some(Style::DOUBLE),
+        _ => None,
+    };
+
+    if let Some(style) = letterlike {
+        let plain = match c {
+            'ℬ' => 'B',
+            'ℰ' => 'E',
+            'ℱ' => 'F',
+            'ℋ' | 'ℌ' | 'ℍ' => 'H',
+            'ℐ' | 'ℑ' => 'I',
+            'ℒ' => 'L',
+            'ℳ' => 'M',
+            'ℛ' | 'ℜ' | 'ℝ' => 'R',
+            'ℯ' => 'e',
+            'ℊ' => 'g',
+            'ℴ' => 'o',
+            'ℎ' => 'h',
+            'ℭ' | 'ℂ' => 'C',
+            'ℨ' | 'ℤ' => 'Z',
+            'ℕ' => 'N',
+            'ℙ' => 'P',
+            _ => 'Q',
+        };
+
+        return (plain, style);
+    }
+
+    let code = c as u32;
+
+    let (plain, style) = match code {
+        0x1D400..=0x1D6A3 => {
+            let (alphabet, index) = ((code - 0x1D400) / 52, (code - 0x1D400) % 52);
+            let plain = match index {
+                0..=25 => 'A' as u32 + index,
+                _ => 'a' as u32 + index - 26,
+            };
+
+            (plain, LETTER_STYLES[alphabet as usize])
+        }
+        0x1D7CE..=0x1D7FF => {
+            let (alphabet, index) = ((code - 0x1D7CE) / 10, (code - 0x1D7CE) % 10);
+            ('0' as u32 + index, DIGIT_STYLES[alphabet as usize])
+        }
+        _ => return (c, Style::PLAIN),
+    };
+
+    (char::from_u32(plain).unwrap_or(c), style)
+}
+
+/// Parses the outline of a glyph into path segments.
+fn segments(path: &'static str) -> impl Iterator<Item = Segment> {
+    let mut rest = path;
+
+    core::iter::from_fn(move || {
+        let command = rest.chars().next()?;
+        let end = rest[1..]
+            .find(|c: char| c.is_ascii_uppercase())
+            .map_or(rest.len(), |i| i + 1);
+
+        let mut numbers = rest[1..end].split(' ').map(|n| n.parse().unwrap_or(0.0));
+        let mut next = || numbers.next().unwrap_or(0.0);
+        rest = &rest[end..];
+
+        let segment = match command {
+            'M' => Segment::Move(next(), next()),
+            'L' => Segment::Line(next(), next()),
+            _ => Segment::Quad(next(), next(), next(), next()),
+        };
+
+        Some(segment)
+    })
+}
+
+/// Lays out the text at the given size and returns its dimensions and its strokes. Slanted text
+/// is drawn like italic letters. The strokes are positioned relative to the left end of the
+/// baseline, with the `y` axis pointing down.
+pub(super) fn outline(text: &str, size: f32, slanted: bool) -> (Glyph, Vec<Shape>) {
+    let scale = size / UNITS_PER_EM;
+
+    let mut glyph = Glyph::default();
+    let mut regular = Vec::new();
+    let mut bold = Vec::new();
+
+    // horizontal position of the current glyph, in font units
+    let mut pen = 0.0;
+
+    for c in text.chars() {
+        let (plain, mut style) = decompose(c);
+        style.slanted |= slanted;
+
+        let (advance, path) = GLYPHS
+            .binary_search_by_key(&plain, |&(c, ..)| c)
+            .map_or(NOTDEF, |i| (GLYPHS[i].1, GLYPHS[i].2));
+
+        let mut advance = f32::from(advance);
+
+        // monospace glyphs are narrowed to the fixed advance if needed and centered in it
+        let (stretch, mut offset) = if style.mono {
+            let stretch = (MONO_ADVANCE / advance).min(1.0);
+            let offset = (MONO_ADVANCE - advance * stretch) / 2.0;
+            advance = MONO_ADVANCE;
+            (stretch, offset)
+        } else {
+            (1.0, 0.0)
+        };
+
+        let copies = if style.double {
+            advance += DOUBLE_OFFSET;
+            2
+        } else {
+            1
+        };
+
+        let stroke = if style.bold { BOLD_STROKE } else { STROKE };
+        let strokes = if style.bold { &mut bold } else { &mut regular };
+
+        for _ in 0..copies {
+            let x0 = pen + offset;
+            let point = |x: f32, y: f32| {
+                let slant = if style.slanted {
+                    SLANT * (y - AXIS)
+                } else {
+                    0.0
+                };
+                ((x0 + x * stretch + slant) * scale, -y * scale)
+            };
+
+            for segment in segments(path) {
+                let segment = match segment {
+                    Segment::Move(x, y) => {
+                        let (x, y) = point(x, y);
+                        Segment::Move(x, y)
+                    }
+                    Segment::Line(x, y) => {
+                        let (x, y) = point(x, y);
+                        Segment::Line(x, y)
+                    }
+                    Segment::Quad(cx, cy, x, y) => {
+                        let (cx, cy) = point(cx, cy);
+                        let (x, y) = point(x, y);
+                        Segment::Quad(cx, cy, x, y)
+                    }
+                };
+
+                for (_, y) in segment.points() {
+                    glyph.height = glyph.height.max(-y + stroke * size / 2.0);
+                    glyph.depth = glyph.depth.max(y + stroke * size / 2.0);
+                }
+
+                strokes.push(segment);
+            }
+
+            offset += DOUBLE_OFFSET;
+        }
+
+        pen += advance;
+    }
+
+    glyph.width = pen * scale;
+
+    let shapes = [(regular, STROKE), (bold, BOLD_STROKE)]
+        .into_iter()
+        .filter(|(segments, _)| !segments.is_empty())
+        .map(|(segments, stroke)| Shape::Path {
+            segments,
+            stroke: stroke * size,
+        })
+        .collect();
+
+    (glyph, shapes)
+}
+
+/// Glyphs sorted by their character, with their advance width and their outline in font units.
+/// The outlines are paths of absolute `M`, `L` and `Q` commands, with the `y` axis pointing up and
+/// the origin at the left end of the baseline.
+#[rustfmt::skip]
+const GLYPHS: &[(char, u16, &str)] = &[
+    (' ', 333, ""),
+    ('!', 280, "M140 680L140 200M140 65Q132 65 126 59Q120 53 120 45Q120 37 126 31Q132 25 140 25Q148 25 154 31Q160 37 160 45Q160 53 154 59Q148 65 140 65"),
+    ('"', 400, "M130 680L130 520M270 680L270 520"),
+    ('#', 760, "M280 0L330 680M480 0L530 680M130 220L650 220M150 460L670 460"),
+    ('$', 560, "M470 590Q420 685 285 685Q100 685 100 520Q100 380 280 345Q480 305 480 165Q480 -5 280 -5Q130 -5 80 100M285 760L285 -80"),
+    ('%', 800, "M190 680Q144 680 112 633Q80 586 80 520Q80 454 112 407Q144 360 190 360Q236 360 268 407Q300 454 300 520Q300 586 268 633Q236 680 190 680M610 320Q564 320 532 273Q500 226 500 160Q500 94 532 47Q564 0 610 0Q656 0 688 47Q720 94 720 160Q720 226 688 273Q656 320 610 320M620 680L180 0"),
+    ('&', 760, "M680 0L230 470Q150 560 200 640Q260 700 330 660Q400 610 350 520L110 250Q40 140 110 60Q200 -30 360 30Q520 100 620 280"),
+    ('\'', 240, "M120 680L120 520"),
+    ('(', 390, "M300 750Q80 500 80 250Q80 0 300 -250"),
+    (')', 390, "M90 750Q310 500 310 250Q310 0 90 -250"),
+    ('*', 500, "M250 620L250 340M130 550L370 410M370 550L130 410"),
+    ('+', 780, "M390 20L390 480M160 250L620 250"),
+    (',', 280, "M150 30L150 10Q150 -80 90 -150"),
+    ('-', 340, "M60 250L280 250"),
+    ('.', 280, "M140 65Q132 65 126 59Q120 53 120 45Q120 37 126 31Q132 25 140 25Q148 25 154 31Q160 37 160 45Q160 53 154 59Q148 65 140 65"),
+    ('/', 500, "M420 750L80 -250"),
+    ('0', 500, "M250 660Q182 660 133 563Q85 467 85 330Q85 193 133 97Q182 0 250 0Q318 0 367 97Q415 193 415 330Q415 467 367 563Q318 660 250 660"),
+    ('1', 500, "M130 540L260 660L260 0M130 0L390 0"),
+    ('2', 500, "M100 551Q119 606 168 636Q216 667 272 658Q328 650 367 608Q406 565 410 507Q414 448 381 400L90 0L420 0"),
+    ('3', 500, "M115 584Q142 632 194 651Q245 670 296 651Q348 632 375 584Q402 535 393 480Q383 426 341 390Q300 354 245 354M250 354Q312 354 359 313Q407 271 417 208Q428 144 397 89Q366 33 308 11Q250 -11 192 11Q134 33 103 89"),
+    ('4', 500, "M330 0L330 660L60 200L440 200"),
+    ('5', 500, "M410 660L135 660L115 370L138 367Q181 422 241 429Q300 436 350 391Q399 346 414 271Q430 195 405 124Q379 53 325 20Q270 -13 212 7Q154 28 120 92"),
+    ('6', 500, "M390 610Q300 680 200 640Q90 580 90 230M255 420Q187 420 138 358Q90 297 90 210Q90 123 138 62Q187 0 255 0Q323 0 372 62Q420 123 420 210Q420 297 372 358Q323 420 255 420"),
+    ('7', 500, "M80 660L420 660L190 0"),
+    ('8', 500, "M250 660Q192 660 151 615Q110 570 110 507Q110 444 151 399Q192 354 250 354Q308 354 349 399Q390 444 390 507Q390 570 349 615Q308 660 250 660M250 354Q182 354 133 302Q85 250 85 177Q85 104 133 52Q182 0 250 0Q318 0 367 52Q415 104 415 177Q415 250 367 302Q318 354 250 354"),
+    ('9', 500, "M245 660Q177 660 128 598Q80 537 80 450Q80 363 128 302Q177 240 245 240Q313 240 362 302Q410 363 410 450Q410 537 362 598Q313 660 245 660M410 450Q410 140 330 60Q230 -30 110 50"),
+    (':', 280, "M140 65Q132 65 126 59Q120 53 120 45Q120 37 126 31Q132 25 140 25Q148 25 154 31Q160 37 160 45Q160 53 154 59Q148 65 140 65M140 445Q132 445 126 439Q120 433 120 425Q120 417 126 411Q132 405 140 405Q148 405 154 411Q160 417 160 425Q160 433 154 439Q148 445 140 445"),
+    (';', 280, "M140 445Q132 445 126 439Q120 433 120 425Q120 417 126 411Q132 405 140 405Q148 405 154 411Q160 417 160 425Q160 433 154 439Q148 445 140 445M150 30L150 10Q150 -80 90 -150"),
+    ('<', 780, "M620 480L160 250L620 20"),
+    ('=', 780, "M160 340L620 340M160 160L620 160"),
+    ('>', 780, "M160 480L620 250L160 20"),
+    ('?', 470, "M90 560Q110 690 240 690Q390 690 390 540Q390 430 240 360L240 200M240 65Q232 65 226 59Q220 53 220 45Q220 37 226 31Q232 25 240 25Q248 25 254 31Q260 37 260 45Q260 53 254 59Q248 65 240 65"),
+    ('@', 900, "M440 450Q390 450 355 406Q320 362 320 300Q320 238 355 194Q390 150 440 150Q490 150 525 194Q560 238 560 300Q560 362 525 406Q490 450 440 450M560 450L560 200Q560 120 640 120Q780 120 780 330Q780 680 450 680Q120 680 120 300Q120 -80 460 -80Q600 -80 700 0"),
+    ('A', 700, "M60 0L350 680L640 0M160 230L540 230"),
+    ('B', 640, "M100 350L340 350Q520 350 520 515Q520 680 340 680L100 680L100 0L360 0Q560 0 560 175Q560 350 340 350"),
+    ('C', 700, "M592 584Q504 685 380 685Q256 685 168 584Q80 483 80 340Q80 197 168 96Q256 -5 380 -5Q504 -5 592 96"),
+    ('D', 720, "M100 0L100 680L300 680Q620 680 620 340Q620 0 300 0L100 0"),
+    ('E', 600, "M520 680L100 680L100 0L520 0M100 350L440 350"),
+    ('F', 580, "M520 680L100 680L100 0M100 350L430 350"),
+    ('G', 740, "M602 584Q514 685 390 685Q266 685 178 584Q90 483 90 340Q90 197 178 96Q266 -5 390 -5Q514 -5 602 96Q690 197 690 340L450 340"),
+    ('H', 720, "M100 0L100 680M620 0L620 680M100 350L620 350"),
+    ('I', 300, "M150 0L150 680"),
+    ('J', 460, "M360 680L360 180Q360 -5 205 -5Q85 -5 60 120"),
+    ('K', 660, "M100 0L100 680M600 680L100 250M260 390L610 0"),
+    ('L', 560, "M100 680L100 0L500 0"),
+    ('M', 860, "M100 0L100 680L430 120L760 680L760 0"),
+    ('N', 720, "M100 0L100 680L620 0L620 680"),
+    ('O', 780, "M390 685Q266 685 178 584Q90 483 90 340Q90 197 178 96Q266 -5 390 -5Q514 -5 602 96Q690 197 690 340Q690 483 602 584Q514 685 390 685"),
+    ('P', 620, "M100 0L100 680L340 680Q540 680 540 505Q540 330 340 330L100 330"),
+    ('Q', 780, "M390 685Q266 685 178 584Q90 483 90 340Q90 197 178 96Q266 -5 390 -5Q514 -5 602 96Q690 197 690 340Q690 483 602 584Q514 685 390 685M460 150L700 -70"),
+    ('R', 660, "M100 0L100 680L340 680Q540 680 540 505Q540 330 340 330L100 330M330 330L580 0"),
+    ('S', 560, "M470 590Q420 685 285 685Q100 685 100 520Q100 380 280 345Q480 305 480 165Q480 -5 280 -5Q130 -5 80 100"),
+    ('T', 640, "M40 680L600 680M320 680L320 0"),
+    ('U', 720, "M100 680L100 230Q100 -5 360 -5Q620 -5 620 230L620 680"),
+    ('V', 700, "M60 680L350 0L640 680"),
+    ('W', 980, "M60 680L260 0L490 600L720 0L920 680"),
+    ('X', 680, "M70 680L610 0M610 680L70 0"),
+    ('Y', 680, "M60 680L340 330L620 680M340 330L340 0"),
+    ('Z', 640, "M80 680L560 680L80 0L560 0"),
+    ('[', 300, "M250 750L100 750L100 -250L250 -250"),
+    ('\\', 500, "M80 750L420 -250"),
+    (']', 300, "M50 750L200 750L200 -250L50 -250"),
+    ('^', 500, "M100 480L250 680L400 480"),
+    ('_', 500, "M30 -80L470 -80"),
+    ('`', 300, "M100 700L200 580"),
+    ('a', 520, "M245 450Q177 450 128 384Q80 318 80 225Q80 132 128 66Q177 0 245 0Q313 0 362 66Q410 132 410 225Q410 318 362 384Q313 450 245 450M410 450L410 0"),
+    ('b', 540, "M100 720L100 0M290 450Q211 450 156 384Q100 318 100 225Q100 132 156 66Q211 0 290 0Q369 0 424 66Q480 132 480 225Q480 318 424 384Q369 450 290 450"),
+    ('c', 480, "M416 370Q371 432 303 447Q235 461 175 420Q115 379 91 302Q68 225 91 148Q115 71 175 30Q235 -11 303 3Q371 18 416 80"),
+    ('d', 540, "M440 720L440 0M250 450Q171 450 116 384Q60 318 60 225Q60 132 116 66Q171 0 250 0Q329 0 384 66Q440 132 440 225Q440 318 384 384Q329 450 250 450"),
+    ('e', 500, "M90 240L420 240Q420 450 255 450Q90 450 90 225Q90 0 260 0Q360 0 410 70"),
+    ('f', 340, "M310 700Q290 720 250 720Q150 720 150 600L150 0M50 440L290 440"),
+    ('g', 540, "M250 450Q180 450 130 387Q80 324 80 235Q80 146 130 83Q180 20 250 20Q320 20 370 83Q420 146 420 235Q420 324 370 387Q320 450 250 450M420 450L420 -60Q420 -210 250 -210Q130 -210 90 -150"),
+    ('h', 540, "M100 720L100 0M100 300Q100 450 270 450Q440 450 440 300L440 0"),
+    ('i', 240, "M120 0L120 450M120 650Q112 650 106 644Q100 638 100 630Q100 622 106 616Q112 610 120 610Q128 610 134 616Q140 622 140 630Q140 638 134 644Q128 650 120 650"),
+    ('j', 260, "M160 450L160 -80Q160 -210 40 -210M160 650Q152 650 146 644Q140 638 140 630Q140 622 146 616Q152 610 160 610Q168 610 174 616Q180 622 180 630Q180 638 174 644Q168 650 160 650"),
+    ('k', 500, "M100 720L100 0M420 450L100 170M210 265L440 0"),
+    ('l', 240, "M120 720L120 0"),
+    ('m', 820, "M100 450L100 0M100 300Q100 450 255 450Q410 450 410 300L410 0M410 300Q410 450 565 450Q720 450 720 300L720 0"),
+    ('n', 540, "M100 450L100 0M100 300Q100 450 270 450Q440 450 440 300L440 0"),
+    ('o', 540, "M270 450Q195 450 143 384Q90 318 90 225Q90 132 143 66Q195 0 270 0Q345 0 397 66Q450 132 450 225Q450 318 397 384Q345 450 270 450"),
+    ('p', 540, "M100 450L100 -210M290 450Q211 450 156 384Q100 318 100 225Q100 132 156 66Q211 0 290 0Q369 0 424 66Q480 132 480 225Q480 318 424 384Q369 450 290 450"),
+    ('q', 540, "M440 450L440 -210M250 450Q171 450 116 384Q60 318 60 225Q60 132 116 66Q171 0 250 0Q329 0 384 66Q440 132 440 225Q440 318 384 384Q329 450 250 450"),
+    ('r', 380, "M100 450L100 0M100 260Q120 450 340 440"),
+    ('s', 440, "M380 390Q340 450 230 450Q90 450 90 340Q90 250 220 230Q370 205 370 110Q370 0 220 0Q110 0 60 70"),
+    ('t', 360, "M160 640L160 90Q160 0 250 0Q300 0 330 20M50 450L320 450"),
+    ('u', 540, "M100 450L100 150Q100 0 270 0Q440 0 440 150M440 450L440 0"),
+    ('v', 500, "M60 450L250 0L440 450"),
+    ('w', 760, "M50 450L200 0L380 400L560 0L710 450"),
+    ('x', 500, "M70 450L430 0M430 450L70 0"),
+    ('y', 500, "M60 450L250 0M440 450L220 -150Q180 -210 100 -210"),
+    ('z', 480, "M80 450L400 450L80 0L400 0"),
+    ('{', 500, "M400 750Q250 750 250 600L250 400Q250 250 100 250Q250 250 250 100L250 -100Q250 -250 400 -250"),
+    ('|', 280, "M140 750L140 -250"),
+    ('}', 500, "M100 750Q250 750 250 600L250 400Q250 250 400 250Q250 250 250 100L250 -100Q250 -250 100 -250"),
+    ('~', 560, "M80 220Q180 330 280 250Q380 170 480 280"),
+    ('\u{a0}', 333, ""),
+    ('¨', 400, "M120 640Q112 640 106 634Q100 628 100 620Q100 612 106 606Q112 600 120 600Q128 600 134 606Q140 612 140 620Q140 628 134 634Q128 640 120 640M280 640Q272 640 266 634Q260 628 260 620Q260 612 266 606Q272 600 280 600Q288 600 294 606Q300 612 300 620Q300 628 294 634Q288 640 280 640"),
+    ('¬', 680, "M100 330L560 330L560 140"),
+    ('±', 780, "M390 560L390 140M160 350L620 350M160 40L620 40"),
+    ('×', 780, "M220 80L560 420M560 80L220 420"),
+    ('÷', 780, "M160 250L620 250M390 440Q382 440 376 434Q370 428 370 420Q370 412 376 406Q382 400 390 400Q398 400 404 406Q410 412 410 420Q410 428 404 434Q398 440 390 440M390 100Q382 100 376 94Q370 88 370 80Q370 72 376 66Q382 60 390 60Q398 60 404 66Q410 72 410 80Q410 88 404 94Q398 100 390 100"),
+    ('ˆ', 500, "M130 560L250 680L370 560"),
+    ('˙', 300, "M150 640Q142 640 136 634Q130 628 130 620Q130 612 136 606Q142 600 150 600Q158 600 164 606Q170 612 170 620Q170 628 164 634Q158 640 150 640"),
+    ('˜', 500, "M100 600Q175 680 250 620Q325 560 400 640"),
+    ('Γ', 580, "M100 0L100 680L520 680"),
+    ('Δ', 700, "M60 0L350 680L640 0L60 0"),
+    ('Θ', 780, "M390 685Q266 685 178 584Q90 483 90 340Q90 197 178 96Q266 -5 390 -5Q514 -5 602 96Q690 197 690 340Q690 483 602 584Q514 685 390 685M230 340L550 340"),
+    ('Λ', 700, "M60 0L350 680L640 0"),
+    ('Ξ', 640, "M90 680L550 680M150 350L490 350M80 0L560 0"),
+    ('Π', 720, "M100 0L100 680L620 680L620 0"),
+    ('Σ', 640, "M560 680L90 680L360 340L90 0L560 0"),
+    ('Υ', 680, "M60 600Q80 680 170 680Q300 680 340 480Q380 680 510 680Q600 680 620 600M340 480L340 0"),
+    ('Φ', 760, "M380 540Q264 540 182 481Q100 423 100 340Q100 257 182 199Q264 140 380 140Q496 140 578 199Q660 257 660 340Q660 423 578 481Q496 540 380 540M380 680L380 0"),
+    ('Ψ', 760, "M380 680L380 0M80 680L80 450Q80 230 380 230Q680 230 680 450L680 680"),
+    ('Ω', 760, "M80 0L260 0L260 90Q80 180 80 400Q80 690 380 690Q680 690 680 400Q680 180 500 90L500 0L680 0"),
+    ('α', 580, "M520 450L400 150Q340 0 230 0Q80 0 80 225Q80 450 230 450Q340 450 400 300L520 0"),
+    ('β', 560, "M100 -210L100 560Q100 720 270 720Q430 720 430 580Q430 430 260 430Q470 430 470 220Q470 0 270 0Q150 0 100 80"),
+    ('γ', 520, "M40 400Q90 460 150 430Q200 400 260 100L260 -200M260 100L460 450"),
+    ('δ', 520, "M260 400Q190 400 140 341Q90 283 90 200Q90 117 140 59Q190 0 260 0Q330 0 380 59Q430 117 430 200Q430 283 380 341Q330 400 260 400M330 390Q150 520 190 620Q240 740 420 690"),
+    ('ε', 480, "M410 400Q360 450 260 450Q110 450 110 350Q110 240 260 240M220 240Q80 240 80 120Q80 0 250 0Q360 0 420 60"),
+    ('ζ', 460, "M120 720Q260 680 400 720L200 480Q80 330 80 200Q80 50 250 30Q380 10 380 -80Q380 -180 280 -210"),
+    ('η', 540, "M100 450L100 0M100 300Q100 450 270 450Q440 450 440 300L440 -210"),
+    ('θ', 540, "M270 720Q195 720 143 615Q90 509 90 360Q90 211 143 105Q195 0 270 0Q345 0 397 105Q450 211 450 360Q450 509 397 615Q345 720 270 720M90 360L450 360"),
+    ('ι', 260, "M130 450L130 80Q130 0 210 0"),
+    ('κ', 500, "M100 450L100 0M420 450L100 200M200 280L430 0"),
+    ('λ', 520, "M60 720Q160 720 200 620L460 0M260 470L60 0"),
+    ('μ', 540, "M100 450L100 -210M100 150Q100 0 270 0Q440 0 440 150M440 450L440 0"),
+    ('ν', 500, "M60 450L230 0Q440 200 440 450"),
+    ('ξ', 460, "M130 720L380 720M360 560L230 560Q100 560 100 470Q100 380 230 380L300 380M230 380Q80 380 80 200Q80 40 230 20Q370 0 370 -100Q370 -200 280 -210"),
+    ('π', 600, "M60 420Q80 450 150 450L560 450M190 450L170 0M420 450L420 80Q420 0 500 0"),
+    ('ρ', 540, "M110 -210L110 225M280 450Q210 450 160 384Q110 318 110 225Q110 132 160 66Q210 0 280 0Q350 0 400 66Q450 132 450 225Q450 318 400 384Q350 450 280 450"),
+    ('ς', 460, "M400 420Q340 450 270 450Q90 450 90 250Q90 90 250 60Q370 40 370 -60Q370 -150 280 -200"),
+    ('σ', 560, "M250 450Q180 450 130 384Q80 318 80 225Q80 132 130 66Q180 0 250 0Q320 0 370 66Q420 132 420 225Q420 318 370 384Q320 450 250 450M250 450L530 450"),
+    ('τ', 480, "M50 450L440 450M240 450L240 80Q240 0 320 0"),
+    ('υ', 520, "M80 450L80 180Q80 0 260 0Q440 0 440 200Q440 380 400 450"),
+    ('φ', 600, "M200 430Q70 400 70 230Q70 20 300 20Q530 20 530 230Q530 450 400 450Q300 450 300 300L300 -210"),
+    ('χ', 520, "M60 450Q140 450 200 300L340 -60Q390 -210 460 -210M440 450L80 -210"),
+    ('ψ', 620, "M310 720L310 -210M80 450L80 250Q80 30 310 30Q540 30 540 250L540 450"),
+    ('ω', 700, "M160 450Q60 380 60 220Q60 0 210 0Q340 0 350 200Q360 0 490 0Q640 0 640 220Q640 380 540 450"),
+    ('ϑ', 560, "M40 260Q500 230 470 560Q450 720 340 720Q200 720 200 450L200 150Q200 0 320 0Q450 0 470 120"),
+    ('ϕ', 600, "M300 425Q217 425 159 366Q100 308 100 225Q100 142 159 84Q217 25 300 25Q383 25 441 84Q500 142 500 225Q500 308 441 366Q383 425 300 425M300 680L300 -210"),
+    ('ϵ', 480, "M420 430Q380 450 290 450Q90 450 90 225Q90 0 290 0Q380 0 420 20M90 225L350 225"),
+    ('‖', 500, "M170 750L170 -250M330 750L330 -250"),
+    ('…', 1000, "M167 65Q159 65 153 59Q147 53 147 45Q147 37 153 31Q159 25 167 25Q175 25 181 31Q187 37 187 45Q187 53 181 59Q175 65 167 65M500 65Q492 65 486 59Q480 53 480 45Q480 37 486 31Q492 25 500 25Q508 25 514 31Q520 37 520 45Q520 53 514 59Q508 65 500 65M833 65Q825 65 819 59Q813 53 813 45Q813 37 819 31Q825 25 833 25Q841 25 847 31Q853 37 853 45Q853 53 847 59Q841 65 833 65"),
+    ('′', 275, "M200 720L100 480"),
+    ('‾', 500, "M0 680L500 680"),
+    ('ℵ', 660, "M140 680L520 0M520 680L520 480Q520 400 380 380M140 0L140 200Q140 300 280 320"),
+    ('←', 1000, "M80 250L920 250M220 370L80 250L220 130"),
+    ('↑', 500, "M250 -200L250 700M130 560L250 700L370 560"),
+    ('→', 1000, "M80 250L920 250M780 370L920 250L780 130"),
+    ('↓', 500, "M250 700L250 -200M130 -60L250 -200L370 -60"),
+    ('↔', 1000, "M80 250L920 250M780 370L920 250L780 130M220 370L80 250L220 130"),
+    ('↠', 1000, "M80 250L920 250M780 370L920 250L780 130M680 370L820 250L680 130"),
+    ('↣', 1000, "M80 250L920 250M780 370L920 250L780 130M80 370L200 250L80 130"),
+    ('↦', 1000, "M80 250L920 250M780 370L920 250L780 130M80 370L80 130"),
+    ('⇐', 1000, "M920 360L200 360M920 140L200 140M300 490L80 250L300 10"),
+    ('⇒', 1000, "M80 360L800 360M80 140L800 140M700 490L920 250L700 10"),
+    ('⇔', 1000, "M200 360L800 360M200 140L800 140M700 490L920 250L700 10M300 490L80 250L300 10"),
+    ('∀', 600, "M60 680L300 0L540 680M140 460L460 460"),
+    ('∂', 560, "M120 600Q220 720 340 700Q480 680 480 420Q480 0 260 0Q90 0 90 170Q90 340 280 340Q420 340 470 230"),
+    ('∃', 560, "M100 680L460 680L460 0L100 0M140 340L460 340"),
+    ('∅', 560, "M280 600Q197 600 139 524Q80 448 80 340Q80 232 139 156Q197 80 280 80Q363 80 421 156Q480 232 480 340Q480 448 421 524Q363 600 280 600M480 720L80 -40"),
+    ('∇', 800, "M60 680L400 0L740 680L60 680"),
+    ('∈', 660, "M560 470L330 470Q110 470 110 250Q110 30 330 30L560 30M110 250L520 250"),
+    ('∉', 660, "M560 470L330 470Q110 470 110 250Q110 30 330 30L560 30M110 250L520 250M480 620L200 -120"),
+    ('∏', 940, "M80 750L860 750M200 750L200 -250M740 750L740 -250M80 -250L320 -250M620 -250L860 -250"),
+    ('∑', 900, "M800 600L800 750L100 750L500 250L100 -250L800 -250L800 -100"),
+    ('−', 780, "M160 250L620 250"),
+    ('∓', 780, "M160 460L620 460M390 360L390 -60M160 150L620 150"),
+    ('∖', 780, "M220 520L560 -20"),
+    ('∗', 780, "M390 430L390 70M234 340L546 160M546 340L234 160"),
+    ('∘', 780, "M390 360Q344 360 312 328Q280 296 280 250Q280 204 312 172Q344 140 390 140Q436 140 468 172Q500 204 500 250Q500 296 468 328Q436 360 390 360"),
+    ('√', 830, "M80 280L160 320L330 -220L760 750"),
+    ('∝', 780, "M650 420Q480 420 400 250Q320 80 200 80Q110 80 110 250Q110 420 200 420Q320 420 400 250Q480 80 650 80"),
+    ('∞', 1000, "M500 250Q380 420 250 420Q80 420 80 250Q80 80 250 80Q380 80 500 250Q620 420 750 420Q920 420 920 250Q920 80 750 80Q620 80 500 250"),
+    ('∠', 780, "M620 600L140 0L660 0"),
+    ('∥', 500, "M170 750L170 -250M330 750L330 -250"),
+    ('∧', 780, "M170 0L390 500L610 0"),
+    ('∨', 780, "M170 500L390 0L610 500"),
+    ('∩', 780, "M170 0L170 250Q170 500 390 500Q610 500 610 250L610 0"),
+    ('∪', 780, "M170 500L170 250Q170 0 390 0Q610 0 610 250L610 500"),
+    ('∫', 560, "M520 760Q480 800 420 790Q330 770 300 600L250 -100Q220 -270 130 -290Q70 -300 40 -260"),
+    ('∮', 560, "M520 760Q480 800 420 790Q330 770 300 600L250 -100Q220 -270 130 -290Q70 -300 40 -260M280 380Q234 380 202 342Q170 304 170 250Q170 196 202 158Q234 120 280 120Q326 120 358 158Q390 196 390 250Q390 304 358 342Q326 380 280 380"),
+    ('∴', 600, "M300 450Q292 450 286 444Q280 438 280 430Q280 422 286 416Q292 410 300 410Q308 410 314 416Q320 422 320 430Q320 438 314 444Q308 450 300 450M120 80Q112 80 106 74Q100 68 100 60Q100 52 106 46Q112 40 120 40Q128 40 134 46Q140 52 140 60Q140 68 134 74Q128 80 120 80M480 80Q472 80 466 74Q460 68 460 60Q460 52 466 46Q472 40 480 40Q488 40 494 46Q500 52 500 60Q500 68 494 74Q488 80 480 80"),
+    ('∵', 600, "M120 450Q112 450 106 444Q100 438 100 430Q100 422 106 416Q112 410 120 410Q128 410 134 416Q140 422 140 430Q140 438 134 444Q128 450 120 450M480 450Q472 450 466 444Q460 438 460 430Q460 422 466 416Q472 410 480 410Q488 410 494 416Q500 422 500 430Q500 438 494 444Q488 450 480 450M300 80Q292 80 286 74Q280 68 280 60Q280 52 286 46Q292 40 300 40Q308 40 314 46Q320 52 320 60Q320 68 314 74Q308 80 300 80"),
+    ('≅', 780, "M160 430Q275 520 390 440Q505 360 620 450M160 260L620 260M160 100L620 100"),
+    ('≈', 780, "M160 340Q275 430 390 350Q505 270 620 360M160 150Q275 240 390 160Q505 80 620 170"),
+    ('≔', 780, "M150 360Q142 360 136 354Q130 348 130 340Q130 332 136 326Q142 320 150 320Q158 320 164 326Q170 332 170 340Q170 348 164 354Q158 360 150 360M150 180Q142 180 136 174Q130 168 130 160Q130 152 136 146Q142 140 150 140Q158 140 164 146Q170 152 170 160Q170 168 164 174Q158 180 150 180M260 340L700 340M260 160L700 160"),
+    ('≠', 780, "M160 340L620 340M160 160L620 160M510 520L270 -20"),
+    ('≡', 780, "M160 420L620 420M160 250L620 250M160 80L620 80"),
+    ('≤', 780, "M620 580L160 380L620 180M160 30L620 30"),
+    ('≥', 780, "M160 580L620 380L160 180M160 30L620 30"),
+    ('≪', 880, "M500 480L120 250L500 20M760 480L380 250L760 20"),
+    ('≫', 880, "M120 480L500 250L120 20M380 480L760 250L380 20"),
+    ('≺', 780, "M620 480Q400 260 160 250Q400 240 620 20"),
+    ('≻', 780, "M160 480Q380 260 620 250Q380 240 160 20"),
+    ('⊂', 780, "M620 470L380 470Q160 470 160 250Q160 30 380 30L620 30"),
+    ('⊃', 780, "M160 470L400 470Q620 470 620 250Q620 30 400 30L160 30"),
+    ('⊆', 780, "M620 580L380 580Q160 580 160 380Q160 180 380 180L620 180M160 30L620 30"),
+    ('⊇', 780, "M160 580L400 580Q620 580 620 380Q620 180 400 180L160 180M620 30L160 30"),
+    ('⊕', 780, "M390 480Q295 480 227 413Q160 345 160 250Q160 155 227 87Q295 20 390 20Q485 20 553 87Q620 155 620 250Q620 345 553 413Q485 480 390 480M160 250L620 250M390 20L390 480"),
+    ('⊗', 780, "M390 480Q295 480 227 413Q160 345 160 250Q160 155 227 87Q295 20 390 20Q485 20 553 87Q620 155 620 250Q620 345 553 413Q485 480 390 480M227 87L553 413M227 413L553 87"),
+    ('⊙', 780, "M390 480Q295 480 227 413Q160 345 160 250Q160 155 227 87Q295 20 390 20Q485 20 553 87Q620 155 620 250Q620 345 553 413Q485 480 390 480M390 270Q382 270 376 264Q370 258 370 250Q370 242 376 236Q382 230 390 230Q398 230 404 236Q410 242 410 250Q410 258 404 264Q398 270 390 270"),
+    ('⊢', 680, "M120 680L120 0M120 340L580 340"),
+    ('⊤', 780, "M120 680L660 680M390 680L390 0"),
+    ('⊥', 780, "M390 680L390 0M120 0L660 0"),
+    ('⊨', 680, "M120 680L120 0M120 440L580 440M120 240L580 240"),
+    ('⋀', 840, "M100 -250L420 750L740 -250"),
+    ('⋁', 840, "M100 750L420 -250L740 750"),
+    ('⋂', 840, "M120 -250L120 400Q120 750 420 750Q720 750 720 400L720 -250"),
+    ('⋃', 840, "M120 750L120 100Q120 -250 420 -250Q720 -250 720 100L720 750"),
+    ('⋅', 278, "M139 270Q131 270 125 264Q119 258 119 250Q119 242 125 236Q131 230 139 230Q147 230 153 236Q159 242 159 250Q159 258 153 264Q147 270 139 270"),
+    ('⋆', 780, "M390 480L334 327L171 321L300 221L255 64L390 155L525 64L480 221L609 321L446 327L390 480"),
+    ('⋈', 780, "M160 450L160 50L620 450L620 50L160 450"),
+    ('⋉', 780, "M160 450L160 50L620 450M160 450L620 50"),
+    ('⋊', 780, "M620 450L620 50L160 450M620 450L160 50"),
+    ('⋮', 280, "M140 65Q132 65 126 59Q120 53 120 45Q120 37 126 31Q132 25 140 25Q148 25 154 31Q160 37 160 45Q160 53 154 59Q148 65 140 65M140 300Q132 300 126 294Q120 288 120 280Q120 272 126 266Q132 260 140 260Q148 260 154 266Q160 272 160 280Q160 288 154 294Q148 300 140 300M140 550Q132 550 126 544Q120 538 120 530Q120 522 126 516Q132 510 140 510Q148 510 154 516Q160 522 160 530Q160 538 154 544Q148 550 140 550"),
+    ('⋯', 1000, "M167 270Q159 270 153 264Q147 258 147 250Q147 242 153 236Q159 230 167 230Q175 230 181 236Q187 242 187 250Q187 258 181 264Q175 270 167 270M500 270Q492 270 486 264Q480 258 480 250Q480 242 486 236Q492 230 500 230Q508 230 514 236Q520 242 520 250Q520 258 514 264Q508 270 500 270M833 270Q825 270 819 264Q813 258 813 250Q813 242 819 236Q825 230 833 230Q841 230 847 236Q853 242 853 250Q853 258 847 264Q841 270 833 270"),
+    ('⋱', 1000, "M167 550Q159 550 153 544Q147 538 147 530Q147 522 153 516Q159 510 167 510Q175 510 181 516Q187 522 187 530Q187 538 181 544Q175 550 167 550M500 300Q492 300 486 294Q480 288 480 280Q480 272 486 266Q492 260 500 260Q508 260 514 266Q520 272 520 280Q520 288 514 294Q508 300 500 300M833 65Q825 65 819 59Q813 53 813 45Q813 37 819 31Q825 25 833 25Q841 25 847 31Q853 37 853 45Q853 53 847 59Q841 65 833 65"),
+    ('⌈', 400, "M320 750L120 750L120 -250"),
+    ('⌉', 400, "M80 750L280 750L280 -250"),
+    ('⌊', 400, "M120 750L120 -250L320 -250"),
+    ('⌋', 400, "M280 750L280 -250L80 -250"),
+    ('⌢', 1000, "M80 150Q500 520 920 150"),
+    ('⏞', 1000, "M80 420Q80 540 200 540L400 540Q500 540 500 640Q500 540 600 540L800 540Q920 540 920 420"),
+    ('⏟', 1000, "M80 80Q80 -40 200 -40L400 -40Q500 -40 500 -140Q500 -40 600 -40L800 -40Q920 -40 920 80"),
+    ('□', 780, "M120 530L660 530L660 -10L120 -10L120 530"),
+    ('△', 780, "M110 -10L390 520L670 -10L110 -10"),
+    ('◇', 780, "M390 580L660 250L390 -80L120 250L390 580"),
+    ('⟨', 390, "M300 750L90 250L300 -250"),
+    ('⟩', 390, "M90 750L300 250L90 -250"),
+    ('⤖', 1000, "M80 250L920 250M780 370L920 250L780 130M680 370L820 250L680 130M80 370L200 250L80 130"),
+    ('⪯', 780, "M620 580Q400 390 160 380Q400 370 620 180M160 30L620 30"),
+    ('⪰', 780, "M160 580Q380 390 620 380Q380 370 160 180M160 30L620 30"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_are_sorted() {
+        assert!(GLYPHS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn outlines_are_valid() {
+        for &(c, _, path) in GLYPHS {
+            for command in path.split_inclusive(|c: char| c.is_ascii_uppercase()) {
+                let numbers = command
+                    .trim_end_matches(|c: char| c.is_ascii_uppercase())
+                    .split(' ')
+                    .filter(|n| !n.is_empty());
+
+                assert!(
+                    numbers.clone().all(|n| n.parse::<f32>().is_ok()),
+                    "{c}: {path}"
+                );
+                assert!(matches!(numbers.count(), 0 | 2 | 4), "{c}: {path}");
+            }
+        }
+    }
+
+    #[test]
+    fn symbols_have_glyphs() {
+        let missing: Vec<_> = crate::keywords::symbols()
+            .filter_map(|symbol| symbol.unicode)
+            .flat_map(str::chars)
+            .map(|c| decompose(c).0)
+            .filter(|&c| GLYPHS.binary_search_by_key(&c, |&(c, ..)| c).is_err())
+            .collect();
+
+        assert!(missing.is_empty(), "no glyphs for {missing:?}");
+    }
+}
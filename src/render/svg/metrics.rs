@@ -0,0 +1,21 @@
+//! Font metrics used by the SVG layout, given in ems. They match the glyphs of the built-in font
+//! from the [`font`](super::font) module, the dimensions of the glyphs themselves are measured
+//! from their outlines.
+
+/// Height of the math axis above the baseline. Fractions and big operators are centered on it.
+pub(super) const AXIS_HEIGHT: f32 = 0.25;
+
+/// Thickness of fraction bars, radical overbars and other rules.
+pub(super) const RULE_THICKNESS: f32 = 0.05;
+
+/// Height of lowercase letters without ascenders.
+pub(super) const X_HEIGHT: f32 = 0.45;
+
+/// Scale of subscripts, superscripts and limits relative to their base.
+pub(super) const SCRIPT_SCALE: f32 = 0.7;
+
+/// Smallest scale used for nested scripts.
+pub(super) const MIN_SCALE: f32 = 0.5;
+
+/// Scale of big operators like `∑` and `∫`.
+pub(super) const LARGE_OP_SCALE: f32 = 1.4;
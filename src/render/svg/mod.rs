@@ -0,0 +1,1057 @@
+//! Layout of the abstract syntax tree into boxes and rendering of the boxes into a standalone
+//! SVG image, e.g. for emails and PDFs.
+//!
+//! Every expression is laid out into a [`LayoutBox`] with a width, an ascent above and a descent
+//! below its baseline. Glyphs are drawn with the built-in font from the [`font`] module, so the
+//! boxes contain only rules and stroked paths positioned relative to the baseline. All lengths are
+//! in ems and are converted to pixels only when the SVG is written.
+
+mod font;
+mod metrics;
+
+use crate::{
+    lexer::keywords::{operators::Operator, others::Other},
+    parser::Matrix,
+    Binary, BinaryKind, Expression, GroupingExpr, SimpleExpr, Unary, UnaryKind, Var, VarKind,
+};
+
+use self::metrics::{
+    AXIS_HEIGHT, LARGE_OP_SCALE, MIN_SCALE, RULE_THICKNESS, SCRIPT_SCALE, X_HEIGHT,
+};
+
+use super::{escape_into, is_css_color, keep_spaces, Class, Variant};
+
+/// Options for rendering SVG images with [`render_svg`](crate::render_svg).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// Size of the font in pixels. All dimensions of the image scale with it.
+    pub font_size: f32,
+
+    /// Color of the math, as any color value accepted by SVG, e.g. `black` or `#336699`.
+    pub color: String,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            font_size: 16.0,
+            color: String::from("black"),
+        }
+    }
+}
+
+/// Space around the laid out math, in ems.
+const PADDING: f32 = 0.1;
+
+/// Segment of a path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Segment {
+    Move(f32, f32),
+    Line(f32, f32),
+    /// Quadratic curve with a control point and an end point.
+    Quad(f32, f32, f32, f32),
+}
+
+impl Segment {
+    fn translate(&mut self, dx: f32, dy: f32) {
+        match self {
+            Segment::Move(x, y) | Segment::Line(x, y) => {
+                *x += dx;
+                *y += dy;
+            }
+            Segment::Quad(cx, cy, x, y) => {
+                *cx += dx;
+                *cy += dy;
+                *x += dx;
+                *y += dy;
+            }
+        }
+    }
+
+    fn points(&self) -> Vec<(f32, f32)> {
+        match *self {
+            Segment::Move(x, y) | Segment::Line(x, y) => vec![(x, y)],
+            Segment::Quad(cx, cy, x, y) => vec![(cx, cy), (x, y)],
+        }
+    }
+}
+
+/// Shape positioned relative to the baseline of its box. The `y` axis points down, as in SVG.
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    Circle {
+        x: f32,
+        y: f32,
+        r: f32,
+    },
+    Path {
+        segments: Vec<Segment>,
+        stroke: f32,
+    },
+}
+
+impl Shape {
+    fn translate(&mut self, dx: f32, dy: f32) {
+        match self {
+            Shape::Rect { x, y, .. } | Shape::Circle { x, y, .. } => {
+                *x += dx;
+                *y += dy;
+            }
+            Shape::Path { segments, .. } => {
+                segments.iter_mut().for_each(|s| s.translate(dx, dy));
+            }
+        }
+    }
+
+    /// Returns the right edge, the top and the bottom of the shape.
+    fn extent(&self) -> Option<(f32, f32, f32)> {
+        match self {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => Some((x + width, *y, y + height)),
+            Shape::Circle { x, y, r } => Some((x + r, y - r, y + r)),
+            Shape::Path { segments, stroke } => {
+                let points = segments.iter().flat_map(Segment::points);
+
+                points
+                    .fold(None, |acc, (x, y)| {
+                        let (right, top, bottom) = acc.unwrap_or((x, y, y));
+                        Some((right.max(x), top.min(y), bottom.max(y)))
+                    })
+                    .map(|(right, top, bottom)| {
+                        (
+                            right + stroke / 2.0,
+                            top - stroke / 2.0,
+                            bottom + stroke / 2.0,
+                        )
+                    })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Item {
+    shape: Shape,
+
+    /// Color set with `color(..)`, the color from options is used if not set.
+    color: Option<String>,
+}
+
+/// Laid out content with its dimensions. The origin is at the left end of the baseline.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct LayoutBox {
+    width: f32,
+    ascent: f32,
+    descent: f32,
+    items: Vec<Item>,
+}
+
+impl LayoutBox {
+    fn height(&self) -> f32 {
+        self.ascent + self.descent
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.width == 0.0
+    }
+
+    /// Empty box taking up horizontal space.
+    fn space(width: f32) -> Self {
+        Self {
+            width,
+            ..Default::default()
+        }
+    }
+
+    /// Places the other box into this one, with its origin moved by the given offset.
+    fn place(&mut self, other: LayoutBox, dx: f32, dy: f32) {
+        self.width = self.width.max(dx + other.width);
+        self.ascent = self.ascent.max(other.ascent - dy);
+        self.descent = self.descent.max(other.descent + dy);
+
+        self.items.extend(other.items.into_iter().map(|mut item| {
+            item.shape.translate(dx, dy);
+            item
+        }));
+    }
+
+    /// Adds the shape to the box, growing the box to contain it.
+    fn push(&mut self, shape: Shape) {
+        if let Some((right, top, bottom)) = shape.extent() {
+            self.width = self.width.max(right);
+            self.ascent = self.ascent.max(-top);
+            self.descent = self.descent.max(bottom);
+        }
+
+        self.items.push(Item { shape, color: None });
+    }
+
+    /// Composes the boxes horizontally, aligned on their baselines.
+    fn hbox(boxes: impl IntoIterator<Item = LayoutBox>) -> Self {
+        let mut result = LayoutBox::default();
+
+        for b in boxes {
+            let x = result.width;
+            result.place(b, x, 0.0);
+        }
+
+        result
+    }
+
+    /// Sets the color of all items that don't have a color yet.
+    fn colored(mut self, color: &str) -> Self {
+        for item in self.items.iter_mut().filter(|item| item.color.is_none()) {
+            item.color = Some(color.to_string());
+        }
+
+        self
+    }
+}
+
+/// Lays out [`Expression`]s into boxes.
+#[derive(Debug)]
+pub(crate) struct Layout {
+    /// Scale of the content that is currently laid out, e.g. smaller for scripts.
+    scale: f32,
+
+    /// Font variant selected by the enclosing font command, if any.
+    variant: Option<Variant>,
+}
+
+impl Layout {
+    /// Lays out the expressions and renders them into a standalone SVG image.
+    pub(crate) fn render<'e, I>(exprs: I, options: &SvgOptions) -> String
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        let mut layout = Layout {
+            scale: 1.0,
+            variant: None,
+        };
+
+        let root = layout.row(exprs);
+        Svg::new(options).write(&root)
+    }
+
+    /// Lays out content with the scale of scripts.
+    fn scripted<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let outer = self.scale;
+        self.scale = (outer * SCRIPT_SCALE).max(MIN_SCALE);
+        let result = f(self);
+        self.scale = outer;
+        result
+    }
+
+    fn text(&self, text: &str, italic: bool) -> LayoutBox {
+        let (text, italic) = match self.variant {
+            Some(variant) => (text.chars().map(|c| variant.apply(c)).collect(), false),
+            None => (text.to_string(), italic),
+        };
+
+        let s = self.scale;
+        let (glyph, strokes) = font::outline(&text, s, italic);
+
+        // slanted letters lean into the following content
+        let correction = if italic { 0.05 } else { 0.0 };
+
+        LayoutBox {
+            width: glyph.width + correction * s,
+            ascent: glyph.height,
+            descent: glyph.depth,
+            items: strokes
+                .into_iter()
+                .map(|shape| Item { shape, color: None })
+                .collect(),
+        }
+    }
+
+    /// Lays out big operators like `∑`, enlarged and centered on the math axis.
+    fn large_op(&self, op: &str) -> LayoutBox {
+        let (glyph, strokes) = font::outline(op, self.scale * LARGE_OP_SCALE, false);
+
+        let center = (glyph.height - glyph.depth) / 2.0;
+        let shift = center - AXIS_HEIGHT * self.scale;
+
+        let mut b = LayoutBox::default();
+        b.place(
+            LayoutBox {
+                width: glyph.width,
+                ascent: glyph.height,
+                descent: glyph.depth,
+                items: strokes
+                    .into_iter()
+                    .map(|shape| Item { shape, color: None })
+                    .collect(),
+            },
+            0.0,
+            shift,
+        );
+
+        b
+    }
+
+    fn row<'e, I>(&mut self, exprs: I) -> LayoutBox
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        let mut prev = None;
+        let mut boxes = vec![];
+
+        for expr in exprs {
+            let b = self.expr(expr);
+
+            if b.is_empty() {
+                continue;
+            }
+
+            let class = Class::of(expr).after(prev);
+
+            if let Some(prev) = prev {
+                boxes.push(LayoutBox::space(space(prev, class) * self.scale));
+            }
+
+            boxes.push(b);
+            prev = Some(class);
+        }
+
+        LayoutBox::hbox(boxes)
+    }
+
+    fn expr(&mut self, expr: &Expression) -> LayoutBox {
+        if let Some(matrix) = expr.as_matrix() {
+            return self.matrix(&matrix);
+        }
+
+        let base = self.simple(&expr.interm);
+
+        if !expr.is_scripted() {
+            return base;
+        }
+
+        let (sub, sup) = self.scripted(|this| {
+            let sub = expr.subscript.as_ref().map(|sub| this.script(sub));
+            let sup = expr.supscript.as_ref().map(|sup| this.script(sup));
+            (sub, sup)
+        });
+
+        if expr.interm.is_underover() {
+            return self.limits(base, sub, sup);
+        }
+
+        let s = self.scale;
+        let x = base.width;
+
+        let mut up = (base.ascent - 0.3 * s).max(0.36 * s);
+        let mut down = (base.descent + 0.1 * s).max(0.15 * s);
+
+        if let (Some(sub), Some(sup)) = (&sub, &sup) {
+            down = down.max(0.25 * s);
+
+            // keep a gap between the scripts
+            let gap = (up - sup.descent) - (sub.ascent - down);
+            if gap < 0.16 * s {
+                let missing = 0.16 * s - gap;
+                up += missing / 2.0;
+                down += missing / 2.0;
+            }
+        }
+
+        let mut result = base;
+
+        if let Some(sup) = sup {
+            result.place(sup, x, -up);
+        }
+
+        if let Some(sub) = sub {
+            result.place(sub, x, down);
+        }
+
+        result.width += 0.05 * s;
+        result
+    }
+
+    /// Places the scripts centered above and below the base.
+    fn limits(
+        &self,
+        base: LayoutBox,
+        under: Option<LayoutBox>,
+        over: Option<LayoutBox>,
+    ) -> LayoutBox {
+        let gap = 0.1 * self.scale;
+        let width = [Some(&base), under.as_ref(), over.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|b| b.width)
+            .fold(0.0, f32::max);
+
+        let mut result = LayoutBox::default();
+
+        if let Some(over) = over {
+            let dy = -(base.ascent + gap + over.descent);
+            let x = (width - over.width) / 2.0;
+            result.place(over, x, dy);
+        }
+
+        if let Some(under) = under {
+            let dy = base.descent + gap + under.ascent;
+            let x = (width - under.width) / 2.0;
+            result.place(under, x, dy);
+        }
+
+        let x = (width - base.width) / 2.0;
+        result.place(base, x, 0.0);
+        result
+    }
+
+    fn simple(&mut self, expr: &SimpleExpr) -> LayoutBox {
+        match expr {
+            SimpleExpr::Var(var) => self.var(var),
+            SimpleExpr::Grouping(grp) => self.grouping(grp),
+            SimpleExpr::Unary(unary) => self.unary(unary),
+            SimpleExpr::Binary(binary) => self.binary(binary),
+            SimpleExpr::Interm(inner) => self.expr(inner),
+        }
+    }
+
+    /// Lays out an argument of unary or binary operators. Simple groupings are laid out without
+    /// the grouping symbols.
+    fn arg(&mut self, expr: &SimpleExpr) -> LayoutBox {
+        match expr {
+            SimpleExpr::Grouping(grp) if grp.is_simple_grp() => self.row(&grp.expr),
+            _ => self.simple(expr),
+        }
+    }
+
+    /// Lays out a subscript or superscript. Groupings are laid out without the grouping symbols.
+    fn script(&mut self, expr: &SimpleExpr) -> LayoutBox {
+        match expr {
+            SimpleExpr::Grouping(grp) => self.row(&grp.expr),
+            _ => self.simple(expr),
+        }
+    }
+
+    fn var(&mut self, var: &Var) -> LayoutBox {
+        match &var.kind {
+            VarKind::Function(func) => {
+                let is_single = func.as_ref().chars().count() == 1;
+                self.text(func.as_ref(), is_single)
+            }
+            VarKind::Number(num) => self.text(num, false),
+            VarKind::Greek(greek) => {
+                let is_lowercase = greek.as_ref().starts_with(char::is_lowercase);
+                self.text(greek.as_unicode(), is_lowercase)
+            }
+            VarKind::Variable(var) => self.text(var, true),
+            VarKind::Arrow(arrow) => self.text(arrow.as_unicode(), false),
+            VarKind::Relation(rel) => self.text(rel.as_unicode(), false),
+            VarKind::Logical(log) => self.text(log.as_unicode(), false),
+            VarKind::Operator(
+                op @ (Operator::Sum
+                | Operator::Prod
+                | Operator::BigWedge
+                | Operator::BigCap
+                | Operator::BigCup),
+            ) => self.large_op(op.as_unicode()),
+            VarKind::Operator(op) => self.text(op.as_unicode(), false),
+            VarKind::Other(other @ (Other::Integral | Other::OIntegral)) => {
+                self.large_op(other.as_unicode())
+            }
+            VarKind::Other(other) => self.text(other.as_unicode(), false),
+            VarKind::Text(text) => self.text(&keep_spaces(text), false),
+            VarKind::UnknownOperator(op) if op.is_empty() => LayoutBox::default(),
+            VarKind::UnknownOperator(op) => self.text(op, false),
+        }
+    }
+
+    fn grouping(&mut self, grp: &GroupingExpr) -> LayoutBox {
+        let content = self.row(&grp.expr);
+
+        let left = grp.left_grouping.as_unicode(true);
+        let right = grp.right_grouping.as_unicode(false);
+
+        self.delimited(left, content, right)
+    }
+
+    /// Surrounds the content with the delimiters. Delimiters of content taller than a line are
+    /// drawn as paths stretched to the height of the content.
+    fn delimited(&self, left: &str, content: LayoutBox, right: &str) -> LayoutBox {
+        let s = self.scale;
+        let is_tall = content.ascent > 0.8 * s || content.descent > 0.3 * s;
+
+        let delimiter = |symbol: &str| match symbol {
+            "" => LayoutBox::default(),
+            _ if is_tall => self.stretched(symbol, &content),
+            _ => self.text(symbol, false),
+        };
+
+        LayoutBox::hbox([delimiter(left), content.clone(), delimiter(right)])
+    }
+
+    /// Draws the delimiter symmetrically around the math axis, covering the content.
+    fn stretched(&self, symbol: &str, content: &LayoutBox) -> LayoutBox {
+        let s = self.scale;
+        let axis = AXIS_HEIGHT * s;
+        let half = (content.ascent - axis).max(content.descent + axis) + 0.1 * s;
+
+        let (top, bottom) = (-(axis + half), half - axis);
+        let mid = -axis;
+        let w = 0.45 * s;
+
+        // right delimiters are drawn as mirrored left delimiters
+        let (shape, is_mirrored) = match symbol {
+            ")" => ("(", true),
+            "]" => ("[", true),
+            "}" => ("{", true),
+            "⟩" => ("⟨", true),
+            "⌋" => ("⌊", true),
+            "⌉" => ("⌈", true),
+            other => (other, false),
+        };
+
+        use Segment::*;
+
+        let q = 0.12 * (bottom - top);
+        let segments = match shape {
+            "(" => vec![Move(0.8 * w, top), Quad(-0.5 * w, mid, 0.8 * w, bottom)],
+            "[" => vec![
+                Move(0.8 * w, top),
+                Line(0.25 * w, top),
+                Line(0.25 * w, bottom),
+                Line(0.8 * w, bottom),
+            ],
+            "⌊" => vec![
+                Move(0.25 * w, top),
+                Line(0.25 * w, bottom),
+                Line(0.8 * w, bottom),
+            ],
+            "⌈" => vec![
+                Move(0.8 * w, top),
+                Line(0.25 * w, top),
+                Line(0.25 * w, bottom),
+            ],
+            "{" => vec![
+                Move(0.8 * w, top),
+                Quad(0.45 * w, top, 0.45 * w, top + q),
+                Line(0.45 * w, mid - q),
+                Quad(0.45 * w, mid, 0.1 * w, mid),
+                Quad(0.45 * w, mid, 0.45 * w, mid + q),
+                Line(0.45 * w, bottom - q),
+                Quad(0.45 * w, bottom, 0.8 * w, bottom),
+            ],
+            "⟨" => vec![
+                Move(0.8 * w, top),
+                Line(0.2 * w, mid),
+                Line(0.8 * w, bottom),
+            ],
+            "|" => vec![Move(0.5 * w, top), Line(0.5 * w, bottom)],
+            "‖" => vec![
+                Move(0.35 * w, top),
+                Line(0.35 * w, bottom),
+                Move(0.65 * w, top),
+                Line(0.65 * w, bottom),
+            ],
+            _ => return self.text(symbol, false),
+        };
+
+        let segments = if is_mirrored {
+            segments
+                .into_iter()
+                .map(|segment| match segment {
+                    Move(x, y) => Move(w - x, y),
+                    Line(x, y) => Line(w - x, y),
+                    Quad(cx, cy, x, y) => Quad(w - cx, cy, w - x, y),
+                })
+                .collect()
+        } else {
+            segments
+        };
+
+        let mut b = LayoutBox::space(w);
+        b.push(Shape::Path {
+            segments,
+            stroke: 1.5 * RULE_THICKNESS * s,
+        });
+        b
+    }
+
+    fn unary(&mut self, unary: &Unary) -> LayoutBox {
+        if let Some(variant) = Variant::from_unary(unary.kind) {
+            let outer = self.variant.replace(variant);
+            let b = self.arg(&unary.expr);
+            self.variant = outer;
+            return b;
+        }
+
+        let arg = self.arg(&unary.expr);
+
+        match unary.kind {
+            UnaryKind::Hat
+            | UnaryKind::Vector
+            | UnaryKind::Tilde
+            | UnaryKind::Dot
+            | UnaryKind::DoubleDot => self.accent(unary.kind, arg),
+            UnaryKind::Overline | UnaryKind::Underline => self.line(unary.kind, arg),
+            UnaryKind::Overbrace => self.brace(arg, true),
+            UnaryKind::Underbrace => self.brace(arg, false),
+            UnaryKind::Cancel => {
+                let mut b = arg.clone();
+                b.push(Shape::Path {
+                    segments: vec![
+                        Segment::Move(0.0, arg.descent),
+                        Segment::Line(arg.width, -arg.ascent),
+                    ],
+                    stroke: RULE_THICKNESS * self.scale,
+                });
+                b
+            }
+            UnaryKind::SquareRoot => self.radical(arg),
+            UnaryKind::Absolute => self.delimited("|", arg, "|"),
+            UnaryKind::Floor => self.delimited("⌊", arg, "⌋"),
+            UnaryKind::Ceiling => self.delimited("⌈", arg, "⌉"),
+            UnaryKind::Norm => self.delimited("‖", arg, "‖"),
+
+            // font commands are handled above
+            _ => arg,
+        }
+    }
+
+    /// Draws the accent centered above the box.
+    fn accent(&self, kind: UnaryKind, arg: LayoutBox) -> LayoutBox {
+        use Segment::*;
+
+        let s = self.scale;
+        let stroke = RULE_THICKNESS * s;
+
+        let width = arg.width.max(0.3 * s);
+        let center = arg.width / 2.0;
+        let (left, right) = (center - width * 0.4, center + width * 0.4);
+        let y = -(arg.ascent.max(X_HEIGHT * s) + 0.12 * s);
+
+        let mut b = arg;
+
+        match kind {
+            UnaryKind::Hat => b.push(Shape::Path {
+                segments: vec![
+                    Move(center - 0.15 * s, y),
+                    Line(center, y - 0.12 * s),
+                    Line(center + 0.15 * s, y),
+                ],
+                stroke,
+            }),
+            UnaryKind::Vector => b.push(Shape::Path {
+                segments: vec![
+                    Move(left, y),
+                    Line(right, y),
+                    Move(right - 0.1 * s, y - 0.07 * s),
+                    Line(right, y),
+                    Line(right - 0.1 * s, y + 0.07 * s),
+                ],
+                stroke,
+            }),
+            UnaryKind::Tilde => b.push(Shape::Path {
+                segments: vec![
+                    Move(center - 0.2 * s, y),
+                    Quad(center - 0.1 * s, y - 0.1 * s, center, y),
+                    Quad(
+                        center + 0.1 * s,
+                        y + 0.1 * s,
+                        center + 0.2 * s,
+                        y - 0.05 * s,
+                    ),
+                ],
+                stroke,
+            }),
+            UnaryKind::Dot => b.push(Shape::Circle {
+                x: center,
+                y,
+                r: 0.05 * s,
+            }),
+            _ => {
+                for x in [center - 0.12 * s, center + 0.12 * s] {
+                    b.push(Shape::Circle { x, y, r: 0.05 * s });
+                }
+            }
+        }
+
+        b
+    }
+
+    /// Draws a rule above or below the box.
+    fn line(&self, kind: UnaryKind, arg: LayoutBox) -> LayoutBox {
+        let s = self.scale;
+        let thickness = RULE_THICKNESS * s;
+
+        let y = match kind {
+            UnaryKind::Overline => -(arg.ascent + 0.1 * s + thickness),
+            _ => arg.descent + 0.1 * s,
+        };
+
+        let width = arg.width;
+        let mut b = arg;
+        b.push(Shape::Rect {
+            x: 0.0,
+            y,
+            width,
+            height: thickness,
+        });
+        b
+    }
+
+    /// Draws a horizontal brace above or below the box.
+    fn brace(&self, arg: LayoutBox, is_over: bool) -> LayoutBox {
+        use Segment::*;
+
+        let s = self.scale;
+        let (x0, x1) = (0.0, arg.width.max(0.4 * s));
+        let center = (x0 + x1) / 2.0;
+
+        // direction in which the tip of the brace points
+        let dir = if is_over { -1.0 } else { 1.0 };
+        let y0 = if is_over {
+            -(arg.ascent + 0.08 * s)
+        } else {
+            arg.descent + 0.08 * s
+        };
+        let (y1, y2) = (y0 + dir * 0.1 * s, y0 + dir * 0.2 * s);
+        let r = (0.1 * s).min((x1 - x0) / 4.0);
+
+        let mut b = arg;
+        b.push(Shape::Path {
+            segments: vec![
+                Move(x0, y0),
+                Quad(x0, y1, x0 + r, y1),
+                Line(center - r, y1),
+                Quad(center, y1, center, y2),
+                Quad(center, y1, center + r, y1),
+                Line(x1 - r, y1),
+                Quad(x1, y1, x1, y0),
+            ],
+            stroke: RULE_THICKNESS * s,
+        });
+        b
+    }
+
+    /// Draws the radical sign in front of the box and a rule above it.
+    fn radical(&self, arg: LayoutBox) -> LayoutBox {
+        use Segment::*;
+
+        let s = self.scale;
+        let thickness = RULE_THICKNESS * s;
+        let ascent = arg.ascent.max(X_HEIGHT * s);
+        let descent = arg.descent.max(0.1 * s);
+
+        let top = -(ascent + 0.12 * s + thickness / 2.0);
+        let bottom = descent + 0.05 * s;
+        let height = bottom - top;
+        let sign = 0.55 * s;
+
+        let end = sign + 0.05 * s + arg.width + 0.1 * s;
+
+        let mut b = LayoutBox::default();
+        b.push(Shape::Path {
+            segments: vec![
+                Move(0.0, bottom - 0.4 * height),
+                Line(0.15 * sign, bottom - 0.48 * height),
+                Line(0.5 * sign, bottom),
+                Line(sign, top),
+                Line(end, top),
+            ],
+            stroke: thickness,
+        });
+        b.place(arg, sign + 0.05 * s, 0.0);
+        b
+    }
+
+    fn binary(&mut self, binary: &Binary) -> LayoutBox {
+        let s = self.scale;
+
+        match binary.kind {
+            BinaryKind::Fraction => {
+                let num = self.arg(&binary.expr_1);
+                let den = self.arg(&binary.expr_2);
+
+                let thickness = RULE_THICKNESS * s;
+                let axis = AXIS_HEIGHT * s;
+                let gap = 0.15 * s;
+                let padding = 0.1 * s;
+                let width = num.width.max(den.width) + 2.0 * padding;
+
+                let mut b = LayoutBox::default();
+                b.push(Shape::Rect {
+                    x: 0.0,
+                    y: -axis - thickness / 2.0,
+                    width,
+                    height: thickness,
+                });
+
+                let num_y = -(axis + thickness / 2.0 + gap + num.descent);
+                let den_y = -axis + thickness / 2.0 + gap + den.ascent;
+
+                let (num_x, den_x) = ((width - num.width) / 2.0, (width - den.width) / 2.0);
+                b.place(num, num_x, num_y);
+                b.place(den, den_x, den_y);
+
+                let mut result = LayoutBox::space(0.05 * s);
+                let x = result.width;
+                result.place(b, x, 0.0);
+                result.width += 0.05 * s;
+                result
+            }
+            BinaryKind::Root => {
+                let index = self.scripted(|this| this.arg(&binary.expr_1));
+                let radicand = self.arg(&binary.expr_2);
+                let root = self.radical(radicand);
+
+                // the index sits above the hook of the radical sign
+                let sign = 0.55 * s;
+                let kern = (index.width - 0.5 * sign).max(0.0);
+                let index_y = root.descent - 0.6 * root.height() - index.descent;
+                let index_x = (0.5 * sign - index.width).max(0.0);
+
+                let mut b = LayoutBox::default();
+                b.place(index, index_x, index_y);
+                b.place(root, kern, 0.0);
+                b
+            }
+            BinaryKind::Overset => {
+                let over = self.scripted(|this| this.arg(&binary.expr_1));
+                let base = self.arg(&binary.expr_2);
+                self.limits(base, None, Some(over))
+            }
+            BinaryKind::Underset => {
+                let under = self.scripted(|this| this.arg(&binary.expr_1));
+                let base = self.arg(&binary.expr_2);
+                self.limits(base, Some(under), None)
+            }
+            BinaryKind::Color => {
+                let color = match &*binary.expr_1 {
+                    SimpleExpr::Var(Var {
+                        kind: VarKind::Text(color),
+                        ..
+                    }) => Some(color.trim()).filter(|color| is_css_color(color)),
+                    _ => None,
+                };
+
+                let content = self.arg(&binary.expr_2);
+
+                match color {
+                    Some(color) => content.colored(color),
+                    None => content,
+                }
+            }
+        }
+    }
+
+    fn matrix(&mut self, matrix: &Matrix<'_>) -> LayoutBox {
+        let s = self.scale;
+        let columns = matrix.num_of_columns();
+        let column_gap = 0.8 * s;
+        let row_gap = 0.25 * s;
+
+        let cells: Vec<Vec<LayoutBox>> = matrix
+            .rows
+            .iter()
+            .map(|row| {
+                (0..columns)
+                    .map(|i| match row.get(i) {
+                        Some(cell) => self.row(cell.iter().copied()),
+                        None => LayoutBox::default(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let widths: Vec<f32> = (0..columns)
+            .map(|i| cells.iter().map(|row| row[i].width).fold(0.0, f32::max))
+            .collect();
+
+        let mut table = LayoutBox::default();
+        let mut y = 0.0;
+
+        for (row_idx, row) in cells.into_iter().enumerate() {
+            // rows are at least as tall as a line of text
+            let ascent = row.iter().map(|c| c.ascent).fold(0.7 * s, f32::max);
+            let descent = row.iter().map(|c| c.descent).fold(0.3 * s, f32::max);
+
+            if row_idx > 0 {
+                y += row_gap;
+            }
+            y += ascent;
+
+            let mut x = 0.0;
+            for (i, cell) in row.into_iter().enumerate() {
+                let cell_x = x + (widths[i] - cell.width) / 2.0;
+                table.place(cell, cell_x, y);
+                x += widths[i] + column_gap;
+            }
+
+            y += descent;
+        }
+
+        // column lines span the whole height of the matrix
+        let mut x = 0.0;
+        for (i, width) in widths.iter().enumerate().take(columns.saturating_sub(1)) {
+            x += width;
+
+            if matrix.column_lines.get(i).copied().unwrap_or(false) {
+                table.push(Shape::Rect {
+                    x: x + column_gap / 2.0 - RULE_THICKNESS * s / 2.0,
+                    y: 0.0,
+                    width: RULE_THICKNESS * s,
+                    height: y,
+                });
+            }
+
+            x += column_gap;
+        }
+
+        // the matrix is centered on the math axis
+        let height = y;
+        let mut centered = LayoutBox::default();
+        centered.place(table, 0.0, -AXIS_HEIGHT * s - height / 2.0);
+
+        let left = matrix.left_grouping.as_unicode(true);
+        let right = matrix.right_grouping.as_unicode(false);
+
+        self.delimited(left, centered, right)
+    }
+}
+
+/// Space between expressions of the given classes, in ems.
+fn space(prev: Class, next: Class) -> f32 {
+    match (prev, next) {
+        (Class::Rel, _) | (_, Class::Rel) => 0.278,
+        (Class::Bin, _) | (_, Class::Bin) => 0.222,
+        (Class::Punct, _) => 0.167,
+        (Class::Func, Class::Group) => 0.0,
+        (Class::Func | Class::Large, _) => 0.167,
+        _ => 0.0,
+    }
+}
+
+/// Writes laid out boxes as SVG.
+struct Svg<'o> {
+    options: &'o SvgOptions,
+    out: String,
+}
+
+impl<'o> Svg<'o> {
+    fn new(options: &'o SvgOptions) -> Self {
+        Self {
+            options,
+            out: String::new(),
+        }
+    }
+
+    /// Converts the length in ems into pixels.
+    fn px(&self, em: f32) -> String {
+        let px = format!("{:.2}", em * self.options.font_size);
+        let px = px.trim_end_matches('0').trim_end_matches('.');
+
+        match px {
+            "-0" => String::from("0"),
+            px => px.to_string(),
+        }
+    }
+
+    fn write(mut self, root: &LayoutBox) -> String {
+        let width = self.px(root.width + 2.0 * PADDING);
+        let height = self.px(root.height() + 2.0 * PADDING);
+
+        self.out.push_str(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">"
+        ));
+
+        self.out.push_str("<g fill=\"");
+        escape_into(&mut self.out, &self.options.color);
+        self.out.push_str("\">");
+
+        // origin of the root box in the image
+        let (x0, y0) = (PADDING, PADDING + root.ascent);
+
+        for item in &root.items {
+            let mut shape = item.shape.clone();
+            shape.translate(x0, y0);
+            self.shape(&shape, item.color.as_deref());
+        }
+
+        self.out.push_str("</g></svg>");
+        self.out
+    }
+
+    fn shape(&mut self, shape: &Shape, color: Option<&str>) {
+        let fill = |out: &mut String| {
+            if let Some(color) = color {
+                out.push_str(" fill=\"");
+                escape_into(out, color);
+                out.push('"');
+            }
+        };
+
+        match shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let attrs = format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+                    self.px(*x),
+                    self.px(*y),
+                    self.px(*width),
+                    self.px(*height)
+                );
+                self.out.push_str(&attrs);
+                fill(&mut self.out);
+                self.out.push_str("/>");
+            }
+            Shape::Circle { x, y, r } => {
+                let attrs = format!(
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"",
+                    self.px(*x),
+                    self.px(*y),
+                    self.px(*r)
+                );
+                self.out.push_str(&attrs);
+                fill(&mut self.out);
+                self.out.push_str("/>");
+            }
+            Shape::Path { segments, stroke } => {
+                let d = segments
+                    .iter()
+                    .map(|segment| match *segment {
+                        Segment::Move(x, y) => format!("M{} {}", self.px(x), self.px(y)),
+                        Segment::Line(x, y) => format!("L{} {}", self.px(x), self.px(y)),
+                        Segment::Quad(cx, cy, x, y) => format!(
+                            "Q{} {} {} {}",
+                            self.px(cx),
+                            self.px(cy),
+                            self.px(x),
+                            self.px(y)
+                        ),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let stroke_width = self.px(*stroke);
+                self.out
+                    .push_str(&format!("<path d=\"{d}\" fill=\"none\" stroke=\""));
+                escape_into(&mut self.out, color.unwrap_or(&self.options.color));
+                self.out.push_str(&format!(
+                    "\" stroke-width=\"{stroke_width}\" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>"
+                ));
+            }
+        }
+    }
+}
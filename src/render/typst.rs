@@ -0,0 +1,629 @@
+//! Rendering of the abstract syntax tree into [Typst](https://typst.app/) math markup.
+
+use crate::{
+    lexer::keywords::{
+        arrows::Arrow, functions::Function, greeks::Greek, groupings::Grouping, logicals::Logical,
+        operators::Operator, others::Other, relations::Relation,
+    },
+    parser::Matrix,
+    Binary, BinaryKind, Expression, GroupingExpr, SimpleExpr, Unary, UnaryKind, Var, VarKind,
+};
+
+/// Renders [`Expression`]s into Typst math markup. The output does not contain the surrounding
+/// `$` delimiters.
+#[derive(Debug, Default)]
+pub(crate) struct Typst {
+    out: String,
+
+    /// Depth of nested function call arguments. Commas must be escaped inside of arguments,
+    /// otherwise Typst interprets them as argument separators.
+    args_depth: usize,
+}
+
+impl Typst {
+    /// Renders all the given expressions into a single string of Typst math markup.
+    pub(crate) fn render<'e, I>(exprs: I) -> String
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        let mut typst = Typst::default();
+        typst.exprs(exprs);
+        typst.out
+    }
+
+    fn exprs<'e, I>(&mut self, exprs: I)
+    where
+        I: IntoIterator<Item = &'e Expression<'e>>,
+    {
+        for (i, expr) in exprs.into_iter().enumerate() {
+            if i > 0 && !is_attached(expr) {
+                self.out.push(' ');
+            }
+
+            self.expr(expr);
+        }
+    }
+
+    fn expr(&mut self, expr: &Expression) {
+        if let Some(matrix) = expr.as_matrix() {
+            return self.matrix(&matrix);
+        }
+
+        // braces with a label are rendered with the label as the second argument
+        if let SimpleExpr::Unary(unary) = &expr.interm {
+            let label = match (unary.kind, &expr.subscript, &expr.supscript) {
+                (UnaryKind::Underbrace, Some(sub), None) => Some(sub),
+                (UnaryKind::Overbrace, None, Some(sup)) => Some(sup),
+                _ => None,
+            };
+
+            if let Some(label) = label {
+                self.call(unary_fn(unary.kind), [&*unary.expr, label]);
+                return;
+            }
+        }
+
+        self.simple(&expr.interm);
+
+        if let Some(sub) = &expr.subscript {
+            self.out.push_str("_(");
+            self.script(sub);
+            self.out.push(')');
+        }
+
+        if let Some(sup) = &expr.supscript {
+            self.out.push_str("^(");
+            self.script(sup);
+            self.out.push(')');
+        }
+    }
+
+    fn simple(&mut self, expr: &SimpleExpr) {
+        match expr {
+            SimpleExpr::Var(var) => self.var(var),
+            SimpleExpr::Grouping(grp) => self.grouping(grp),
+            SimpleExpr::Unary(unary) => self.unary(unary),
+            SimpleExpr::Binary(binary) => self.binary(binary),
+            SimpleExpr::Interm(inner) => self.expr(inner),
+        }
+    }
+
+    /// Renders a function argument. Simple groupings are rendered without the grouping symbols.
+    fn arg(&mut self, expr: &SimpleExpr) {
+        self.args_depth += 1;
+
+        match expr {
+            SimpleExpr::Grouping(grp) if grp.is_simple_grp() => self.exprs(&grp.expr),
+            _ => self.simple(expr),
+        }
+
+        self.args_depth -= 1;
+    }
+
+    /// Renders a subscript or superscript. Groupings are rendered without the grouping symbols.
+    fn script(&mut self, expr: &SimpleExpr) {
+        match expr {
+            SimpleExpr::Grouping(grp) => self.exprs(&grp.expr),
+            _ => self.simple(expr),
+        }
+    }
+
+    fn call<const N: usize>(&mut self, name: &str, args: [&SimpleExpr; N]) {
+        self.out.push_str(name);
+        self.out.push('(');
+
+        for (i, arg) in args.into_iter().enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+
+            self.arg(arg);
+        }
+
+        self.out.push(')');
+    }
+
+    fn var(&mut self, var: &Var) {
+        match &var.kind {
+            VarKind::Function(func) => self.out.push_str(function(*func)),
+            VarKind::Number(num) => self.out.push_str(num),
+            VarKind::Greek(greek) => self.out.push_str(greek_letter(*greek)),
+            VarKind::Variable(var) => {
+                // multi-letter identifiers refer to variables in Typst, so they are split
+                for (i, c) in var.chars().enumerate() {
+                    if i > 0 {
+                        self.out.push(' ');
+                    }
+
+                    self.out.push(c);
+                }
+            }
+            VarKind::Arrow(arrow) => self.out.push_str(self::arrow(*arrow)),
+            VarKind::Relation(rel) => self.out.push_str(relation(*rel)),
+            VarKind::Logical(log) => self.out.push_str(logical(*log)),
+            VarKind::Operator(op) => self.out.push_str(operator(*op)),
+            VarKind::Other(Other::Comma) if self.args_depth > 0 => self.out.push_str("\\,"),
+            VarKind::Other(other) => match self::other(*other) {
+                Some(other_str) => self.out.push_str(other_str),
+                None => self.text(other.as_ref()),
+            },
+            VarKind::Text(text) => self.text(text),
+            VarKind::UnknownOperator(op) if op.is_empty() => self.out.push_str("\"\""),
+            VarKind::UnknownOperator(op) => {
+                for c in op.chars() {
+                    if c.is_ascii_punctuation() {
+                        self.out.push('\\');
+                    }
+
+                    self.out.push(c);
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        self.out.push('"');
+
+        for c in text.chars() {
+            if matches!(c, '"' | '\\') {
+                self.out.push('\\');
+            }
+
+            self.out.push(c);
+        }
+
+        self.out.push('"');
+    }
+
+    /// Renders a grouping. Groupings without a visible delimiter on both sides are wrapped in
+    /// `lr(..)`, with the visible delimiter escaped, so that the output stays balanced and the
+    /// content stays grouped, e.g. as the base of scripts.
+    fn grouping(&mut self, grp: &GroupingExpr) {
+        let left = delimiter(grp.left_grouping, true);
+        let right = delimiter(grp.right_grouping, false);
+        let is_unpaired = left.is_empty() || right.is_empty();
+
+        if is_unpaired {
+            self.out.push_str("lr(");
+            self.lr_delimiter(left);
+        } else {
+            self.out.push_str(left);
+        }
+
+        if left.ends_with(|c: char| c.is_ascii_alphabetic()) {
+            self.out.push(' ');
+        }
+
+        self.exprs(&grp.expr);
+
+        if right.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            self.out.push(' ');
+        }
+
+        if is_unpaired {
+            self.lr_delimiter(right);
+            self.out.push(')');
+        } else {
+            self.out.push_str(right);
+        }
+    }
+
+    /// Writes a delimiter inside `lr(..)`. Brackets are escaped, because the delimiter on the
+    /// other side might not close them.
+    fn lr_delimiter(&mut self, delimiter: &str) {
+        if matches!(delimiter, "(" | ")" | "[" | "]" | "{" | "}") {
+            self.out.push('\\');
+        }
+
+        self.out.push_str(delimiter);
+    }
+
+    fn unary(&mut self, unary: &Unary) {
+        self.call(unary_fn(unary.kind), [&*unary.expr]);
+    }
+
+    fn binary(&mut self, binary: &Binary) {
+        match binary.kind {
+            BinaryKind::Fraction => self.call("frac", [&*binary.expr_1, &*binary.expr_2]),
+            BinaryKind::Root => self.call("root", [&*binary.expr_1, &*binary.expr_2]),
+            BinaryKind::Overset | BinaryKind::Underset => {
+                self.call("limits", [&*binary.expr_2]);
+
+                match binary.kind {
+                    BinaryKind::Overset => self.out.push_str("^("),
+                    _ => self.out.push_str("_("),
+                }
+
+                self.script(&binary.expr_1);
+                self.out.push(')');
+            }
+            BinaryKind::Color => {
+                let color = match &*binary.expr_1 {
+                    SimpleExpr::Var(Var {
+                        kind: VarKind::Text(color),
+                        ..
+                    }) => self::color(color),
+                    _ => None,
+                };
+
+                match color {
+                    Some(color) => {
+                        self.out.push_str("#text(fill: ");
+                        self.out.push_str(&color);
+                        self.out.push_str(")[$");
+                        self.arg(&binary.expr_2);
+                        self.out.push_str("$]");
+                    }
+                    None => self.arg(&binary.expr_2),
+                }
+            }
+        }
+    }
+
+    fn matrix(&mut self, matrix: &Matrix<'_>) {
+        let delim = match (matrix.left_grouping, matrix.right_grouping) {
+            (Grouping::OpenParen, Grouping::CloseParen) => Some("\"(\""),
+            (Grouping::OpenBracket, Grouping::CloseBracket) => Some("\"[\""),
+            (Grouping::OpenBrace, Grouping::CloseBrace) => Some("\"{\""),
+            _ => None,
+        };
+
+        if delim.is_none() {
+            // delimiters that `mat` does not support are placed around the matrix
+            self.out.push_str("lr(");
+            let left = delimiter(matrix.left_grouping, true);
+            if !left.is_empty() {
+                self.lr_delimiter(left);
+                self.out.push(' ');
+            }
+        }
+
+        self.out.push_str("mat(delim: ");
+        self.out.push_str(delim.unwrap_or("#none"));
+        self.out.push_str(", ");
+
+        let columns = matrix.num_of_columns();
+        let lines: Vec<_> = matrix
+            .column_lines
+            .iter()
+            .enumerate()
+            .filter(|(i, &line)| line && i + 1 < columns)
+            .map(|(i, _)| (i + 1).to_string())
+            .collect();
+
+        match lines.as_slice() {
+            [] => {}
+            [line] => {
+                self.out.push_str("augment: #");
+                self.out.push_str(line);
+                self.out.push_str(", ");
+            }
+            lines => {
+                self.out.push_str("augment: #(vline: (");
+                self.out.push_str(&lines.join(", "));
+                self.out.push_str(")), ");
+            }
+        }
+
+        self.args_depth += 1;
+
+        for (i, row) in matrix.rows.iter().enumerate() {
+            if i > 0 {
+                self.out.push_str("; ");
+            }
+
+            for (j, cell) in row.iter().enumerate() {
+                if j > 0 {
+                    self.out.push_str(", ");
+                }
+
+                self.exprs(cell.iter().copied());
+            }
+        }
+
+        self.args_depth -= 1;
+        self.out.push(')');
+
+        if delim.is_none() {
+            let right = delimiter(matrix.right_grouping, false);
+            if !right.is_empty() {
+                self.out.push(' ');
+                self.lr_delimiter(right);
+            }
+
+            self.out.push(')');
+        }
+    }
+}
+
+/// Returns `true` if the expression is written right after the previous one, without a space.
+/// Primes must be directly attached to their base in Typst.
+fn is_attached(expr: &Expression) -> bool {
+    match &expr.interm {
+        SimpleExpr::Var(var) if !expr.is_scripted() => matches!(
+            var.kind,
+            VarKind::Other(Other::Comma) | VarKind::Operator(Operator::Prime)
+        ),
+        _ => false,
+    }
+}
+
+fn unary_fn(kind: UnaryKind) -> &'static str {
+    match kind {
+        UnaryKind::Hat => "hat",
+        UnaryKind::Overline => "overline",
+        UnaryKind::Underline => "underline",
+        UnaryKind::Vector => "arrow",
+        UnaryKind::Tilde => "tilde",
+        UnaryKind::Dot => "dot",
+        UnaryKind::DoubleDot => "dot.double",
+        UnaryKind::Underbrace => "underbrace",
+        UnaryKind::Overbrace => "overbrace",
+        UnaryKind::Cancel => "cancel",
+        UnaryKind::SquareRoot => "sqrt",
+        UnaryKind::Absolute => "abs",
+        UnaryKind::Floor => "floor",
+        UnaryKind::Ceiling => "ceil",
+        UnaryKind::Norm => "norm",
+        UnaryKind::Bold => "bold",
+        UnaryKind::BlackboardBold => "bb",
+        UnaryKind::Calligraphic => "cal",
+        UnaryKind::Typewriter => "mono",
+        UnaryKind::Gothic => "frak",
+        UnaryKind::SansSerif => "sans",
+    }
+}
+
+fn delimiter(grp: Grouping, is_opening: bool) -> &'static str {
+    match grp {
+        Grouping::OpenParen => "(",
+        Grouping::CloseParen => ")",
+        Grouping::OpenBracket => "[",
+        Grouping::CloseBracket => "]",
+        Grouping::OpenBrace => "{",
+        Grouping::CloseBrace => "}",
+        Grouping::LeftAngled => "angle.l",
+        Grouping::RightAngled => "angle.r",
+        Grouping::OpenIgnored | Grouping::CloseIgnored => "",
+        Grouping::Absolute => "bar.v",
+        Grouping::Floor if is_opening => "floor.l",
+        Grouping::Floor => "floor.r",
+        Grouping::Ceiling if is_opening => "ceil.l",
+        Grouping::Ceiling => "ceil.r",
+        Grouping::NormFn | Grouping::Norm => "bar.v.double",
+    }
+}
+
+/// Converts the color given in AsciiMath into a Typst color. Returns `None` for colors that Typst
+/// does not know about.
+fn color(color: &str) -> Option<String> {
+    const NAMED: &[&str] = &[
+        "black", "gray", "silver", "white", "navy", "blue", "aqua", "teal", "eastern", "purple",
+        "fuchsia", "maroon", "red", "orange", "yellow", "olive", "green", "lime",
+    ];
+
+    let color = color.trim();
+
+    if NAMED.contains(&color) {
+        Some(color.to_string())
+    } else if let Some(hex) = color.strip_prefix('#') {
+        // Typst only accepts the `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa` forms
+        let valid =
+            matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit());
+        valid.then(|| format!("rgb(\"{color}\")"))
+    } else {
+        None
+    }
+}
+
+fn function(func: Function) -> &'static str {
+    match func {
+        Function::Sin => "sin",
+        Function::Cos => "cos",
+        Function::Tan => "tan",
+        Function::Sec => "sec",
+        Function::Csc => "csc",
+        Function::Cot => "cot",
+        Function::ArcSin => "arcsin",
+        Function::ArcCos => "arccos",
+        Function::ArcTan => "arctan",
+        Function::SinH => "sinh",
+        Function::CosH => "cosh",
+        Function::TanH => "tanh",
+        Function::SecH => "sech",
+        Function::CscH => "csch",
+        Function::CotH => "coth",
+        Function::Exp => "exp",
+        Function::Lim => "lim",
+        Function::Log => "log",
+        Function::Ln => "ln",
+        Function::Det => "det",
+        Function::Dim => "dim",
+        Function::Mod => "mod",
+        Function::Gcd => "gcd",
+        Function::Lcm => "lcm",
+        Function::Lub => "op(\"lub\")",
+        Function::Glb => "op(\"glb\")",
+        Function::Min => "min",
+        Function::Max => "max",
+        Function::F => "f",
+        Function::G => "g",
+    }
+}
+
+fn greek_letter(greek: Greek) -> &'static str {
+    match greek {
+        Greek::Alpha => "alpha",
+        Greek::Beta => "beta",
+        Greek::Gamma => "gamma",
+        Greek::BigGamma => "Gamma",
+        Greek::Delta => "delta",
+        Greek::Epsilon => "epsilon",
+        Greek::Varepsilon => "epsilon.alt",
+        Greek::Zeta => "zeta",
+        Greek::Eta => "eta",
+        Greek::Theta => "theta",
+        Greek::Vartheta => "theta.alt",
+        Greek::Iota => "iota",
+        Greek::Kappa => "kappa",
+        Greek::Lambda => "lambda",
+        Greek::Mu => "mu",
+        Greek::Nu => "nu",
+        Greek::Xi => "xi",
+        Greek::BigXi => "Xi",
+        Greek::Pi => "pi",
+        Greek::BigPi => "Pi",
+        Greek::Rho => "rho",
+        Greek::Sigma => "sigma",
+        Greek::BigSigma => "Sigma",
+        Greek::Tau => "tau",
+        Greek::Upsilon => "upsilon",
+        Greek::Phi => "phi",
+        Greek::BigPhi => "Phi",
+        Greek::Varphi => "phi.alt",
+        Greek::Chi => "chi",
+        Greek::Psi => "psi",
+        Greek::BigPsi => "Psi",
+        Greek::Omega => "omega",
+        Greek::BigOmega => "Omega",
+    }
+}
+
+fn arrow(arrow: Arrow) -> &'static str {
+    match arrow {
+        Arrow::Up => "arrow.t",
+        Arrow::Down => "arrow.b",
+        Arrow::Right => "arrow.r",
+        Arrow::RightTail => "arrow.r.tail",
+        Arrow::TwoHeadRight => "arrow.r.twohead",
+        Arrow::TwoHeadRightTail => "⤖",
+        Arrow::MapsTo => "arrow.r.bar",
+        Arrow::Left => "arrow.l",
+        Arrow::LeftRight => "arrow.l.r",
+        Arrow::BigRight => "arrow.r.double",
+        Arrow::BigLeft => "arrow.l.double",
+        Arrow::BigLeftRight => "arrow.l.r.double",
+    }
+}
+
+fn relation(rel: Relation) -> &'static str {
+    match rel {
+        Relation::Eq => "=",
+        Relation::NotEq => "!=",
+        Relation::Define => ":=",
+        Relation::LessThan => "<",
+        Relation::GreaterThan => ">",
+        Relation::LessEqualThan => "<=",
+        Relation::GreaterEqualThan => ">=",
+        Relation::MuchLessThan => "<<",
+        Relation::MuchGreaterThan => ">>",
+        Relation::Prec => "prec",
+        Relation::PrecEq => "prec.eq",
+        Relation::Succ => "succ",
+        Relation::SuccEq => "succ.eq",
+        Relation::In => "in",
+        Relation::NotIn => "in.not",
+        Relation::Subset => "subset",
+        Relation::Superset => "supset",
+        Relation::SubsetEq => "subset.eq",
+        Relation::SupersetEq => "supset.eq",
+        Relation::Equivalent => "equiv",
+        Relation::Congruent => "tilde.equiv",
+        Relation::Approximate => "approx",
+        Relation::Prop => "prop",
+    }
+}
+
+fn logical(log: Logical) -> &'static str {
+    match log {
+        Logical::And => "and",
+        Logical::Or => "or",
+        Logical::Not => "not",
+        Logical::Implies => "=>",
+        Logical::If => "\"if\"",
+        Logical::IfAndOnlyIf => "<=>",
+        Logical::ForAll => "forall",
+        Logical::Exists => "exists",
+        Logical::Bottom => "bot",
+        Logical::Top => "top",
+        Logical::VerticalDash => "tack.r",
+        Logical::Models => "models",
+    }
+}
+
+fn operator(op: Operator) -> &'static str {
+    match op {
+        Operator::Plus => "+",
+        Operator::Minus => "-",
+        Operator::Prime => "'",
+        Operator::Dot => "dot.op",
+        Operator::Asterisk => "ast",
+        Operator::Star => "star",
+        Operator::ForwardSlashLiteral => "slash",
+        Operator::Backslash => "without",
+        Operator::Times => "times",
+        Operator::Divide => "div",
+        Operator::LTimes => "times.l",
+        Operator::RTimes => "times.r",
+        Operator::Bowtie => "join",
+        Operator::Circle => "compose",
+        Operator::OPlus => "plus.circle",
+        Operator::OTimes => "times.circle",
+        Operator::ODot => "dot.circle",
+        Operator::Sum => "sum",
+        Operator::Prod => "product",
+        Operator::Wedge => "and",
+        Operator::BigWedge => "and.big",
+        Operator::Cap => "sect",
+        Operator::BigCap => "sect.big",
+        Operator::Cup => "union",
+        Operator::BigCup => "union.big",
+    }
+}
+
+/// Returns the Typst markup for the given symbol, or `None` if the symbol should be rendered as
+/// text.
+fn other(other: Other) -> Option<&'static str> {
+    let other_str = match other {
+        Other::Comma => ",",
+        Other::ForwardSlash => "slash",
+        Other::Power => "\\^",
+        Other::Subscript => "\\_",
+        Other::Integral => "integral",
+        Other::OIntegral => "integral.cont",
+        Other::Partial => "diff",
+        Other::Nabla => "nabla",
+        Other::PlusMinus => "plus.minus",
+        Other::EmptySet => "emptyset",
+        Other::Infinity => "infinity",
+        Other::Aleph => "aleph",
+        Other::Therefore => "therefore",
+        Other::Because => "because",
+        Other::LowDots => "dots.h",
+        Other::CenterDots => "dots.c",
+        Other::VerticalDots => "dots.v",
+        Other::DiagonalDots => "dots.down",
+        Other::VerticalBar => "|",
+        Other::VerticalBars => "bar.v.double",
+        Other::VerticalBarsWide => "| quad |",
+        Other::Angle => "angle",
+        Other::Frown => "frown",
+        Other::Triangle => "triangle.stroked.t",
+        Other::Diamond => "diamond.stroked",
+        Other::Square => "square.stroked",
+        Other::LeftFloor => "floor.l",
+        Other::RightFloor => "floor.r",
+        Other::LeftCeiling => "ceil.l",
+        Other::RightCeiling => "ceil.r",
+        Other::Complex => "CC",
+        Other::Natural => "NN",
+        Other::Rational => "QQ",
+        Other::Irrational => "RR",
+        Other::Integer => "ZZ",
+        Other::Fraction | Other::SquareRoot | Other::Root | Other::Text | Other::Quote => {
+            return None
+        }
+    };
+
+    Some(other_str)
+}
@@ -0,0 +1,467 @@
+//! Rendering into the MathMl elements of [`alemat`], the default [`Renderer`].
+//!
+//! Every function renders one kind of node, and renders the nested nodes with the given
+//! renderer, so renderers producing [`Elements`] can use them for the nodes they don't draw
+//! differently.
+
+use alemat::attributes::MathVariant;
+use alemat::elements::grouping::{Phantom, Row, Style};
+use alemat::elements::radicals::Radical;
+use alemat::elements::scripted::{SubSup, UnderOver};
+use alemat::elements::{
+    ColumnLine, Frac, Ident, IntoElements, Num, Operator, Table, TableAttr, TableCell, TableRow,
+    Text,
+};
+use alemat::{Attribute, Element, Elements, MathMl};
+
+use crate::lexer::keywords::groupings::{Grouping, GrpCtxt};
+use crate::render::keep_spaces;
+use crate::{
+    AsciiMath, Binary, BinaryKind, Expression, GroupingExpr, Matrix, SimpleExpr, Unary, UnaryKind,
+    Var, VarKind,
+};
+
+use super::Renderer;
+
+/// Style of the `<mrow>` that `cancel` is rendered into, striking out the row from the bottom
+/// left to the top right corner.
+const CANCEL_STYLE: &str = "background: linear-gradient(to top right, transparent calc(50% - \
+     0.5px), currentColor calc(50% - 0.5px), currentColor calc(50% + 0.5px), transparent \
+     calc(50% + 0.5px))";
+
+/// Renderer producing the MathMl elements of [`alemat`], as used by
+/// [`render_mathml`](crate::render_mathml).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MathMlRenderer;
+
+impl MathMlHooks for MathMlRenderer {}
+
+/// Hooks of a [`Renderer`] producing MathMl elements. Every hook renders the default MathMl, so
+/// a renderer implements only the hooks of the nodes it draws differently. Every type
+/// implementing the hooks is a [`Renderer`], see the [module](super) documentation.
+pub trait MathMlHooks {
+    /// Renders a variable, see [`Renderer::var`].
+    fn var(&mut self, var: &Var) -> Elements {
+        self::var(var)
+    }
+
+    /// Renders a grouping, see [`Renderer::grouping`].
+    fn grouping(&mut self, grp: &GroupingExpr) -> Elements {
+        self::grouping(self, grp)
+    }
+
+    /// Renders a unary operator, see [`Renderer::unary`].
+    fn unary(&mut self, unary: &Unary) -> Elements {
+        self::unary(self, unary)
+    }
+
+    /// Renders a binary operator, see [`Renderer::binary`].
+    fn binary(&mut self, binary: &Binary) -> Elements {
+        self::binary(self, binary)
+    }
+
+    /// Renders an expression with scripts, see [`Renderer::scripts`].
+    fn scripts(&mut self, expr: &Expression) -> Elements {
+        self::scripts(self, expr)
+    }
+
+    /// Renders a matrix, see [`Renderer::matrix`].
+    fn matrix(&mut self, matrix: &Matrix<'_>) -> Elements {
+        self::matrix(self, matrix)
+    }
+}
+
+impl<T: MathMlHooks + ?Sized> Renderer for T {
+    type Output = Elements;
+
+    fn var(&mut self, var: &Var) -> Elements {
+        MathMlHooks::var(self, var)
+    }
+
+    fn grouping(&mut self, grp: &GroupingExpr) -> Elements {
+        MathMlHooks::grouping(self, grp)
+    }
+
+    fn unary(&mut self, unary: &Unary) -> Elements {
+        MathMlHooks::unary(self, unary)
+    }
+
+    fn binary(&mut self, binary: &Binary) -> Elements {
+        MathMlHooks::binary(self, binary)
+    }
+
+    fn scripts(&mut self, expr: &Expression) -> Elements {
+        MathMlHooks::scripts(self, expr)
+    }
+
+    fn matrix(&mut self, matrix: &Matrix<'_>) -> Elements {
+        MathMlHooks::matrix(self, matrix)
+    }
+}
+
+impl IntoElements for Expression<'_> {
+    fn into_elements(self) -> Elements {
+        MathMlRenderer.expr(&self)
+    }
+}
+
+impl IntoElements for SimpleExpr<'_> {
+    fn into_elements(self) -> Elements {
+        MathMlRenderer.simple(&self)
+    }
+}
+
+impl IntoElements for Var<'_> {
+    fn into_elements(self) -> Elements {
+        var(&self)
+    }
+}
+
+/// The grouping symbols and the expressions between them, without a row.
+impl IntoElements for GroupingExpr<'_> {
+    fn into_elements(self) -> Elements {
+        fenced(&mut MathMlRenderer, &self)
+    }
+}
+
+impl IntoElements for Unary<'_> {
+    fn into_elements(self) -> Elements {
+        Renderer::unary(&mut MathMlRenderer, &self)
+    }
+}
+
+impl IntoElements for Binary<'_> {
+    fn into_elements(self) -> Elements {
+        Renderer::binary(&mut MathMlRenderer, &self)
+    }
+}
+
+impl From<AsciiMath<'_>> for MathMl {
+    fn from(value: AsciiMath<'_>) -> Self {
+        let mut mathml = MathMl::default();
+
+        for expr in value {
+            mathml.append_content(expr);
+        }
+
+        mathml
+    }
+}
+
+/// Renders the variable into an identifier, an operator, a number or a text.
+pub fn var(var: &Var) -> Elements {
+    match &var.kind {
+        VarKind::Function(i) => Ident::builder().ident(i.as_ref()).build().into_elements(),
+        VarKind::Greek(greek) => Ident::from(*greek).into_elements(),
+        VarKind::Variable(i) => Ident::builder().ident(i.as_ref()).build().into_elements(),
+
+        VarKind::Relation(rel) => Operator::from(*rel).into_elements(),
+        VarKind::Logical(log) => Operator::from(*log).into_elements(),
+        VarKind::Operator(op) => Operator::from(*op).into_elements(),
+        VarKind::Arrow(arrow) => Operator::from(*arrow).into_elements(),
+
+        VarKind::Other(ot) => [*ot].into_elements(),
+        VarKind::Text(txt) => Text::from(keep_spaces(txt)).into_elements(),
+        VarKind::Number(num) => Num::from(num.as_ref()).into_elements(),
+        VarKind::UnknownOperator(op) => Operator::from(op.as_ref()).into_elements(),
+    }
+}
+
+/// Renders the grouping into a row of the grouping symbols and the expressions between them.
+pub fn grouping<R>(renderer: &mut R, grp: &GroupingExpr) -> Elements
+where
+    R: Renderer<Output = Elements> + ?Sized,
+{
+    Row::from(fenced(renderer, grp)).into_elements()
+}
+
+/// Renders the argument of an operator, e.g. the `(a + b)` of `sqrt(a + b)`. Parentheses,
+/// brackets and braces around the argument are omitted, other grouping symbols are kept.
+pub fn argument<R>(renderer: &mut R, expr: &SimpleExpr) -> Elements
+where
+    R: Renderer<Output = Elements> + ?Sized,
+{
+    match expr {
+        SimpleExpr::Grouping(grp) if grp.is_simple_grp() => ungrouped(renderer, grp),
+        SimpleExpr::Grouping(grp) => fenced(renderer, grp),
+        _ => renderer.simple(expr),
+    }
+}
+
+/// Renders the unary operator applied to its argument.
+///
+/// alemat has no `<menclose>` element, so `cancel` is rendered as an `<mrow>` striking out its
+/// argument with a CSS style. The MathMl written by [`render_mathml`](crate::render_mathml) uses
+/// `<menclose notation="updiagonalstrike">` instead.
+pub fn unary<R>(renderer: &mut R, unary: &Unary) -> Elements
+where
+    R: Renderer<Output = Elements> + ?Sized,
+{
+    use alemat::children;
+
+    let mut inner = argument(renderer, &unary.expr);
+
+    match unary.kind {
+        UnaryKind::Hat => children![UnderOver::builder()
+            .expr(inner)
+            .over(Operator::hat())
+            .build()]
+        .into_elements(),
+        UnaryKind::Overline => children![UnderOver::builder()
+            .expr(inner)
+            .over(Operator::bar())
+            .build()]
+        .into_elements(),
+        UnaryKind::Underline => children![UnderOver::builder()
+            .expr(inner)
+            .under(Operator::bar())
+            .build()]
+        .into_elements(),
+        UnaryKind::Vector => children![UnderOver::builder()
+            .expr(inner)
+            .over(Operator::rarrow())
+            .build()]
+        .into_elements(),
+        UnaryKind::Tilde => children![UnderOver::builder()
+            .expr(inner)
+            .over(Operator::tilde())
+            .build()]
+        .into_elements(),
+        UnaryKind::Dot => children![UnderOver::builder()
+            .expr(inner)
+            .over(Operator::dot())
+            .build()]
+        .into_elements(),
+        UnaryKind::DoubleDot => children![UnderOver::builder()
+            .expr(inner)
+            .over(Operator::double_dot())
+            .build()]
+        .into_elements(),
+        UnaryKind::Underbrace => children![UnderOver::builder()
+            .expr(inner)
+            .under(Operator::ubrace())
+            .build()]
+        .into_elements(),
+        UnaryKind::Overbrace => children![UnderOver::builder()
+            .expr(inner)
+            .over(Operator::obrace())
+            .build()]
+        .into_elements(),
+        UnaryKind::Cancel => Row::from(inner)
+            .with_attr([Attribute::Style(String::from(CANCEL_STYLE))].into_iter())
+            .into_elements(),
+        UnaryKind::SquareRoot => children![Radical::builder()
+            .content(inner)
+            .index(Num::from(2))
+            .build()]
+        .into_elements(),
+        UnaryKind::Absolute => {
+            let mut el = Operator::vert_bar().into_elements();
+            el.append(&mut inner);
+            el.push(Operator::vert_bar().into());
+            el
+        }
+        UnaryKind::Floor => {
+            let mut el = Operator::lfloor().into_elements();
+            el.append(&mut inner);
+            el.push(Operator::rfloor().into());
+            el
+        }
+        UnaryKind::Ceiling => {
+            let mut el = Operator::lceiling().into_elements();
+            el.append(&mut inner);
+            el.push(Operator::rceiling().into());
+            el
+        }
+        UnaryKind::Norm => {
+            let mut el = Operator::norm().into_elements();
+            el.append(&mut inner);
+            el.push(Operator::norm().into());
+            Row::from(el).into_elements()
+        }
+        UnaryKind::Bold => Style::from(inner)
+            .with_attr([Attribute::MathVariant(MathVariant::Bold)])
+            .into_elements(),
+        UnaryKind::BlackboardBold => Style::from(inner)
+            .with_attr([Attribute::MathVariant(MathVariant::DoubleStruck)])
+            .into_elements(),
+        UnaryKind::Calligraphic => Style::from(inner)
+            .with_attr([Attribute::MathVariant(MathVariant::Script)])
+            .into_elements(),
+        UnaryKind::Typewriter => Style::from(inner)
+            // NOTE: not sure if monospace is the one AsciiMath uses here
+            .with_attr([Attribute::MathVariant(MathVariant::Monospace)])
+            .into_elements(),
+        UnaryKind::Gothic => Style::from(inner)
+            .with_attr([Attribute::MathVariant(MathVariant::Fraktur)])
+            .into_elements(),
+        UnaryKind::SansSerif => Style::from(inner)
+            .with_attr([Attribute::MathVariant(MathVariant::SansSerif)])
+            .into_elements(),
+    }
+}
+
+/// Renders the binary operator applied to its arguments.
+pub fn binary<R>(renderer: &mut R, binary: &Binary) -> Elements
+where
+    R: Renderer<Output = Elements> + ?Sized,
+{
+    match binary.kind {
+        BinaryKind::Fraction => Frac::builder()
+            .num(argument(renderer, &binary.expr_1))
+            .denom(argument(renderer, &binary.expr_2))
+            .build()
+            .into_elements(),
+        BinaryKind::Root => Radical::builder()
+            .index(argument(renderer, &binary.expr_1))
+            .content(argument(renderer, &binary.expr_2))
+            .build()
+            .into_elements(),
+        BinaryKind::Overset => UnderOver::builder()
+            .expr(argument(renderer, &binary.expr_2))
+            .over(argument(renderer, &binary.expr_1))
+            .build()
+            .into_elements(),
+        BinaryKind::Underset => UnderOver::builder()
+            .expr(argument(renderer, &binary.expr_2))
+            .under(argument(renderer, &binary.expr_1))
+            .build()
+            .into_elements(),
+        BinaryKind::Color => {
+            let expr = argument(renderer, &binary.expr_2);
+
+            // the parser reads the color as text, empty colors are removed by sanitization, see
+            // `render::sanitize`
+            match &*binary.expr_1 {
+                SimpleExpr::Var(Var {
+                    kind: VarKind::Text(color),
+                    ..
+                }) if !color.is_empty() => Style::from(expr)
+                    .with_attr([Attribute::MathColor(color.to_string())])
+                    .into_elements(),
+                _ => Style::from(expr).into_elements(),
+            }
+        }
+    }
+}
+
+/// Renders the expression with its subscript and superscript. Big operators, like `sum`, and
+/// braces, like `ubrace`, get their scripts below and above them.
+pub fn scripts<R>(renderer: &mut R, expr: &Expression) -> Elements
+where
+    R: Renderer<Output = Elements> + ?Sized,
+{
+    if !expr.is_scripted() {
+        return renderer.simple(&expr.interm);
+    }
+
+    let mut script = |s: &SimpleExpr| match s {
+        SimpleExpr::Grouping(grp) => ungrouped(renderer, grp),
+        _ => renderer.simple(s),
+    };
+
+    let sub = expr.subscript.as_ref().map(&mut script);
+    let sup = expr.supscript.as_ref().map(&mut script);
+    let inner = renderer.simple(&expr.interm);
+
+    if expr.interm.is_underover() {
+        let builder = UnderOver::builder().expr(inner);
+
+        match (sub, sup) {
+            (None, None) => unreachable!(),
+            (None, Some(sup)) => builder.over(sup).build(),
+            (Some(sub), None) => builder.under(sub).build(),
+            (Some(sub), Some(sup)) => builder.under(sub).over(sup).build(),
+        }
+        .into_elements()
+    } else {
+        let builder = SubSup::builder().base(inner);
+
+        match (sub, sup) {
+            (None, None) => unreachable!(),
+            (None, Some(sup)) => builder.supscript(sup).build(),
+            (Some(sub), None) => builder.subscript(sub).build(),
+            (Some(sub), Some(sup)) => builder.subscript(sub).supscript(sup).build(),
+        }
+        .into_elements()
+    }
+}
+
+/// Renders the matrix into a table between the grouping symbols of the matrix. Columns followed
+/// by vertical bars get solid column lines.
+pub fn matrix<R>(renderer: &mut R, matrix: &Matrix<'_>) -> Elements
+where
+    R: Renderer<Output = Elements> + ?Sized,
+{
+    let mut table = Table::default();
+
+    for row in &matrix.rows {
+        let mut table_row = TableRow::default();
+
+        for cell in row {
+            let cell = match cell.len() {
+                2.. => {
+                    let mut r = Row::default();
+                    for exp in cell {
+                        r.add_elements(renderer.expr(exp));
+                    }
+
+                    TableCell::from(r)
+                }
+                1 => TableCell::from(renderer.expr(cell[0])),
+                _ => TableCell::from(Phantom::from(Elements::default())),
+            };
+
+            table_row.add_cell(cell);
+        }
+
+        table.add_row(table_row);
+    }
+
+    let column_lines = matrix
+        .column_lines
+        .iter()
+        .map(|&line| match line {
+            true => ColumnLine::Solid,
+            false => ColumnLine::None,
+        })
+        .collect();
+
+    table.add_attr([TableAttr::ColumnLines(column_lines)]);
+
+    alemat::row![
+        grouping_symbol(matrix.left_grouping, true),
+        table,
+        grouping_symbol(matrix.right_grouping, false)
+    ]
+    .into_elements()
+}
+
+/// Renders the expressions between the grouping symbols, without the symbols.
+fn ungrouped<R>(renderer: &mut R, grp: &GroupingExpr) -> Elements
+where
+    R: Renderer<Output = Elements> + ?Sized,
+{
+    let mut elements = Elements::default();
+
+    for e in &grp.expr {
+        elements.append(&mut renderer.expr(e));
+    }
+
+    elements
+}
+
+/// Renders the grouping symbols and the expressions between them, without a row.
+fn fenced<R>(renderer: &mut R, grp: &GroupingExpr) -> Elements
+where
+    R: Renderer<Output = Elements> + ?Sized,
+{
+    let mut elements = alemat::children![grouping_symbol(grp.left_grouping, true)].into_elements();
+    elements.append(&mut ungrouped(renderer, grp));
+    elements.push(grouping_symbol(grp.right_grouping, false));
+    elements
+}
+
+fn grouping_symbol(grp: Grouping, is_opening: bool) -> Element {
+    Element::from(GrpCtxt { grp, is_opening })
+}
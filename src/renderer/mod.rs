@@ -0,0 +1,102 @@
+//! Pluggable rendering of the abstract syntax tree.
+//!
+//! A [`Renderer`] has a hook for every kind of node of the tree. The hooks take the nodes by
+//! reference, and render their nested nodes by calling back into the renderer, so a renderer
+//! decides how every node is drawn. [`MathMlRenderer`] is the default implementation, producing
+//! MathMl elements of [`alemat`], and is used by [`render_mathml`](crate::render_mathml).
+//!
+//! The MathMl rendering of every node is also available as a function in the [`mathml`] module,
+//! generic over the renderer. Renderers producing MathMl implement [`MathMlHooks`] instead, which
+//! renders the default MathMl for every hook. That way a renderer changes how a single kind of
+//! node is drawn, and keeps the default for all others, e.g. to draw `abs(x)` as a function
+//! instead of bars:
+//!
+//! ```
+//! # #[cfg(feature = "mathml")] {
+//! use alemat::{elements::Ident, elements::IntoElements, Elements, MathMl};
+//! use mathemascii::{
+//!     renderer::{mathml, MathMlHooks},
+//!     Unary, UnaryKind,
+//! };
+//!
+//! struct AbsAsFunction;
+//!
+//! impl MathMlHooks for AbsAsFunction {
+//!     fn unary(&mut self, unary: &Unary) -> Elements {
+//!         if unary.kind() != UnaryKind::Absolute {
+//!             return mathml::unary(self, unary);
+//!         }
+//!
+//!         let mut elements = Ident::from("abs").into_elements();
+//!         elements.append(&mut mathml::argument(self, unary.expr()));
+//!         elements
+//!     }
+//! }
+//!
+//! let ascii_math = mathemascii::parse("abs(x)");
+//! let elements = mathemascii::render_with(ascii_math, &mut AbsAsFunction);
+//!
+//! let mathml = MathMl::with_content(elements.into_iter().collect::<Elements>());
+//! # }
+//! ```
+
+#[cfg(feature = "mathml")]
+pub mod mathml;
+
+#[cfg(feature = "mathml")]
+pub use mathml::{MathMlHooks, MathMlRenderer};
+
+use crate::{Binary, Expression, GroupingExpr, Matrix, SimpleExpr, Unary, Var};
+
+/// Renderer of the abstract syntax tree, with a hook for every kind of node.
+///
+/// The hooks for expressions, [`Renderer::expr`] and [`Renderer::simple`], dispatch to the hooks
+/// of the nodes they contain by default.
+pub trait Renderer {
+    /// Output of the renderer for a single node.
+    type Output;
+
+    /// Renders the expression, a matrix, a node with a subscript or a superscript, or a node on
+    /// its own.
+    fn expr(&mut self, expr: &Expression) -> Self::Output {
+        if let Some(matrix) = expr.as_matrix() {
+            return self.matrix(&matrix);
+        }
+
+        if expr.is_scripted() {
+            return self.scripts(expr);
+        }
+
+        self.simple(expr.interm())
+    }
+
+    /// Renders the simple expression with the hook of its kind.
+    fn simple(&mut self, expr: &SimpleExpr) -> Self::Output {
+        match expr {
+            SimpleExpr::Var(var) => self.var(var),
+            SimpleExpr::Grouping(grp) => self.grouping(grp),
+            SimpleExpr::Unary(unary) => self.unary(unary),
+            SimpleExpr::Binary(binary) => self.binary(binary),
+            SimpleExpr::Interm(expr) => self.expr(expr),
+        }
+    }
+
+    /// Renders a variable, i.e. a number, an identifier, a symbol or a text.
+    fn var(&mut self, var: &Var) -> Self::Output;
+
+    /// Renders a grouping, i.e. `(a + b)`, including its grouping symbols.
+    fn grouping(&mut self, grp: &GroupingExpr) -> Self::Output;
+
+    /// Renders a unary operator applied to its argument, i.e. `sqrt x` or `abs(x)`.
+    fn unary(&mut self, unary: &Unary) -> Self::Output;
+
+    /// Renders a binary operator applied to its arguments, i.e. `frac(a)(b)` or `a/b`.
+    fn binary(&mut self, binary: &Binary) -> Self::Output;
+
+    /// Renders an expression with a subscript, a superscript or both, i.e. `x_i^2`. The base of
+    /// the expression is [`Expression::interm`].
+    fn scripts(&mut self, expr: &Expression) -> Self::Output;
+
+    /// Renders a matrix or a vector, i.e. `[[a, b], [c, d]]`.
+    fn matrix(&mut self, matrix: &Matrix<'_>) -> Self::Output;
+}
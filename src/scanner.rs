@@ -1,13 +1,14 @@
-use std::ops::Deref;
+use alloc::vec::Vec;
+use core::ops::Deref;
 
 /// Symbol found in the source input.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Symbol<'src> {
     /// Source input of ascii math.
-    src: &'src str,
+    pub(crate) src: &'src str,
     /// Content of this symbols.
     pub(crate) content: &'src str,
-    /// Offset at which the input is found in the input.
+    /// Byte offset at which the symbol is found in the input.
     offs: usize,
 }
 
@@ -16,15 +17,14 @@ where
     S: AsRef<str> + ?Sized,
 {
     let src = src.as_ref();
-    let mut symbols: Vec<_> = src
-        .split("")
-        .skip(1)
-        .enumerate()
-        .map(|(offs, content)| Symbol { src, content, offs })
-        .collect();
-
-    symbols.pop();
-    symbols
+
+    src.char_indices()
+        .map(|(offs, c)| Symbol {
+            src,
+            content: &src[offs..offs + c.len_utf8()],
+            offs,
+        })
+        .collect()
 }
 
 impl<'src> Symbol<'src> {
@@ -43,9 +43,10 @@ impl<'src> Symbol<'src> {
         let first = symbols.first()?;
         let src = first.src;
         let start = first.offs;
-        let end = symbols.last()?.offs;
+        let last = symbols.last()?;
+        let end = last.offs + last.content.len();
 
-        Some(&src[start..=end])
+        Some(&src[start..end])
     }
 
     pub(crate) fn is_digit(&self) -> bool {
@@ -144,6 +145,17 @@ mod tests {
         assert_eq!(Symbol::as_str(&symbols[0..=2]), Some("Hi "));
     }
 
+    #[test]
+    fn test_unicode() {
+        let src = "α ≤ β";
+        let symbols = scan_str(src);
+
+        assert_eq!(symbols.len(), 5);
+        assert_eq!(symbols[2].content, "≤");
+        assert_eq!(symbols[4].offs, 7);
+        assert_eq!(Symbol::as_str(&symbols[2..]), Some("≤ β"));
+    }
+
     #[test]
     #[cfg(debug_assertions)]
     #[should_panic]
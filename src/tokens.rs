@@ -0,0 +1,159 @@
+//! Tokens of AsciiMath source as they're seen by the parser, e.g. for syntax highlighting. Unlike
+//! the parser, the token stream keeps the whitespace between the tokens, so the texts of all the
+//! tokens add up to the whole input.
+//!
+//! ```
+//! use mathemascii::{
+//!     keywords::{Relation, Variant},
+//!     tokens::{self, TokenKind},
+//! };
+//!
+//! let kinds: Vec<_> = tokens::tokenize("x != 2").map(|t| t.kind).collect();
+//!
+//! assert_eq!(
+//!     kinds,
+//!     [
+//!         TokenKind::Variable,
+//!         TokenKind::Whitespace,
+//!         TokenKind::Keyword(Variant::Relation(Relation::NotEq)),
+//!         TokenKind::Whitespace,
+//!         TokenKind::Number,
+//!     ]
+//! );
+//! ```
+
+use crate::{
+    keywords::{Category, Variant},
+    lexer::{self, TokenIterator},
+    Span,
+};
+
+/// Kind of a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// Whitespace between other tokens.
+    Whitespace,
+
+    /// Number, i.e. `42` or `4.2`.
+    Number,
+
+    /// Variable, i.e. `x`.
+    Variable,
+
+    /// Text, including its delimiters, i.e. `"hello"` or `text(hello)`.
+    Text,
+
+    /// Keyword of the AsciiMath grammar, i.e. `alpha` or `->`, or its Unicode form, i.e. `α`.
+    Keyword(Variant),
+
+    /// Symbol that is not a keyword, which the parser treats as an operator, i.e. `;`.
+    UnknownOperator,
+}
+
+impl TokenKind {
+    /// Returns the category of the keyword, or `None` if the token is not a keyword.
+    pub fn category(&self) -> Option<Category> {
+        match self {
+            TokenKind::Keyword(variant) => Some(variant.category()),
+            _ => None,
+        }
+    }
+}
+
+/// Token found in AsciiMath source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Token<'src> {
+    /// Kind of the token.
+    pub kind: TokenKind,
+
+    /// Text of the token, as it's found in the source.
+    pub text: &'src str,
+
+    /// Span of characters occupied by the token.
+    pub span: Span,
+}
+
+/// Iterator over the tokens of AsciiMath source, created with [`tokenize`].
+#[derive(Debug, Clone)]
+pub struct Tokens<'src> {
+    input: &'src str,
+    lexer: TokenIterator<'src>,
+    peeked: Option<lexer::Token<'src>>,
+
+    /// Character and byte offsets of the end of the last token.
+    chars: usize,
+    bytes: usize,
+}
+
+/// Splits the AsciiMath source into tokens, including the whitespace between them.
+pub fn tokenize(input: &str) -> Tokens<'_> {
+    Tokens {
+        input,
+        lexer: TokenIterator::tokenize(input),
+        peeked: None,
+        chars: 0,
+        bytes: 0,
+    }
+}
+
+impl<'src> Tokens<'src> {
+    /// Returns the token of the given kind, ending at the given character offset.
+    fn token(&mut self, kind: TokenKind, end: Option<usize>) -> Token<'src> {
+        let rest = &self.input[self.bytes..];
+
+        let len = match end {
+            Some(end) => rest
+                .char_indices()
+                .nth(end - self.chars)
+                .map_or(rest.len(), |(offs, _)| offs),
+            None => rest.len(),
+        };
+
+        let start = self.chars;
+        let text = &rest[..len];
+
+        self.chars += text.chars().count();
+        self.bytes += len;
+
+        Token {
+            kind,
+            text,
+            span: Span {
+                start,
+                end: self.chars,
+            },
+        }
+    }
+}
+
+impl<'src> Iterator for Tokens<'src> {
+    type Item = Token<'src>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next();
+        }
+
+        match self.peeked {
+            Some(token) if token.span().start > self.chars => {
+                Some(self.token(TokenKind::Whitespace, Some(token.span().start)))
+            }
+            Some(token) => {
+                self.peeked = None;
+                Some(self.token(kind(token), Some(token.span().end)))
+            }
+            None if self.bytes < self.input.len() => Some(self.token(TokenKind::Whitespace, None)),
+            None => None,
+        }
+    }
+}
+
+/// Converts the kind of the token produced by the lexer.
+fn kind(token: lexer::Token<'_>) -> TokenKind {
+    match token.kind() {
+        lexer::TokenKind::Number => TokenKind::Number,
+        lexer::TokenKind::Variable => TokenKind::Variable,
+        lexer::TokenKind::Other(lexer::keywords::others::Other::Text) => TokenKind::Text,
+        kind => Variant::from_token(kind).map_or(TokenKind::UnknownOperator, TokenKind::Keyword),
+    }
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "std")]
+
+use mathemascii::ArtOptions;
+
+macro_rules! test_snap {
+    ($name:ident, $input:literal) => {
+        #[test]
+        fn $name() {
+            let input = $input;
+
+            let unicode =
+                mathemascii::render_art(mathemascii::parse(&input), ArtOptions::default());
+
+            let ascii_options = ArtOptions {
+                ascii: true,
+                ..Default::default()
+            };
+            let ascii = mathemascii::render_art(mathemascii::parse(&input), ascii_options);
+
+            let snap = format!("{}\n\n{}\n\n{}", input, unicode, ascii);
+
+            insta::assert_snapshot!(snap);
+        }
+    };
+}
+
+test_snap!(ubrace, "ubrace(1+2)");
+test_snap!(ubrace_text, r#"ubrace(1+2+3+4)_("4 terms")"#);
+test_snap!(obrace, "obrace(1+2)");
+test_snap!(obrace_text, r#"obrace(1+2+3+4)^("4 terms")"#);
+test_snap!(color_red, "color(red)(x)");
+test_snap!(math_caligraphy, r#"cc "AaBbCc""#);
+test_snap!(math_outlined, r#"bbb "AaBbCc""#);
+test_snap!(math_fraktur, r#"fr "AaBbCc""#);
+test_snap!(matrix_sq, "[[a,b],[c,d]]");
+test_snap!(matrix_aug, "[[a,b,|,c],[d,e,|,f]]");
+test_snap!(matrix_layout, "{(2x,+,17y,=,23),(x,-,y,=,5):}");
+test_snap!(vector, "((a),(b))");
+test_snap!(complex_subscripts, "lim_(N->oo) sum_(i=0)^N");
+test_snap!(integral, "int_0^1 f(x)dx");
+test_snap!(derivative, "f'(x) = dy/dx");
+
+test_snap!(
+    accents,
+    "hat x bar x ul x vec x tilde x dot x ddot x cancel x"
+);
+test_snap!(
+    unary_groupings,
+    "sqrt x abs(x) floor(x) ceil(x) norm(vec x)"
+);
+test_snap!(
+    binary,
+    "frac(a)(b) root(3)(x) overset(x)(=) underset(x)(=) color(#00ff00)(y)"
+);
+test_snap!(
+    groupings,
+    "(a) [b] {c} (:d:) {:e:} (f:} {:g] langle h rangle"
+);
+test_snap!(scripts, "x_i^2 + e^(i pi) = -1");
+test_snap!(sum_of_squares, "sum_(i=0)^n i^2 = (n(n+1)(2n+1))/6");
+test_snap!(nested_radicals, "sqrt(sqrt(a/b)) + root(3)(a/b)");
+test_snap!(
+    tall_groupings,
+    "(a/b) [c/d] {e/f} abs(g/h) langle i/j rangle"
+);
+test_snap!(cases, "abs(x) = {(x, x >= 0),(-x, x < 0):}");
+
+#[test]
+fn wraps_to_width() {
+    let input = "a + b + c + d + e + f + g + h = x/y";
+    let options = ArtOptions {
+        width: 12,
+        ..Default::default()
+    };
+
+    let art = mathemascii::render_art(mathemascii::parse(input), options);
+
+    for line in art.lines() {
+        assert!(line.chars().count() <= 12, "line too wide: {line:?}");
+    }
+
+    insta::assert_snapshot!(format!("{}\n\n{}", input, art));
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "rayon")]
+
+use mathemascii::{BatchOptions, LimitError, Limits};
+
+fn inputs() -> Vec<String> {
+    (0..500)
+        .map(|i| match i % 4 {
+            0 => format!("x^{i}"),
+            1 => String::from("sum_(i=1)^n i"),
+            2 => format!("frac({i})(2"),
+            _ => String::from("sqrt sqrt sqrt x"),
+        })
+        .collect()
+}
+
+#[test]
+fn same_as_render() {
+    let inputs = inputs();
+
+    for dedup in [true, false] {
+        for limits in [Some(Limits::default()), None] {
+            let options = BatchOptions {
+                dedup,
+                limits,
+                ..Default::default()
+            };
+
+            let items = mathemascii::render_batch(&inputs, &options);
+
+            assert_eq!(items.len(), inputs.len());
+
+            for (input, item) in inputs.iter().zip(&items) {
+                let expected = match &options.limits {
+                    Some(limits) => mathemascii::render_mathml_limited(input, limits),
+                    None => Ok(mathemascii::render_mathml(mathemascii::parse(input))),
+                };
+
+                assert_eq!(item.mathml, expected, "input: {input}");
+            }
+        }
+    }
+}
+
+#[test]
+fn diagnostics() {
+    let items = mathemascii::render_batch(&["a/(b", "a/b", "a/(b"], &BatchOptions::default());
+
+    let messages: Vec<Vec<_>> = items
+        .iter()
+        .map(|item| {
+            item.diagnostics
+                .iter()
+                .map(|d| d.message.as_str())
+                .collect()
+        })
+        .collect();
+
+    assert_eq!(
+        messages,
+        [vec!["`(` is not closed"], vec![], vec!["`(` is not closed"]]
+    );
+
+    let options = BatchOptions {
+        diagnostics: false,
+        ..Default::default()
+    };
+
+    let items = mathemascii::render_batch(&["a/(b"], &options);
+    assert!(items[0].diagnostics.is_empty());
+}
+
+#[test]
+fn limited() {
+    let limits = Limits {
+        max_depth: 4,
+        ..Default::default()
+    };
+
+    let options = BatchOptions {
+        limits: Some(limits),
+        ..Default::default()
+    };
+
+    let inputs = [
+        "x^2",
+        "sqrt sqrt sqrt sqrt x",
+        r#"color(red" onclick="alert(1))(x)"#,
+    ];
+    let items = mathemascii::render_batch(&inputs, &options);
+
+    assert_eq!(
+        items[0].mathml.as_deref(),
+        Ok("<math><msup><mi>x</mi><mn>2</mn></msup></math>")
+    );
+    assert_eq!(items[1].mathml, Err(LimitError::TooDeep { max: 4 }));
+    assert!(items[1].diagnostics.is_empty());
+
+    // limited output is sanitized, like with `render_mathml_limited`
+    assert_eq!(
+        items[2].mathml,
+        mathemascii::render_mathml_limited(inputs[2], &limits)
+    );
+    assert!(!items[2].mathml.as_ref().unwrap().contains("onclick"));
+}
+
+#[test]
+fn limited_by_default() {
+    let deep = "(".repeat(100_000);
+    let items = mathemascii::render_batch(&["x", &deep, "cancel x"], &BatchOptions::default());
+
+    assert!(items[0].mathml.is_ok());
+    assert!(items[1].mathml.is_err());
+    assert!(items[2].mathml.as_ref().unwrap().contains("<menclose"));
+}
+
+#[test]
+fn empty() {
+    let inputs: [&str; 0] = [];
+    assert!(mathemascii::render_batch(&inputs, &BatchOptions::default()).is_empty());
+}
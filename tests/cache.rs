@@ -0,0 +1,278 @@
+#![cfg(feature = "std")]
+
+use mathemascii::{ArtOptions, Backend, RenderCache};
+
+fn typst(input: &str) -> String {
+    mathemascii::render_typst(mathemascii::parse(input))
+}
+
+#[test]
+fn same_key_for_aliases_and_whitespace() {
+    let mut cache = RenderCache::new(16);
+
+    for input in ["a -> b", "a rarr  b", "a\trightarrow b", "a->b", "a → b"] {
+        assert_eq!(cache.render(input, &Backend::Typst), typst("a -> b"));
+    }
+
+    assert_eq!(cache.len(), 1);
+
+    for input in ["\"a  b\"", "text(a  b)", "mbox(a  b)"] {
+        assert_eq!(cache.render(input, &Backend::Typst), typst("\"a  b\""));
+    }
+
+    assert_eq!(cache.len(), 2);
+
+    // whitespace in texts and between digits is significant
+    cache.render("\"a b\"", &Backend::Typst);
+    cache.render("1 2", &Backend::Typst);
+    cache.render("12", &Backend::Typst);
+
+    assert_eq!(cache.len(), 5);
+}
+
+#[test]
+fn texts_with_quotes() {
+    let mut cache = RenderCache::new(16);
+
+    for input in [r#"text(say "hi")"#, r#""say \"hi\"""#, "text(a) \"b\""] {
+        assert_eq!(cache.render(input, &Backend::Typst), typst(input));
+    }
+
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn separate_backends_and_options() {
+    let mut cache = RenderCache::new(16);
+    let narrow = ArtOptions {
+        width: 4,
+        ..Default::default()
+    };
+
+    cache.render("x^2 + y^2", &Backend::Typst);
+    cache.render("x^2 + y^2", &Backend::Html);
+    cache.render("x^2 + y^2", &Backend::Art(ArtOptions::default()));
+
+    assert_eq!(
+        cache.render("x^2 + y^2", &Backend::Art(narrow)),
+        mathemascii::render_art(mathemascii::parse("x^2 + y^2"), narrow)
+    );
+    assert_eq!(cache.len(), 4);
+}
+
+#[cfg(feature = "mathml")]
+#[test]
+fn mathml() {
+    let mut cache = RenderCache::new(16);
+    let options = mathemascii::RenderOptions {
+        indent: Some(2),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        cache.render("sum_(i=1)^n i", &Backend::MathMl(options.clone())),
+        mathemascii::render_mathml_with(mathemascii::parse("sum_(i=1)^n i"), &options)
+    );
+    cache.render("sum_(i=1)^n i", &Backend::MathMl(Default::default()));
+    assert_eq!(cache.len(), 2);
+
+    // string options can't be mistaken for each other
+    let alttext = mathemascii::RenderOptions {
+        alttext: Some(String::from("x mathsize=2:1em")),
+        ..Default::default()
+    };
+    let mathsize = mathemascii::RenderOptions {
+        alttext: Some(String::from("x")),
+        mathsize: Some(String::from("1em")),
+        ..Default::default()
+    };
+
+    for options in [alttext, mathsize] {
+        assert_eq!(
+            cache.render("x", &Backend::MathMl(options.clone())),
+            mathemascii::render_mathml_with(mathemascii::parse("x"), &options)
+        );
+    }
+    assert_eq!(cache.len(), 4);
+}
+
+#[test]
+fn evicts_least_recently_used() {
+    let mut cache = RenderCache::new(2);
+
+    cache.render("a", &Backend::Typst);
+    cache.render("b", &Backend::Typst);
+    cache.render("a", &Backend::Typst);
+    cache.render("c", &Backend::Typst);
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("a", &Backend::Typst).is_some());
+    assert!(cache.get("b", &Backend::Typst).is_none());
+    assert!(cache.get("c", &Backend::Typst).is_some());
+
+    // `get` counts as use, too
+    cache.get("a", &Backend::Typst);
+    cache.render("d", &Backend::Typst);
+
+    assert!(cache.get("a", &Backend::Typst).is_some());
+    assert!(cache.get("c", &Backend::Typst).is_none());
+
+    let mut cache = RenderCache::new(0);
+    assert_eq!(cache.render("a", &Backend::Typst), typst("a"));
+    assert_eq!(cache.render("b", &Backend::Typst), typst("b"));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn persisted() {
+    let mut cache = RenderCache::new(16);
+
+    for input in ["a", "b", "\"multi\nline\"", "x^2"] {
+        cache.render(input, &Backend::Art(ArtOptions::default()));
+    }
+
+    let mut data = Vec::new();
+    cache.write_to(&mut data).unwrap();
+
+    // loading into a smaller cache keeps the most recently used entries
+    let mut loaded = RenderCache::read_from(&data[..], 3).unwrap();
+
+    assert_eq!(loaded.len(), 3);
+    assert!(loaded
+        .get("a", &Backend::Art(ArtOptions::default()))
+        .is_none());
+
+    for input in ["b", "\"multi\nline\"", "x^2"] {
+        let output = mathemascii::render_art(mathemascii::parse(input), ArtOptions::default());
+        assert_eq!(
+            loaded.get(input, &Backend::Art(ArtOptions::default())),
+            Some(output.as_str())
+        );
+    }
+}
+
+#[test]
+fn persisted_by_other_version() {
+    let data = "mathemascii render cache 0.0.1\n1 1\nab\n";
+    assert!(RenderCache::read_from(data.as_bytes(), 16)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn invalid_data() {
+    let mut cache = RenderCache::new(16);
+    cache.render("a", &Backend::Typst);
+
+    let mut data = Vec::new();
+    cache.write_to(&mut data).unwrap();
+
+    let truncated = &data[..data.len() - 2];
+    let not_cache = b"<math></math>";
+
+    for data in [truncated, not_cache] {
+        let err = RenderCache::read_from(data, 16).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn file() {
+    let path = std::env::temp_dir().join(format!("mathemascii-cache-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut cache = RenderCache::load(&path, 16).unwrap();
+    assert!(cache.is_empty());
+
+    cache.render("x^2", &Backend::Typst);
+    cache.save(&path).unwrap();
+
+    let mut loaded = RenderCache::load(&path, 16).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        loaded.get("x ^ 2", &Backend::Typst),
+        Some(typst("x^2").as_str())
+    );
+}
+
+/// Renders inputs built from all the spellings of the keywords, so that inputs sharing a key are
+/// rendered into the same cache entry, and compares the cached output with direct rendering.
+#[test]
+fn same_output_as_rendering() {
+    let symbols: Vec<Vec<&str>> = mathemascii::keywords::symbols()
+        .map(|symbol| {
+            symbol
+                .keywords
+                .iter()
+                .copied()
+                .chain(symbol.unicode)
+                .collect()
+        })
+        .chain(["x", "12", "\"t\"", "text(t)", "red", "#f00"].map(|atom| vec![atom]))
+        .collect();
+
+    // xorshift, to generate the same inputs in every run
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut random = |n: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % n as u64) as usize
+    };
+
+    let mut inputs = Vec::new();
+
+    for _ in 0..2000 {
+        let len = 1 + random(4);
+        let picked: Vec<_> = (0..len).map(|_| random(symbols.len())).collect();
+
+        // the same symbols in two different spellings, with or without spaces between them
+        for _ in 0..2 {
+            let separator = [" ", "", "  "][random(3)];
+            let spelled: Vec<_> = picked
+                .iter()
+                .map(|&s| symbols[s][random(symbols[s].len())])
+                .collect();
+            inputs.push(spelled.join(separator));
+        }
+    }
+
+    let backends = [
+        #[cfg(feature = "mathml")]
+        Backend::MathMl(Default::default()),
+        Backend::Typst,
+        Backend::Omml,
+        Backend::Html,
+        Backend::Art(ArtOptions::default()),
+        Backend::Svg(Default::default()),
+    ];
+
+    for backend in &backends {
+        let mut cache = RenderCache::new(inputs.len());
+
+        for input in &inputs {
+            let rendered = match backend {
+                #[cfg(feature = "mathml")]
+                Backend::MathMl(options) => {
+                    mathemascii::render_mathml_with(mathemascii::parse(input), options)
+                }
+                Backend::Typst => typst(input),
+                Backend::Omml => mathemascii::render_omml(mathemascii::parse(input)),
+                Backend::Html => mathemascii::render_html(mathemascii::parse(input)),
+                Backend::Art(options) => {
+                    mathemascii::render_art(mathemascii::parse(input), *options)
+                }
+                Backend::Svg(options) => {
+                    mathemascii::render_svg(mathemascii::parse(input), options)
+                }
+            };
+
+            assert_eq!(
+                cache.render(input, backend),
+                rendered,
+                "rendering {input:?} with {backend:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt::Write;
+
+use mathemascii::{
+    cst::{Child, Cst, Node, NodeKind},
+    keywords::{Grouping, Variant},
+    tokens::{Token, TokenKind},
+    Span,
+};
+
+/// Writes the tree with one line per node and token.
+fn dump(node: &Node<'_>, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    let _ = writeln!(out, "{indent}{:?}", node.kind);
+
+    for child in &node.children {
+        match child {
+            Child::Node(node) => dump(node, depth + 1, out),
+            Child::Token(token) => {
+                let _ = writeln!(out, "{indent}  {:?} {:?}", token.text, token.kind);
+            }
+        }
+    }
+}
+
+macro_rules! test_snap {
+    ($name:ident, $input:literal) => {
+        #[test]
+        fn $name() {
+            let input = $input;
+
+            let mut snap = format!("{}\n\n", input);
+            dump(Cst::parse(input).root(), 0, &mut snap);
+
+            insta::assert_snapshot!(snap);
+        }
+    };
+}
+
+test_snap!(scripts, " sum_(i = 0)^n  x_i ");
+test_snap!(unary_and_binary, r#"sqrt x + frac(a) (b)/ c"#);
+test_snap!(missing_arguments, "root(2) +frac");
+
+#[test]
+fn reproduces_source() {
+    const SNIPPETS: &[&str] = &[
+        "(",
+        ")",
+        "[",
+        ":}",
+        "a",
+        "x",
+        "12.5",
+        "+",
+        "/",
+        "_",
+        "^",
+        ",",
+        " ",
+        "  ",
+        "\t",
+        "\"",
+        "text(",
+        "sum",
+        "sqrt",
+        "root",
+        "frac",
+        "color(red)",
+        "hat",
+        "bb",
+        "alpha",
+        "->",
+        "|",
+        "α",
+        "≤",
+        "é",
+        "[[a,b],[c,d]]",
+    ];
+
+    // small linear congruential generator, to keep the test deterministic
+    let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut next = |n: usize| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) as usize % n
+    };
+
+    for _ in 0..2000 {
+        let len = next(12);
+        let input: String = (0..len).map(|_| SNIPPETS[next(SNIPPETS.len())]).collect();
+
+        let cst = Cst::parse(&input);
+        assert_eq!(cst.to_string(), input);
+
+        let expected: Vec<_> = mathemascii::parse(&input).collect();
+        assert_eq!(cst.to_ast(), expected, "converting {input:?}");
+    }
+}
+
+#[test]
+fn node_spans() {
+    let cst = Cst::parse("a + (b)");
+    let nodes: Vec<_> = cst.root().nodes().collect();
+
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(nodes[2].kind, NodeKind::Expression);
+    assert_eq!(nodes[2].span(), Some(Span { start: 4, end: 7 }));
+    assert_eq!(Cst::parse("").root().span(), None);
+}
+
+#[test]
+fn surgical_edits() {
+    let input = "x_1 != sqrt  x";
+    let name = String::from("y");
+
+    let mut cst = Cst::parse(input);
+
+    cst.root_mut().for_each_token_mut(&mut |token| {
+        if token.text == "!=" {
+            token.text = "ne";
+        } else if token.text == "x" {
+            token.text = &name;
+        }
+    });
+
+    assert_eq!(cst.to_string(), "y_1 ne sqrt  y");
+
+    // wrap the first expression into `abs(...)`
+    let Child::Node(first) = cst.root_mut().children.remove(0) else {
+        panic!("expected an expression");
+    };
+
+    let token = |kind, text| {
+        Child::Token(Token {
+            kind,
+            text,
+            span: Span::default(),
+        })
+    };
+
+    let wrapped = Node {
+        kind: NodeKind::Grouping,
+        children: vec![
+            token(
+                TokenKind::Keyword(Variant::Grouping(Grouping::Absolute)),
+                "abs",
+            ),
+            token(
+                TokenKind::Keyword(Variant::Grouping(Grouping::OpenParen)),
+                "(",
+            ),
+            Child::Node(first),
+            token(
+                TokenKind::Keyword(Variant::Grouping(Grouping::CloseParen)),
+                ")",
+            ),
+        ],
+    };
+
+    cst.root_mut().children.insert(0, Child::Node(wrapped));
+
+    assert_eq!(cst.to_string(), "abs(y_1) ne sqrt  y");
+
+    let expected: Vec<_> = mathemascii::parse("abs(y_1) ne sqrt  y").collect();
+    assert_eq!(cst.to_ast(), expected);
+}
@@ -0,0 +1,209 @@
+use std::ops::Range;
+
+use mathemascii::{Document, Limits};
+
+/// Applies the edit to the document and checks it against parsing the edited source from scratch.
+fn check_edit(doc: &mut Document, range: Range<usize>, text: &str) {
+    let before = doc.source().to_string();
+    doc.edit(range.clone(), text);
+
+    let expected: Vec<_> = mathemascii::parse(doc.source()).collect();
+
+    assert_eq!(
+        doc.exprs(),
+        expected,
+        "editing {before:?} at {range:?} with {text:?} produced {:?}",
+        doc.source()
+    );
+}
+
+#[test]
+fn edit_inside_grouping() {
+    let mut doc = Document::new("sum_(i=0)^(k * 2) a^k + (b / c) d");
+
+    check_edit(&mut doc, 5..6, "j");
+    check_edit(&mut doc, 12..12, " + 1");
+    check_edit(&mut doc, 29..30, "x_1");
+    assert_eq!(doc.source(), "sum_(j=0)^(k + 1 * 2) a^k + (x_1 / c) d");
+}
+
+#[test]
+fn edit_changes_structure() {
+    let mut doc = Document::new("(a + b) c");
+
+    // removing the closing parenthesis makes the grouping span the rest of the input
+    check_edit(&mut doc, 6..7, "");
+    check_edit(&mut doc, 6..6, ")");
+    check_edit(&mut doc, 0..0, "sqrt ");
+    check_edit(&mut doc, 12..12, "/");
+    check_edit(&mut doc, 12..13, "^");
+}
+
+#[test]
+fn edit_keywords_and_texts() {
+    let mut doc = Document::new(r#"alp + "text" + b"#);
+
+    check_edit(&mut doc, 3..3, "ha");
+    check_edit(&mut doc, 8..9, "");
+    check_edit(&mut doc, 8..8, "\"");
+    check_edit(&mut doc, 0..5, "gamma");
+    check_edit(&mut doc, 17..17, "text(a b) ");
+}
+
+#[test]
+#[should_panic]
+fn edit_out_of_bounds() {
+    let mut doc = Document::new("a + b");
+    doc.edit(3..6, "c");
+}
+
+/// Same as [`check_edit`], but for documents parsed with a maximum depth.
+fn check_limited_edit(doc: &mut Document, range: Range<usize>, text: &str, max_depth: usize) {
+    let before = doc.source().to_string();
+    doc.edit(range.clone(), text);
+
+    let limits = Limits {
+        max_depth,
+        ..Limits::UNLIMITED
+    };
+
+    match mathemascii::parse_limited(doc.source(), &limits) {
+        Ok(expected) => assert_eq!(
+            (doc.exprs(), doc.exceeded()),
+            (expected.as_slice(), None),
+            "editing {before:?} at {range:?} with {text:?} produced {:?}",
+            doc.source()
+        ),
+        Err(err) => assert_eq!(
+            doc.exceeded(),
+            Some(err),
+            "editing {before:?} at {range:?} with {text:?} produced {:?}",
+            doc.source()
+        ),
+    }
+}
+
+/// Applies random edits to documents with the given sources, checking every edit.
+fn edit_randomly(
+    sources: &[&str],
+    new: impl Fn(&str) -> Document,
+    check: impl Fn(&mut Document, Range<usize>, &str),
+) {
+    const SNIPPETS: &[&str] = &[
+        "(",
+        ")",
+        "[",
+        "]",
+        "{:",
+        ":}",
+        "a",
+        "x",
+        "d",
+        "12.5",
+        "+",
+        "-",
+        "/",
+        "_",
+        "^",
+        ",",
+        " ",
+        "\"",
+        "text(",
+        "sum",
+        "int",
+        "sqrt",
+        "root(3)",
+        "frac",
+        "color(red)",
+        "hat",
+        "bb",
+        "alpha",
+        "->",
+        "<=",
+        "|",
+        "oo",
+        "[[a,b],[c,d]]",
+        "α",
+        "≤",
+        "→",
+        "é",
+    ];
+
+    // small linear congruential generator, to keep the test deterministic
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |n: usize| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) as usize % n.max(1)
+    };
+
+    for source in sources {
+        let mut doc = new(source);
+
+        for _ in 0..300 {
+            let len = doc.source().chars().count();
+            let start = next(len + 1);
+            let end = (start + next(4)).min(len);
+            let text = if next(3) == 0 {
+                ""
+            } else {
+                SNIPPETS[next(SNIPPETS.len())]
+            };
+
+            check(&mut doc, start..end, text);
+        }
+    }
+}
+
+#[test]
+fn random_edits() {
+    edit_randomly(
+        &[
+            "",
+            "sum_(i=0)^n i^2 = (n(n+1)(2n+1))/6",
+            "int_0^1 f(x)dx + lim_(N->oo) [[a,b],[c,d]]",
+            r#"color(red)(x) "text" sqrt(a/b) (a, (b, c)"#,
+            "∑_(i=0)^n α_i ≤ ∞ \"ünïcode\"",
+        ],
+        |source| Document::new(source),
+        check_edit,
+    );
+}
+
+#[test]
+fn random_edits_within_limits() {
+    const MAX_DEPTH: usize = 6;
+
+    let limits = Limits {
+        max_depth: MAX_DEPTH,
+        ..Limits::default()
+    };
+
+    edit_randomly(
+        &[
+            "sum_(i=0)^n i^2 = (n(n+1)(2n+1))/6",
+            "((((a)))) + sqrt sqrt sqrt sqrt x",
+            "a/((b/(c/d))) (((((((x))))))) y",
+        ],
+        |source| Document::with_limits(source, &limits),
+        |doc, range, text| check_limited_edit(doc, range, text, MAX_DEPTH),
+    );
+}
+
+#[test]
+fn deep_nesting() {
+    let mut doc = Document::with_limits("(".repeat(100_000), &Limits::default());
+    assert!(doc.exceeded().is_some());
+
+    doc.edit(50_000..50_000, "sqrt ");
+    assert!(doc.exceeded().is_some());
+    assert_eq!(doc.exprs().len(), 1);
+
+    doc.edit(0..100_005, "(a) + b");
+    assert_eq!(doc.exceeded(), None);
+    assert_eq!(
+        doc.exprs(),
+        mathemascii::parse("(a) + b").collect::<Vec<_>>()
+    );
+}
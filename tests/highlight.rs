@@ -0,0 +1,54 @@
+#![cfg(feature = "std")]
+
+use mathemascii::{
+    keywords::{Greek, Variant},
+    tokens::{self, TokenKind},
+    Span,
+};
+
+macro_rules! test_snap {
+    ($name:ident, $input:literal) => {
+        #[test]
+        fn $name() {
+            let input = $input;
+
+            let html = mathemascii::highlight_html(input);
+            let ansi = mathemascii::highlight_ansi(input).replace('\x1b', "\\e");
+
+            let snap = format!("{}\n\n{}\n\n{}", input, html, ansi);
+
+            insta::assert_snapshot!(snap);
+        }
+    };
+}
+
+test_snap!(sum, "sum_(i=0)^n i^2 = (n(n+1)(2n+1))/6");
+test_snap!(texts_and_commands, r#"frac(a)(b) + hat x "a < b"  bb(A)"#);
+test_snap!(unicode_and_unknown, "α ≤ β; sin x -> oo");
+
+#[test]
+fn tokens_cover_input() {
+    for input in ["", "  ", " a+ b ", r#"text(a  b) "c"#, "α ≤\tβ\n"] {
+        let text: String = tokens::tokenize(input).map(|t| t.text).collect();
+        assert_eq!(text, input);
+
+        let spans: Vec<_> = tokens::tokenize(input).map(|t| t.span).collect();
+        assert!(spans.windows(2).all(|w| w[0].end == w[1].start));
+    }
+}
+
+#[test]
+fn token_kinds() {
+    let tokens: Vec<_> = tokens::tokenize(r#"α "x" 1;"#).collect();
+
+    assert_eq!(
+        tokens[0].kind,
+        TokenKind::Keyword(Variant::Greek(Greek::Alpha))
+    );
+    assert_eq!(tokens[0].span, Span { start: 0, end: 1 });
+    assert_eq!(tokens[2].kind, TokenKind::Text);
+    assert_eq!(tokens[2].text, r#""x""#);
+    assert_eq!(tokens[4].kind, TokenKind::Number);
+    assert_eq!(tokens[5].kind, TokenKind::UnknownOperator);
+    assert_eq!(tokens[5].span, Span { start: 7, end: 8 });
+}
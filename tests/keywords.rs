@@ -0,0 +1,62 @@
+use mathemascii::keywords::{self, Arrow, Category, Grouping, Relation, Variant};
+
+#[test]
+fn search_prefix() {
+    let sub: Vec<_> = keywords::search("sub")
+        .into_iter()
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(sub, ["sub", "sube", "subset", "subseteq"]);
+
+    let (keyword, ri) = keywords::search("ri")[0];
+    assert_eq!(keyword, "rightarrow");
+    assert_eq!(ri.variant, Variant::Arrow(Arrow::Right));
+    assert_eq!(ri.category(), Category::Arrow);
+    assert_eq!(ri.keywords, ["->", "to", "rarr", "rightarrow"]);
+    assert_eq!(ri.keyword(), "->");
+    assert_eq!(ri.unicode, Some("→"));
+
+    assert!(keywords::search("xyz").is_empty());
+    assert_eq!(
+        keywords::search("").len(),
+        keywords::symbols().map(|s| s.keywords.len()).sum()
+    );
+}
+
+#[test]
+fn lookup_keywords() {
+    let ne = keywords::lookup("ne").unwrap();
+    assert_eq!(ne, keywords::symbol(Relation::NotEq));
+    assert_eq!(ne.unicode, Some("≠"));
+    assert_eq!(ne.category().to_string(), "Relation");
+
+    let sin = keywords::lookup("sin").unwrap();
+    assert_eq!(sin.category(), Category::Function);
+    assert_eq!(sin.unicode, None);
+
+    let close = keywords::symbol(Grouping::RightAngled);
+    assert_eq!(close.keywords, [":)", "rangle", ">>"]);
+    assert_eq!(close.unicode, Some("⟩"));
+
+    assert_eq!(keywords::lookup("x"), None);
+}
+
+#[test]
+fn all_symbols() {
+    let symbols: Vec<_> = keywords::symbols().collect();
+
+    // every symbol is found by each of its keywords
+    for symbol in &symbols {
+        for keyword in symbol.keywords {
+            let found = keywords::search(keyword);
+            assert!(found.contains(&(keyword, *symbol)), "searching {keyword:?}");
+        }
+    }
+
+    assert!(symbols
+        .windows(2)
+        .all(|w| w[0].category() <= w[1].category()));
+    assert!(symbols
+        .iter()
+        .any(|s| s.variant == Variant::Greek(keywords::Greek::Alpha)));
+}
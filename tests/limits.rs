@@ -0,0 +1,140 @@
+use mathemascii::{LimitError, Limits};
+
+#[test]
+#[cfg(feature = "mathml")]
+fn within_limits() {
+    let input = "sum_(i=0)^n i^2 = (n(n+1)(2n+1))/6";
+
+    let exprs = mathemascii::parse_limited(input, &Limits::default()).unwrap();
+    assert_eq!(exprs, mathemascii::parse(input).collect::<Vec<_>>());
+
+    let mathml = mathemascii::render_mathml_limited(input, &Limits::default()).unwrap();
+    assert_eq!(
+        mathml,
+        mathemascii::render_mathml(mathemascii::parse(input))
+    );
+}
+
+#[test]
+#[cfg(feature = "mathml")]
+fn deep_nesting() {
+    let sqrts = "sqrt ".repeat(100_000) + "x";
+    let parens = "(".repeat(100_000);
+
+    for input in [sqrts, parens] {
+        let limits = Limits {
+            max_input_len: usize::MAX,
+            max_tokens: usize::MAX,
+            ..Limits::default()
+        };
+
+        assert_eq!(
+            mathemascii::parse_limited(&input, &limits),
+            Err(LimitError::TooDeep { max: 64 })
+        );
+    }
+
+    // the deepest accepted nesting renders without overflowing the stack
+    let input = "sqrt ".repeat(63) + "x";
+    assert!(mathemascii::render_mathml_limited(&input, &Limits::default()).is_ok());
+}
+
+#[test]
+#[cfg(feature = "mathml")]
+fn every_command_renders() {
+    assert_eq!(
+        mathemascii::render_mathml_limited("cancel x", &Limits::default()).as_deref(),
+        Ok(r#"<math><menclose notation="updiagonalstrike"><mi>x</mi></menclose></math>"#)
+    );
+}
+
+#[test]
+fn exact_depth() {
+    let limits = |max_depth| Limits {
+        max_depth,
+        ..Limits::default()
+    };
+
+    assert!(mathemascii::parse_limited("sqrt sqrt x", &limits(3)).is_ok());
+    assert_eq!(
+        mathemascii::parse_limited("sqrt sqrt x", &limits(2)),
+        Err(LimitError::TooDeep { max: 2 })
+    );
+
+    // grouping, the expression in it and the variable
+    assert!(mathemascii::parse_limited("(x)", &limits(2)).is_ok());
+    assert!(mathemascii::parse_limited("(x)^(y)", &limits(2)).is_ok());
+    assert!(mathemascii::parse_limited("((x))", &limits(2)).is_err());
+}
+
+#[test]
+fn input_length() {
+    let limits = Limits {
+        max_input_len: 8,
+        ..Limits::default()
+    };
+
+    assert!(mathemascii::parse_limited("a + b", &limits).is_ok());
+    assert_eq!(
+        mathemascii::parse_limited("alpha + beta", &limits),
+        Err(LimitError::InputTooLong { max: 8 })
+    );
+}
+
+#[test]
+fn token_count() {
+    let limits = Limits {
+        max_tokens: 3,
+        ..Limits::default()
+    };
+
+    assert!(mathemascii::parse_limited("a  +  b", &limits).is_ok());
+    assert_eq!(
+        mathemascii::parse_limited("a + b + c", &limits),
+        Err(LimitError::TooManyTokens { max: 3 })
+    );
+}
+
+#[test]
+#[cfg(feature = "mathml")]
+fn output_length() {
+    let limits = Limits {
+        max_output_len: 64,
+        ..Limits::default()
+    };
+
+    assert!(mathemascii::render_mathml_limited("x", &limits).is_ok());
+    assert_eq!(
+        mathemascii::render_mathml_limited("sum_(i=0)^n i", &limits),
+        Err(LimitError::OutputTooLong { max: 64 })
+    );
+
+    // the output is checked while it's written, up to the exact length
+    let exact = |max_output_len| Limits {
+        max_output_len,
+        ..Limits::default()
+    };
+
+    let mathml = "<math><mi>x</mi></math>";
+    assert_eq!(
+        mathemascii::render_mathml_limited("x", &exact(mathml.len())).as_deref(),
+        Ok(mathml)
+    );
+    assert_eq!(
+        mathemascii::render_mathml_limited("x", &exact(mathml.len() - 1)),
+        Err(LimitError::OutputTooLong {
+            max: mathml.len() - 1
+        })
+    );
+}
+
+#[test]
+fn unlimited() {
+    let input = "((((((((((x))))))))))".repeat(100);
+
+    let exprs = mathemascii::parse_limited(&input, &Limits::UNLIMITED).unwrap();
+    assert_eq!(
+        exprs,
+        mathemascii::parse(input.as_str()).collect::<Vec<_>>()
+    );
+}
@@ -1,3 +1,7 @@
+#![cfg(feature = "mathml")]
+// the snapshot tests use the deprecated `assert_display_snapshot!`
+#![allow(deprecated)]
+
 macro_rules! test_snap {
     ($name:ident, $input:literal) => {
         #[test]
@@ -24,6 +28,8 @@ test_snap!(ubrace_text, r#"ubrace(1+2+3+4)_("4 terms")"#);
 test_snap!(obrace, "obrace(1+2)");
 test_snap!(obrace_text, r#"obrace(1+2+3+4)^("4 terms")"#);
 test_snap!(color_red, "color(red)(x)");
+test_snap!(color_rgb, "color(rgb(255, 0, 0))(x)");
+test_snap!(cancel, "cancel x + cancel(a+b)");
 test_snap!(math_caligraphy, r#"cc "AaBbCc""#);
 test_snap!(math_outlined, r#"bbb "AaBbCc""#);
 test_snap!(math_fraktur, r#"fr "AaBbCc""#);
@@ -34,6 +40,10 @@ test_snap!(vector, "((a),(b))");
 test_snap!(complex_subscripts, "lim_(N->oo) sum_(i=0)^N");
 test_snap!(integral, "int_0^1 f(x)dx");
 test_snap!(derivative, "f'(x) = dy/dx");
+test_snap!(chained_fractions, "a/b/c");
+test_snap!(repeated_scripts, "x^2_i^3");
+test_snap!(text_parens, "text( f(x) is defined ) = mbox(ok)");
+test_snap!(text_escapes, r#""say \"hi\"" text(\) \\)"#);
 
 #[test]
 fn api_test() {
@@ -83,3 +93,29 @@ fn api_test_3() {
 
     println!("{math_ml}");
 }
+
+#[test]
+fn cancel_keeps_text() {
+    let math_ml = mathemascii::render_mathml(mathemascii::parse(r#"cancel(x "</maction>")"#));
+
+    assert_eq!(
+        math_ml,
+        r#"<math><menclose notation="updiagonalstrike"><mrow><mi>x</mi><mtext></maction></mtext></mrow></menclose></math>"#
+    );
+}
+
+#[test]
+fn cancel_elements() {
+    use alemat::{BufMathMlWriter, Writer};
+
+    // alemat has no `<menclose>`, the row is struck out with a style instead
+    let math_ml = mathemascii::write_mathml(
+        mathemascii::parse("cancel(a+b)"),
+        &mut BufMathMlWriter::default(),
+    )
+    .map(|w| w.finish())
+    .unwrap();
+
+    assert!(math_ml.starts_with(r#"<math><mrow style="background: linear-gradient("#));
+    assert!(math_ml.ends_with("<mi>a</mi><mo>+</mo><mi>b</mi></mrow></math>"));
+}
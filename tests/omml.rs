@@ -0,0 +1,74 @@
+#![cfg(feature = "std")]
+
+macro_rules! test_snap {
+    ($name:ident, $input:literal) => {
+        #[test]
+        fn $name() {
+            use std::str::FromStr;
+            let input = $input;
+
+            let ascii_math = mathemascii::parse(&input);
+            let omml = mathemascii::render_omml(ascii_math);
+
+            xmlem::Document::from_str(&omml).expect(&format!("input: {} is not valid XML.", input));
+
+            let snap = format!("{}\n\n{}", input, pretty(&omml));
+
+            insta::assert_snapshot!(snap);
+        }
+    };
+}
+
+/// Puts every element on its own line, keeping the namespace prefixes which are dropped when
+/// formatting with `xmlem`. Runs are kept on a single line.
+fn pretty(xml: &str) -> String {
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        let end = start + rest[start..].find('>').expect("unterminated tag") + 1;
+        let tag = &rest[start..end];
+
+        if tag.starts_with("<m:r>") || tag.starts_with("<m:r ") {
+            let run_end = rest.find("</m:r>").expect("unterminated run") + "</m:r>".len();
+            out.push_str(&"  ".repeat(depth));
+            out.push_str(&rest[start..run_end]);
+            out.push('\n');
+            rest = &rest[run_end..];
+            continue;
+        }
+
+        if tag.starts_with("</") {
+            depth -= 1;
+        }
+
+        out.push_str(&"  ".repeat(depth));
+        out.push_str(tag);
+        out.push('\n');
+
+        if !tag.starts_with("</") && !tag.ends_with("/>") {
+            depth += 1;
+        }
+
+        rest = &rest[end..];
+    }
+
+    out
+}
+
+test_snap!(ubrace_text, r#"ubrace(1+2+3+4)_("4 terms")"#);
+test_snap!(obrace_text, r#"obrace(1+2+3+4)^("4 terms")"#);
+test_snap!(color_red, "color(red)(x)");
+test_snap!(math_outlined, r#"bbb "AaBbCc""#);
+test_snap!(matrix_sq, "[[a,b],[c,d]]");
+test_snap!(matrix_aug, "[[a,b,|,c],[d,e,|,f]]");
+test_snap!(complex_subscripts, "lim_(N->oo) sum_(i=0)^N a_i");
+test_snap!(integral, "int_0^1 f(x)dx");
+test_snap!(derivative, "f'(x) = dy/dx");
+
+test_snap!(radicals, "sqrt x root(3)(x+1)");
+test_snap!(nary, "prod_(i=1)^n x_i = oint x uuu_k A_k");
+test_snap!(accents, "hat x bar x vec x tilde x dot x ddot x ul x");
+test_snap!(delimiters, "(a, b) [c] abs(x) floor(x) {:d:)");
+test_snap!(escaping, "a < b & c > d");
@@ -0,0 +1,85 @@
+#![cfg(feature = "mathml")]
+
+use mathemascii::{Direction, DisplayMode, RenderOptions};
+
+fn render(input: &str, options: RenderOptions) -> String {
+    mathemascii::render_mathml_with(mathemascii::parse(input), &options)
+}
+
+#[test]
+fn default_options() {
+    for input in [
+        "sum_(i=0)^n i",
+        "(a+b)(c-d)",
+        "[[a,b],[c,d]]",
+        r#"text(x) "y""#,
+    ] {
+        assert_eq!(
+            render(input, RenderOptions::default()),
+            mathemascii::render_mathml(mathemascii::parse(input))
+        );
+    }
+}
+
+#[test]
+fn math_attributes() {
+    let options = RenderOptions {
+        display: DisplayMode::Block,
+        alttext: Some(String::from(r#"a < "b""#)),
+        xmlns: true,
+        dir: Some(Direction::RightToLeft),
+        mathsize: Some(String::from("120%")),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        render("x", options),
+        concat!(
+            r#"<math xmlns="http://www.w3.org/1998/Math/MathML" display="block" "#,
+            r#"alttext="a &lt; &quot;b&quot;" dir="rtl" mathsize="120%"><mi>x</mi></math>"#
+        )
+    );
+}
+
+#[test]
+fn unwrapped_groupings() {
+    let options = || RenderOptions {
+        wrap_groupings: false,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        render("(a+(b))", options()),
+        "<math><mo>(</mo><mi>a</mi><mo>+</mo><mo>(</mo><mi>b</mi><mo>)</mo><mo>)</mo></math>"
+    );
+
+    // the base of a script has to be a single element
+    assert_eq!(
+        render("(a)^2", options()),
+        "<math><msup><mrow><mo>(</mo><mi>a</mi><mo>)</mo></mrow><mn>2</mn></msup></math>"
+    );
+}
+
+#[test]
+fn indented() {
+    let options = RenderOptions {
+        indent: Some(2),
+        ..Default::default()
+    };
+
+    insta::assert_snapshot!(render("sum_(i=0)^(k * 2) a^k \"some text\"", options));
+}
+
+#[test]
+fn safe() {
+    let options = RenderOptions {
+        safe: true,
+        ..Default::default()
+    };
+
+    let input = r#"color(red" onclick="x)(y) "<b>""#;
+    assert_eq!(
+        render(input, options),
+        mathemascii::render_mathml_safe(mathemascii::parse(input))
+    );
+}
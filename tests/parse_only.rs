@@ -0,0 +1,116 @@
+//! Parsing without the `mathml` feature, for consumers that only need the abstract syntax tree.
+
+use std::borrow::Cow;
+
+use mathemascii::{
+    renderer::Renderer, Binary, Expression, GroupingExpr, Matrix, SimpleExpr, Unary, Var, VarKind,
+};
+
+/// Collects the names of the variables of the expressions.
+#[derive(Default)]
+struct Variables(Vec<String>);
+
+impl Renderer for Variables {
+    type Output = ();
+
+    fn var(&mut self, var: &Var) {
+        if let VarKind::Variable(name) = &var.kind {
+            self.0.push(name.to_string());
+        }
+    }
+
+    fn grouping(&mut self, grp: &GroupingExpr) {
+        grp.iter_inner().for_each(|e| self.expr(e));
+    }
+
+    fn unary(&mut self, unary: &Unary) {
+        self.simple(unary.expr());
+    }
+
+    fn binary(&mut self, binary: &Binary) {
+        self.simple(binary.first());
+        self.simple(binary.second());
+    }
+
+    fn scripts(&mut self, expr: &Expression) {
+        self.simple(expr.interm());
+        expr.subscript().into_iter().for_each(|s| self.simple(s));
+        expr.supscript().into_iter().for_each(|s| self.simple(s));
+    }
+
+    fn matrix(&mut self, matrix: &Matrix<'_>) {
+        for cell in matrix.rows.iter().flatten() {
+            cell.iter().for_each(|e| self.expr(e));
+        }
+    }
+}
+
+#[test]
+fn walk_syntax_tree() {
+    let mut variables = Variables::default();
+    let ascii_math = mathemascii::parse("sum_(i=0)^n sqrt(a_i) + frac(x)(y) [[p, q], [r, s]]");
+    mathemascii::render_with(ascii_math, &mut variables);
+
+    assert_eq!(
+        variables.0,
+        ["i", "n", "a", "i", "x", "y", "p", "q", "r", "s"]
+    );
+}
+
+#[test]
+fn parse_without_mathml() {
+    let exprs = mathemascii::parse_limited("x^2 + y", &Default::default()).unwrap();
+    assert_eq!(exprs.len(), 3);
+    assert!(exprs[0].is_scripted());
+}
+
+#[test]
+fn borrows_from_input() {
+    let input = String::from(r#"x_12 "a\"b" color(red)(y)"#);
+    let exprs: Vec<_> = mathemascii::parse(&input).collect();
+
+    let Var { kind, .. } = var(exprs[0].interm());
+    assert!(matches!(kind, VarKind::Variable(Cow::Borrowed("x"))));
+
+    let Var { kind, .. } = var(exprs[0].subscript().unwrap());
+    assert!(matches!(kind, VarKind::Number(Cow::Borrowed("12"))));
+
+    // escapes are replaced, so the text has to be copied
+    let Var { kind, .. } = var(exprs[1].interm());
+    assert!(matches!(kind, VarKind::Text(Cow::Owned(t)) if t == "a\"b"));
+
+    let SimpleExpr::Binary(color) = exprs[2].interm() else {
+        panic!("expected color, got {:?}", exprs[2]);
+    };
+    let Var { kind, .. } = var(color.first());
+    assert!(matches!(kind, VarKind::Text(Cow::Borrowed("red"))));
+
+    let owned: Vec<Expression<'static>> = exprs.into_iter().map(Expression::into_owned).collect();
+    drop(input);
+
+    assert_eq!(
+        owned,
+        mathemascii::parse(r#"x_12 "a\"b" color(red)(y)"#).collect::<Vec<_>>()
+    );
+}
+
+#[cfg(not(feature = "mathml"))]
+#[test]
+fn limits_without_mathml() {
+    let limits = mathemascii::Limits {
+        max_depth: 2,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        mathemascii::parse_limited("sqrt sqrt sqrt x", &limits),
+        Err(mathemascii::LimitError::TooDeep { max: 2 })
+    );
+}
+
+fn var<'e>(expr: &'e SimpleExpr<'e>) -> &'e Var<'e> {
+    match expr {
+        SimpleExpr::Var(var) => var,
+        _ => panic!("expected variable, got {expr:?}"),
+    }
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use mathemascii::{Config, LimitError, Limits, ParsedMath, Parser};
+
+const INPUT: &str = r#"sum_(i=0)^n i^2 = (n(n+1)(2n+1))/6 "for" color(red)(n in NN)"#;
+
+#[test]
+fn send_and_sync() {
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+    assert_send_sync::<Parser>();
+    assert_send_sync::<ParsedMath>();
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn renders_like_free_functions() {
+    use mathemascii::{ArtOptions, SvgOptions};
+
+    let math = Parser::default().parse(INPUT).unwrap();
+    let parse = || mathemascii::parse(INPUT);
+
+    assert_eq!(math.exprs(), parse().collect::<Vec<_>>());
+    assert_eq!(math.render_typst(), mathemascii::render_typst(parse()));
+    assert_eq!(math.render_omml(), mathemascii::render_omml(parse()));
+    assert_eq!(math.render_html(), mathemascii::render_html(parse()));
+    assert_eq!(
+        math.render_art(ArtOptions::default()),
+        mathemascii::render_art(parse(), ArtOptions::default())
+    );
+    assert_eq!(
+        math.render_svg(&SvgOptions::default()),
+        mathemascii::render_svg(parse(), &SvgOptions::default())
+    );
+
+    // rendering doesn't consume the expressions
+    assert_eq!(math.render_typst(), math.render_typst());
+}
+
+#[test]
+#[cfg(feature = "mathml")]
+fn renders_mathml_like_free_functions() {
+    let math = Parser::default().parse(INPUT).unwrap();
+    let parse = || mathemascii::parse(INPUT);
+
+    assert_eq!(math.render_mathml(), mathemascii::render_mathml(parse()));
+    assert_eq!(
+        math.render_mathml_safe(),
+        mathemascii::render_mathml_safe(parse())
+    );
+    assert_eq!(math.render_mathml(), math.render_mathml());
+}
+
+#[test]
+fn clones_share_expressions() {
+    let math = Parser::default().parse(INPUT).unwrap();
+    let clone = math.clone();
+
+    assert_eq!(math, clone);
+    assert!(std::ptr::eq(math.exprs(), clone.exprs()));
+}
+
+#[test]
+fn shared_between_threads() {
+    let parser = Arc::new(Parser::new(Config {
+        limits: Limits::default(),
+    }));
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let parser = Arc::clone(&parser);
+            std::thread::spawn(move || parser.parse(&format!("x^{i}")).unwrap())
+        })
+        .collect();
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        let math = handle.join().unwrap();
+        assert_eq!(math, Parser::default().parse(&format!("x^{i}")).unwrap());
+    }
+}
+
+#[test]
+fn configured_limits() {
+    let parser = Parser::new(Config {
+        limits: Limits {
+            max_depth: 4,
+            ..Limits::default()
+        },
+    });
+
+    assert!(parser.parse("sqrt x").is_ok());
+    assert_eq!(
+        parser.parse("sqrt sqrt sqrt sqrt x"),
+        Err(LimitError::TooDeep { max: 4 })
+    );
+
+    let input = "sqrt ".repeat(100) + "x";
+    assert!(Parser::default().parse(&input).is_ok());
+}
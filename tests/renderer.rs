@@ -0,0 +1,148 @@
+#![cfg(feature = "mathml")]
+
+use alemat::{
+    elements::{Ident, IntoElements},
+    Elements, MathMl,
+};
+use mathemascii::{
+    keywords::{Operator, Relation},
+    renderer::{mathml, MathMlHooks, MathMlRenderer, Renderer},
+    Binary, BinaryKind, Expression, GroupingExpr, Matrix, Unary, UnaryKind, Var, VarKind,
+};
+
+fn mathml(elements: Vec<Elements>) -> String {
+    let elements: Elements = elements.into_iter().flat_map(|e| e.to_vec()).collect();
+    MathMl::with_content(elements).render().unwrap()
+}
+
+#[test]
+fn mathml_renderer() {
+    for input in [
+        "sum_(i=0)^n i^2 = (n(n+1)(2n+1))/6",
+        "[[a, b | c], [d, e | f]]",
+        "color(red)(x) + sqrt(abs(x)) + root(3)(y)",
+        "ubrace(1 + 2)_(3) + {(x, x > 0), (-x, x <= 0):}",
+        r#"bb(A) "text" overset(a)(b)"#,
+    ] {
+        let elements = mathemascii::render_with(mathemascii::parse(input), &mut MathMlRenderer);
+
+        assert_eq!(
+            mathml(elements),
+            mathemascii::render_mathml(mathemascii::parse(input))
+        );
+    }
+}
+
+struct AbsAsFunction;
+
+impl MathMlHooks for AbsAsFunction {
+    fn unary(&mut self, unary: &Unary) -> Elements {
+        if unary.kind() != UnaryKind::Absolute {
+            return mathml::unary(self, unary);
+        }
+
+        let mut elements = Ident::from("abs").into_elements();
+        elements.append(&mut mathml::grouping(self, unary_grouping(unary)));
+        elements
+    }
+}
+
+fn unary_grouping<'e>(unary: &'e Unary<'e>) -> &'e GroupingExpr<'e> {
+    match unary.expr() {
+        mathemascii::SimpleExpr::Grouping(grp) => grp,
+        _ => panic!("Expected grouping."),
+    }
+}
+
+#[test]
+fn overridden_hook() {
+    let elements = mathemascii::render_with(mathemascii::parse("sqrt(abs(x))"), &mut AbsAsFunction);
+
+    assert_eq!(
+        mathml(elements),
+        concat!(
+            "<math><msqrt><mi>abs</mi><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow>",
+            "</msqrt></math>"
+        )
+    );
+}
+
+/// Renders the expressions back into AsciiMath, with explicit fractions.
+struct Source;
+
+impl Renderer for Source {
+    type Output = String;
+
+    fn var(&mut self, var: &Var) -> String {
+        match &var.kind {
+            VarKind::Number(s) | VarKind::Variable(s) | VarKind::UnknownOperator(s) => {
+                s.to_string()
+            }
+            VarKind::Text(s) => format!("{s:?}"),
+            VarKind::Operator(Operator::Plus) => String::from("+"),
+            VarKind::Relation(Relation::Eq) => String::from("="),
+            _ => String::from("?"),
+        }
+    }
+
+    fn grouping(&mut self, grp: &GroupingExpr) -> String {
+        let inner: Vec<_> = grp.iter_inner().map(|e| self.expr(e)).collect();
+        format!("({})", inner.join(" "))
+    }
+
+    fn unary(&mut self, unary: &Unary) -> String {
+        format!("{:?} {}", unary.kind(), self.simple(unary.expr()))
+    }
+
+    fn binary(&mut self, binary: &Binary) -> String {
+        let name = match binary.kind() {
+            BinaryKind::Fraction => "frac",
+            _ => "binary",
+        };
+
+        format!(
+            "{name}{}{}",
+            self.simple(binary.first()),
+            self.simple(binary.second())
+        )
+    }
+
+    fn scripts(&mut self, expr: &Expression) -> String {
+        let mut out = self.simple(expr.interm());
+
+        if let Some(sub) = expr.subscript() {
+            out += &format!("_{}", self.simple(sub));
+        }
+
+        if let Some(sup) = expr.supscript() {
+            out += &format!("^{}", self.simple(sup));
+        }
+
+        out
+    }
+
+    fn matrix(&mut self, matrix: &Matrix<'_>) -> String {
+        format!("matrix {}x{}", matrix.rows.len(), matrix.num_of_columns())
+    }
+}
+
+#[test]
+fn custom_output() {
+    let output = mathemascii::render_with(
+        mathemascii::parse(r#"frac(a)(b) + x_i^2 = sqrt 2 [[1, 2], [3, 4]] "end""#),
+        &mut Source,
+    );
+
+    assert_eq!(
+        output,
+        [
+            "frac(a)(b)",
+            "+",
+            "x_i^2",
+            "=",
+            "SquareRoot 2",
+            "matrix 2x2",
+            r#""end""#
+        ]
+    );
+}
@@ -0,0 +1,157 @@
+#![cfg(feature = "mathml")]
+
+use std::str::FromStr;
+
+use mathemascii::keywords;
+
+/// Inputs trying to inject markup into the output.
+const HOSTILE: &[&str] = &[
+    r#""<script>alert(1)</script>""#,
+    r#"text(<img src=x onerror=alert(1)>)"#,
+    r#"color(red" onclick="alert(1))(x)"#,
+    r#"color(red"><script>alert(1)</script>)(x)"#,
+    r#"color(</mstyle><script>alert(1)</script>)(x)"#,
+    r#"color(javascript:alert(1))(x)"#,
+    r#"color(url(javascript:alert(1)))(x)"#,
+    r#"color(rgb(0,0,0)" onclick="alert(1))(x)"#,
+    r#""&lt;script&gt;" "&#x3C;script&#x3E;" &amp;"#,
+    r#"a & b < c > d ' e"#,
+    r#"text(]]><script>alert(1)</script>) <!-- x -->"#,
+    r#""<svg/onload=alert(1)>" sqrt("<iframe>") frac("<a>")(b)"#,
+    "\"a\u{0}b\u{7}c\u{1b}[31m\" x\u{8}y",
+];
+
+fn render(input: &str) -> String {
+    mathemascii::render_mathml_safe(mathemascii::parse(input))
+}
+
+/// Checks that the output of the input is well-formed, so nothing broke out of its element or
+/// attribute, and has only MathMl elements and attributes.
+fn assert_safe(input: &str) {
+    let output = render(input);
+
+    let doc = xmlem::Document::from_str(&output)
+        .unwrap_or_else(|_| panic!("input: {input} produced invalid XML: {output}"));
+
+    let mut elements = vec![doc.root()];
+    while let Some(element) = elements.pop() {
+        let name = element.name(&doc);
+        assert!(
+            name.starts_with('m'),
+            "unexpected element {name} in {output}"
+        );
+
+        for (attr, value) in element.attributes(&doc) {
+            assert!(
+                matches!(
+                    attr.prefixed_name(),
+                    "mathcolor" | "mathvariant" | "stretchy" | "columnlines" | "notation"
+                ),
+                "unexpected attribute {attr}={value} in {output}"
+            );
+        }
+
+        elements.extend(element.children(&doc));
+    }
+
+    assert!(!output.contains("<script"), "{output}");
+    assert!(!output.contains("<!--"), "{output}");
+    assert!(!output.chars().any(char::is_control), "{output}");
+}
+
+#[test]
+fn hostile_inputs() {
+    for input in HOSTILE {
+        assert_safe(input);
+    }
+}
+
+#[test]
+fn every_node() {
+    for symbol in keywords::symbols() {
+        for k in symbol.keywords {
+            // every keyword alone, as command, script, grouping, matrix cell and argument
+            let input = format!(
+                r#"{k} {k} "<b>" {k}(text(<i>))("&") x_{k}^{k} ({k}) [[{k},"<"],["&",{k}]] ((a),({k})) color(red)({k}) cancel {k} "&lt;"/{k} {k}_x^y"#
+            );
+
+            assert_safe(&input);
+        }
+    }
+}
+
+#[test]
+fn escaped_content() {
+    assert_eq!(
+        render(r#""<b>&amp;</b>""#),
+        "<math><mtext>&lt;b&gt;&amp;amp;&lt;/b&gt;</mtext></math>"
+    );
+    assert_eq!(
+        render("a & b"),
+        "<math><mi>a</mi><mo>&amp;</mo><mi>b</mi></math>"
+    );
+    assert_eq!(
+        render("\"a\u{0}b\u{1b}\""),
+        "<math><mtext>ab</mtext></math>"
+    );
+    assert_eq!(
+        render("a < b mlt c"),
+        "<math><mi>a</mi><mo>&lt;</mo><mi>b</mi><mo>m&lt;</mo><mi>c</mi></math>"
+    );
+}
+
+#[test]
+fn colors() {
+    let colored = |color: &str| {
+        let output = render(&format!("color({color})(x)"));
+        output
+            .split_once("mathcolor=\"")
+            .map(|(_, rest)| rest.split('"').next().unwrap().to_string())
+    };
+
+    for valid in [
+        "red",
+        "RebeccaPurple",
+        "transparent",
+        "#f00",
+        "#ff000080",
+        "rgb(255, 0, 0)",
+        "hsl(0 100% 50% / 0.5)",
+    ] {
+        assert_eq!(
+            colored(valid),
+            Some(valid.replace(' ', "")),
+            "{valid} is a valid color"
+        );
+    }
+
+    for invalid in [
+        "red\" onclick=\"x",
+        "#ff",
+        "#gg0000",
+        "red2",
+        "javascript",
+        "redd",
+        "url(x)",
+        "expression(alert(1))",
+        "",
+    ] {
+        assert_eq!(colored(invalid), None, "{invalid} is not a valid color");
+        assert!(render(&format!("color({invalid})(x)")).contains("<mi>x</mi>"));
+    }
+}
+
+#[test]
+fn same_as_unsafe_for_trusted_input() {
+    for input in [
+        "sum_(i=0)^n i^2 = (n(n+1)(2n+1))/6",
+        "color(red)(x) + color(#00ff00)(y)",
+        r#"text(f(x) is defined) "for all" x in RR"#,
+        "[[a,b],[c,d]]",
+    ] {
+        assert_eq!(
+            render(input),
+            mathemascii::render_mathml(mathemascii::parse(input))
+        );
+    }
+}
@@ -0,0 +1,216 @@
+#![cfg(feature = "mathml")]
+
+use std::{fs, path::Path};
+
+use alemat::{Element, Elements, MathMl};
+use mathemascii::{Direction, DisplayMode, RenderOptions};
+
+/// Collects the lines of all snapshots as inputs. Most snapshots start with their input, the
+/// rest are still valid, if unusual, AsciiMath.
+fn corpus() -> Vec<String> {
+    fn visit(dir: &Path, inputs: &mut Vec<String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                visit(&path, inputs);
+            } else if path.extension().is_some_and(|ext| ext == "snap") {
+                let snapshot = fs::read_to_string(&path).unwrap();
+                let body = snapshot.splitn(3, "---\n").nth(2).unwrap_or_default();
+
+                inputs.extend(body.lines().filter(|l| !l.is_empty()).map(String::from));
+                inputs.push(body.to_string());
+            }
+        }
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut inputs = Vec::new();
+
+    visit(&root.join("src"), &mut inputs);
+    visit(&root.join("tests"), &mut inputs);
+
+    assert!(inputs.len() > 200);
+    inputs
+}
+
+fn options() -> Vec<RenderOptions> {
+    vec![
+        RenderOptions::default(),
+        RenderOptions {
+            display: DisplayMode::Block,
+            alttext: Some(String::from("a < \"b\"")),
+            dir: Some(Direction::RightToLeft),
+            mathsize: Some(String::from("2em")),
+            xmlns: true,
+            ..Default::default()
+        },
+        RenderOptions {
+            indent: Some(2),
+            ..Default::default()
+        },
+        RenderOptions {
+            wrap_groupings: false,
+            ..Default::default()
+        },
+        RenderOptions {
+            wrap_groupings: false,
+            indent: Some(4),
+            safe: true,
+            ..Default::default()
+        },
+        RenderOptions {
+            safe: true,
+            ..Default::default()
+        },
+    ]
+}
+
+/// Inputs exercising every keyword, alone and with arguments and scripts.
+fn keywords() -> Vec<String> {
+    mathemascii::keywords::symbols()
+        .flat_map(|symbol| symbol.keywords)
+        .flat_map(|kw| {
+            [
+                kw.to_string(),
+                format!("{kw} x"),
+                format!("{kw}(a+b)(c d)"),
+                format!("{kw}_1^2 x y"),
+                format!("(a, {kw}) {kw}|a|b"),
+            ]
+        })
+        .collect()
+}
+
+/// Renders the input with the elements of alemat, as the MathMl was written before it was
+/// written directly.
+fn baseline(input: &str, wrap_groupings: bool) -> String {
+    fn unwrap_rows(elements: Elements) -> Elements {
+        let mut unwrapped = Elements::default();
+
+        for element in elements.iter() {
+            match element {
+                Element::Row(row) if row.attributes().is_empty() => {
+                    let children = row.children().iter().cloned().collect();
+                    unwrapped.append(&mut unwrap_rows(children));
+                }
+                _ => unwrapped.push(element.clone()),
+            }
+        }
+
+        unwrapped
+    }
+
+    let mathml = MathMl::from(mathemascii::parse(input));
+
+    match wrap_groupings {
+        true => mathml.render().unwrap(),
+        false => mathml.map(unwrap_rows).render().unwrap(),
+    }
+}
+
+#[test]
+fn same_as_alemat() {
+    // `cancel` has no element in alemat
+    let inputs = corpus().into_iter().chain(keywords());
+    let inputs = inputs.filter(|input| !input.contains("cancel"));
+
+    for input in inputs {
+        for wrap_groupings in [true, false] {
+            let options = RenderOptions {
+                wrap_groupings,
+                ..Default::default()
+            };
+
+            let mut streamed = String::new();
+            mathemascii::stream_mathml(mathemascii::parse(&input), &options, &mut streamed)
+                .unwrap();
+
+            assert_eq!(
+                streamed,
+                baseline(&input, wrap_groupings),
+                "input: {input}, options: {options:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn indented() {
+    let strip = |markup: &str| markup.split_whitespace().collect::<String>();
+
+    for input in corpus().iter().chain(&keywords()) {
+        for options in options() {
+            let indented = RenderOptions {
+                indent: Some(3),
+                ..options.clone()
+            };
+
+            let mut streamed = String::new();
+            mathemascii::stream_mathml(mathemascii::parse(input), &indented, &mut streamed)
+                .unwrap();
+
+            let unindented = RenderOptions {
+                indent: None,
+                ..options
+            };
+
+            assert_eq!(
+                strip(&streamed),
+                strip(&mathemascii::render_mathml_with(
+                    mathemascii::parse(input),
+                    &unindented
+                )),
+                "input: {input}, options: {indented:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn empty() {
+    for options in options() {
+        let mut streamed = String::new();
+        mathemascii::stream_mathml(mathemascii::parse(""), &options, &mut streamed).unwrap();
+
+        assert_eq!(
+            streamed,
+            mathemascii::render_mathml_with(mathemascii::parse(""), &options)
+        );
+    }
+}
+
+#[test]
+fn io_sink() {
+    let input = "sum_(i=1)^n i^3=((n(n+1))/2)^2";
+    let options = RenderOptions::default();
+
+    let mut streamed = Vec::new();
+    mathemascii::stream_mathml_io(mathemascii::parse(input), &options, &mut streamed).unwrap();
+
+    assert_eq!(
+        String::from_utf8(streamed).unwrap(),
+        mathemascii::render_mathml_with(mathemascii::parse(input), &options)
+    );
+}
+
+#[test]
+fn io_error() {
+    struct Full;
+
+    impl std::io::Write for Full {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::StorageFull.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let err =
+        mathemascii::stream_mathml_io(mathemascii::parse("x"), &Default::default(), &mut Full)
+            .unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::StorageFull);
+}
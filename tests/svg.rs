@@ -0,0 +1,94 @@
+#![cfg(feature = "std")]
+
+use mathemascii::SvgOptions;
+
+macro_rules! test_snap {
+    ($name:ident, $input:literal) => {
+        #[test]
+        fn $name() {
+            let input = $input;
+
+            let ascii_math = mathemascii::parse(&input);
+            let svg = mathemascii::render_svg(ascii_math, &SvgOptions::default());
+
+            let snap = format!("{}\n\n{}", input, pretty(&svg));
+
+            insta::assert_snapshot!(snap);
+        }
+    };
+}
+
+/// Validates the SVG and puts every element on its own line.
+fn pretty(svg: &str) -> String {
+    use std::str::FromStr;
+
+    xmlem::Document::from_str(svg).unwrap_or_else(|_| panic!("{svg} is not valid XML."));
+
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut rest = svg;
+
+    while let Some(start) = rest.find('<') {
+        let end = start + rest[start..].find('>').expect("unterminated tag") + 1;
+        let tag = &rest[start..end];
+
+        if tag.starts_with("</") {
+            depth -= 1;
+        }
+
+        out.push_str(&"  ".repeat(depth));
+        out.push_str(tag);
+        out.push('\n');
+
+        if !tag.starts_with("</") && !tag.ends_with("/>") {
+            depth += 1;
+        }
+
+        rest = &rest[end..];
+    }
+
+    out
+}
+
+test_snap!(ubrace_text, r#"ubrace(1+2+3+4)_("4 terms")"#);
+test_snap!(obrace_text, r#"obrace(1+2+3+4)^("4 terms")"#);
+test_snap!(color_red, "color(red)(x)");
+test_snap!(math_caligraphy, r#"cc "AaBbCc""#);
+test_snap!(matrix_sq, "[[a,b],[c,d]]");
+test_snap!(matrix_aug, "[[a,b,|,c],[d,e,|,f]]");
+test_snap!(matrix_layout, "{(2x,+,17y,=,23),(x,-,y,=,5):}");
+test_snap!(vector, "((a),(b))");
+test_snap!(complex_subscripts, "lim_(N->oo) sum_(i=0)^N");
+test_snap!(integral, "int_0^1 f(x)dx");
+test_snap!(derivative, "f'(x) = dy/dx");
+test_snap!(scripts, "x_i^2 + e^(i pi) = -1");
+test_snap!(
+    accents,
+    "hat x bar x ul x vec x tilde x dot x ddot x cancel x"
+);
+test_snap!(
+    unary_groupings,
+    "sqrt x abs(x) floor(x) ceil(x) norm(vec x)"
+);
+test_snap!(
+    binary,
+    "frac(a)(b) root(3)(x) overset(x)(=) underset(x)(=) color(#00ff00)(y)"
+);
+test_snap!(
+    tall_groupings,
+    "(a/b) [c/d] {e/f} abs(g/h) langle i/j rangle"
+);
+test_snap!(escapes, r#""a < b & c""#);
+
+#[test]
+fn font_size_and_color() {
+    let input = "sqrt(x/2)";
+    let options = SvgOptions {
+        font_size: 32.0,
+        color: String::from("#336699"),
+    };
+
+    let svg = mathemascii::render_svg(mathemascii::parse(input), &options);
+
+    insta::assert_snapshot!(format!("{}\n\n{}", input, pretty(&svg)));
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "std")]
+
+macro_rules! test_snap {
+    ($name:ident, $input:literal) => {
+        #[test]
+        fn $name() {
+            let input = $input;
+
+            let ascii_math = mathemascii::parse(&input);
+            let typst = mathemascii::render_typst(ascii_math);
+
+            let snap = format!("{}\n\n{}", input, typst);
+
+            insta::assert_snapshot!(snap);
+        }
+    };
+}
+
+test_snap!(ubrace, "ubrace(1+2)");
+test_snap!(ubrace_text, r#"ubrace(1+2+3+4)_("4 terms")"#);
+test_snap!(obrace, "obrace(1+2)");
+test_snap!(obrace_text, r#"obrace(1+2+3+4)^("4 terms")"#);
+test_snap!(color_red, "color(red)(x)");
+test_snap!(
+    color_hex,
+    "color(#f00)(a) color(#f00a)(b) color(#ff0000)(c) color(#ff0000aa)(d) color(#12345)(e) color(#)(f)"
+);
+test_snap!(math_caligraphy, r#"cc "AaBbCc""#);
+test_snap!(math_outlined, r#"bbb "AaBbCc""#);
+test_snap!(math_fraktur, r#"fr "AaBbCc""#);
+test_snap!(matrix_sq, "[[a,b],[c,d]]");
+test_snap!(matrix_aug, "[[a,b,|,c],[d,e,|,f]]");
+test_snap!(matrix_layout, "{(2x,+,17y,=,23),(x,-,y,=,5):}");
+test_snap!(vector, "((a),(b))");
+test_snap!(complex_subscripts, "lim_(N->oo) sum_(i=0)^N");
+test_snap!(integral, "int_0^1 f(x)dx");
+test_snap!(derivative, "f'(x) = dy/dx");
+
+test_snap!(
+    accents,
+    "hat x bar x ul x vec x tilde x dot x ddot x cancel x"
+);
+test_snap!(
+    unary_groupings,
+    "sqrt x abs(x) floor(x) ceil(x) norm(vec x)"
+);
+test_snap!(font_commands, "bb x bbb x cc x tt x fr x sf x");
+test_snap!(
+    binary,
+    "frac(a)(b) root(3)(x) overset(x)(=) underset(x)(=) color(#00ff00)(y)"
+);
+test_snap!(
+    groupings,
+    "(a) [b] {c} (:d:) {:e:} (f:} {:g] langle h rangle"
+);
+test_snap!(unpaired_groupings, "sqrt((f:}) frac(a)({:b]) {:a+b:}^2");
+test_snap!(commas_in_args, "sqrt(a, b) (a, b)");
+test_snap!(escapes, r#"a ; b "quoted \ text""#);
@@ -0,0 +1,47 @@
+use mathemascii::unicode_to_asciimath;
+
+#[test]
+fn convert_to_keywords() {
+    let cases = [
+        ("α ≤ β → ∞", "alpha <= beta -> oo"),
+        ("α≤β", "alpha<=beta"),
+        ("x∈ℝ", "x in RR"),
+        ("∀x ∃y", "AA x EE y"),
+        ("a×b·c", "a xx b*c"),
+        ("s∈n", "s in n"),
+        // `-` and `>` next to each other would become an arrow
+        ("−>", "- >"),
+        ("é + ☃", "é + ☃"),
+        (r#""α" text(β) α"#, r#""α" text(β) alpha"#),
+        ("color(ℝ→α)(α)", "color(ℝ→α)(alpha)"),
+        ("color(r(g)b)(α)", "color(r(g)b)(alpha)"),
+        ("", ""),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(
+            unicode_to_asciimath(input),
+            expected,
+            "converting {input:?}"
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "mathml")]
+fn converted_renders_the_same() {
+    for input in [
+        "∑_(i=0)^n α_i ≤ ∞",
+        "f: ℕ → ℝ, x ↦ x²",
+        "¬(p ∧ q) ⇔ ¬p ∨ ¬q",
+        "color(α→β)(x) color (≤(≥)) (y)",
+    ] {
+        let converted = unicode_to_asciimath(input);
+
+        assert_eq!(
+            mathemascii::render_mathml(mathemascii::parse(&converted)),
+            mathemascii::render_mathml(mathemascii::parse(input)),
+            "converting {input:?} into {converted:?}"
+        );
+    }
+}